@@ -0,0 +1,158 @@
+use anyhow::{Context, Result};
+use chrono::{Local, NaiveDateTime};
+use std::path::{Path, PathBuf};
+
+/// Backups kept per file; `clean` removes the oldest ones beyond this.
+pub const MAX_BACKUPS: usize = 5;
+
+const TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A copy of the database or config saved by `clean` before deleting it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// File name of the original, e.g. `timeguru.db` or `config.toml`.
+    pub original_name: String,
+    pub created: NaiveDateTime,
+}
+
+/// Directory holding the backups, next to the database.
+pub fn backup_dir() -> PathBuf {
+    crate::db::Database::default_path()
+        .parent()
+        .map(|dir| dir.join("backups"))
+        .unwrap_or_else(|| PathBuf::from("backups"))
+}
+
+/// Copies `source` into `dir` as `<file name>.bak-<timestamp>` and returns the copy's path.
+pub fn create_backup(source: &Path, dir: &Path, now: NaiveDateTime) -> Result<PathBuf> {
+    let name = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("Invalid file name: {}", source.display()))?;
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create backup directory {}", dir.display()))?;
+
+    let target = dir.join(format!("{}.bak-{}", name, now.format(TIMESTAMP_FORMAT)));
+    std::fs::copy(source, &target).with_context(|| {
+        format!(
+            "Failed to back up {} to {}",
+            source.display(),
+            target.display()
+        )
+    })?;
+    Ok(target)
+}
+
+fn parse_backup_name(file_name: &str) -> Option<(String, NaiveDateTime)> {
+    let (original, stamp) = file_name.rsplit_once(".bak-")?;
+    let created = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()?;
+    (!original.is_empty()).then(|| (original.to_string(), created))
+}
+
+/// Backups found in `dir`, newest first. A missing directory has no backups.
+pub fn list_backups(dir: &Path) -> Result<Vec<Backup>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let (original_name, created) = parse_backup_name(entry.file_name().to_str()?)?;
+            Some(Backup {
+                path: entry.path(),
+                original_name,
+                created,
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| {
+        b.created
+            .cmp(&a.created)
+            .then(a.original_name.cmp(&b.original_name))
+    });
+    Ok(backups)
+}
+
+/// Deletes all but the newest `keep` backups of `original_name`, returning how many were removed.
+pub fn prune_backups(dir: &Path, original_name: &str, keep: usize) -> Result<usize> {
+    let stale: Vec<Backup> = list_backups(dir)?
+        .into_iter()
+        .filter(|b| b.original_name == original_name)
+        .skip(keep)
+        .collect();
+    for backup in &stale {
+        std::fs::remove_file(&backup.path)
+            .with_context(|| format!("Failed to remove old backup {}", backup.path.display()))?;
+    }
+    Ok(stale.len())
+}
+
+/// Backs up `source` and trims older backups of the same file to `MAX_BACKUPS`.
+pub fn backup_before_delete(source: &Path) -> Result<PathBuf> {
+    let dir = backup_dir();
+    let path = create_backup(source, &dir, Local::now().naive_local())?;
+    if let Some(name) = source.file_name().and_then(|n| n.to_str()) {
+        prune_backups(&dir, name, MAX_BACKUPS)?;
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, 7)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_backups_are_listed_newest_first_and_pruned() {
+        let root = std::env::temp_dir().join(format!(
+            "toggl-timeguru-test-{}-backups",
+            std::process::id()
+        ));
+        let dir = root.join("backups");
+        std::fs::create_dir_all(&root).unwrap();
+        let source = root.join("timeguru.db");
+        std::fs::write(&source, b"data").unwrap();
+
+        for hour in [9, 11, 10] {
+            create_backup(&source, &dir, at(hour)).unwrap();
+        }
+        std::fs::write(dir.join("unrelated.txt"), b"").unwrap();
+
+        let backups = list_backups(&dir).unwrap();
+        assert_eq!(
+            backups.iter().map(|b| b.created).collect::<Vec<_>>(),
+            vec![at(11), at(10), at(9)]
+        );
+        assert!(backups.iter().all(|b| b.original_name == "timeguru.db"));
+        assert_eq!(std::fs::read(&backups[0].path).unwrap(), b"data");
+
+        assert_eq!(prune_backups(&dir, "timeguru.db", 2).unwrap(), 1);
+        let remaining = list_backups(&dir).unwrap();
+        std::fs::remove_dir_all(&root).ok();
+        assert_eq!(
+            remaining.iter().map(|b| b.created).collect::<Vec<_>>(),
+            vec![at(11), at(10)]
+        );
+    }
+
+    #[test]
+    fn test_parse_backup_name() {
+        assert_eq!(
+            parse_backup_name("config-work.toml.bak-20250307-090000"),
+            Some(("config-work.toml".to_string(), at(9)))
+        );
+        assert_eq!(parse_backup_name("timeguru.db"), None);
+        assert_eq!(parse_backup_name("timeguru.db.bak-yesterday"), None);
+    }
+}
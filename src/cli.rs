@@ -1,48 +1,103 @@
 use chrono::{DateTime, TimeZone, Utc};
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "toggl-timeguru")]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
-    #[arg(short, long, help = "Toggl API token")]
+    #[arg(
+        short,
+        long,
+        help = "Toggl API token (overrides TOGGL_API_TOKEN and the saved config)"
+    )]
     pub api_token: Option<String>,
 
     #[arg(short = 'c', long, help = "Path to configuration file")]
     pub config: Option<String>,
 
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with = "config",
+        help = "Use the named profile (config-NAME.toml in the config directory)"
+    )]
+    pub profile: Option<String>,
+
     #[arg(short = 'v', long, help = "Enable verbose logging")]
     pub verbose: bool,
 
+    #[arg(
+        long,
+        default_value = "text",
+        value_name = "FORMAT",
+        help = "Log line format for the log file and stderr: text | json (one JSON object per line)"
+    )]
+    pub log_format: String,
+
+    #[arg(
+        short = 'w',
+        long,
+        global = true,
+        value_name = "ID",
+        help = "Workspace ID to use for this invocation (overrides config; filters list/export)"
+    )]
+    pub workspace: Option<i64>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Print the request URL, status and response body of failed API calls to stderr"
+    )]
+    pub verbose_errors: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
-#[derive(Subcommand)]
+#[derive(Subcommand, Clone)]
 pub enum Commands {
     #[command(about = "Configure the application (set API token, preferences)")]
-    Config {
-        #[arg(long, help = "Set Toggl API token")]
-        set_token: Option<String>,
+    Config(Box<ConfigArgs>),
+
+    #[command(about = "Print a day's work as standup notes, grouped by project")]
+    Standup {
+        #[arg(
+            short,
+            long,
+            default_value = "today",
+            help = "Day to summarize: YYYY-MM-DD, today, yesterday or -Nd"
+        )]
+        date: String,
 
-        #[arg(long, help = "Set default date range in days")]
-        set_date_range: Option<i64>,
+        #[arg(long, help = "Also copy the notes to the clipboard")]
+        copy: bool,
 
-        #[arg(long, help = "Set rounding duration in minutes")]
-        set_round_minutes: Option<i64>,
+        #[arg(long, help = "Use cached data (offline mode)")]
+        offline: bool,
 
         #[arg(
             long,
-            help = "Set project selector sort method (name or usage)",
-            value_name = "METHOD"
+            value_name = "MINUTES",
+            conflicts_with = "no_round",
+            help = "Round durations up to this many minutes (overrides config)"
         )]
-        set_project_sort: Option<String>,
+        round: Option<i64>,
 
-        #[arg(long, help = "Show current configuration")]
-        show: bool,
+        #[arg(long, help = "Disable rounding for this run (overrides config)")]
+        no_round: bool,
+    },
+
+    #[command(about = "Show today's entries, the running timer and today's total")]
+    Today {
+        #[arg(long, help = "Show yesterday instead of today")]
+        yesterday: bool,
+
+        #[arg(long, help = "Use cached data (offline mode)")]
+        offline: bool,
     },
 
     #[command(about = "List time entries")]
+    #[command(group(ArgGroup::new("grouping").args(["group", "group_by"])))]
     List {
         #[arg(short, long, help = "Start date (ISO 8601 format or YYYY-MM-DD)")]
         start: Option<String>,
@@ -50,17 +105,133 @@ pub enum Commands {
         #[arg(short, long, help = "End date (ISO 8601 format or YYYY-MM-DD)")]
         end: Option<String>,
 
-        #[arg(short, long, help = "Filter by project ID")]
-        project: Option<i64>,
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            help = "Filter by project ID (repeat or comma-separate for several)"
+        )]
+        project: Vec<i64>,
+
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["start", "end"],
+            help = "Use a date range saved with config --add-range"
+        )]
+        range: Option<String>,
 
         #[arg(short = 't', long, help = "Filter by tag")]
         tag: Option<String>,
 
+        #[arg(
+            long = "tag-id",
+            value_name = "ID",
+            value_delimiter = ',',
+            help = "Filter by tag ID, stable across renames (repeat or comma-separate for several)"
+        )]
+        tag_ids: Vec<i64>,
+
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            help = "Only include entries whose description matches this regular expression"
+        )]
+        description_regex: Option<String>,
+
+        #[arg(
+            long,
+            requires = "description_regex",
+            help = "Match --description-regex case-insensitively"
+        )]
+        case_insensitive: bool,
+
         #[arg(short = 'g', long, help = "Group entries by description")]
         group: bool,
 
+        #[arg(
+            long,
+            help = "Group descriptions that differ only in case or surrounding whitespace (overrides config)"
+        )]
+        case_insensitive_group: bool,
+
+        #[arg(
+            long,
+            value_name = "MODE",
+            help = "Group entries by: description | client | tag"
+        )]
+        group_by: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "grouping",
+            help = "Only show the N longest groups (requires --group or --group-by)"
+        )]
+        top: Option<usize>,
+
+        #[arg(
+            long,
+            value_name = "MINUTES",
+            requires = "grouping",
+            help = "Merge groups shorter than this into a single (other) row (requires --group or --group-by)"
+        )]
+        merge_below: Option<i64>,
+
+        #[arg(
+            long,
+            value_name = "ORDER",
+            help = "Sort entries or groups by: date-asc | date-desc | duration | description"
+        )]
+        sort: Option<String>,
+
+        #[arg(
+            long,
+            help = "Only show entries whose timer is still running, with their elapsed time"
+        )]
+        only_running: bool,
+
+        #[arg(
+            long,
+            value_name = "AGE",
+            help = "Only show entries that started within this long ago, e.g. 12h, 2d, 3w (within the listed range)"
+        )]
+        newer_than: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "AGE",
+            help = "Only show entries that started more than this long ago, e.g. 30d (within the listed range)"
+        )]
+        older_than: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            help = "Only include entries lasting at least this long, e.g. 15m or 1h (inclusive)"
+        )]
+        min_duration: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            help = "Only include entries lasting at most this long, e.g. 1h (inclusive; running entries always pass)"
+        )]
+        max_duration: Option<String>,
+
         #[arg(long, help = "Use cached data (offline mode)")]
         offline: bool,
+
+        #[arg(
+            long,
+            value_name = "MINUTES",
+            conflicts_with = "no_round",
+            help = "Round durations up to this many minutes (overrides config)"
+        )]
+        round: Option<i64>,
+
+        #[arg(long, help = "Disable rounding for this run (overrides config)")]
+        no_round: bool,
     },
 
     #[command(about = "Sync time entries from Toggl to local database")]
@@ -70,6 +241,56 @@ pub enum Commands {
 
         #[arg(short, long, help = "End date for sync")]
         end: Option<String>,
+
+        #[arg(long, help = "Only sync time entries, skip projects")]
+        entries_only: bool,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["entries_only", "start", "end"],
+            help = "Only sync projects, skip time entries"
+        )]
+        projects_only: bool,
+
+        #[arg(
+            long,
+            conflicts_with = "projects_only",
+            help = "Remove cached entries in the synced range that were deleted on Toggl"
+        )]
+        reconcile: bool,
+
+        #[arg(
+            long,
+            help = "Sync every database write to disk and checkpoint when done (slower, crash-safe)"
+        )]
+        durable: bool,
+
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Maximum simultaneous API requests (overrides config)"
+        )]
+        concurrency: Option<usize>,
+
+        #[arg(
+            long,
+            value_name = "DAYS",
+            help = "Days of entries fetched per request (overrides sync_chunk_days)"
+        )]
+        chunk_days: Option<i64>,
+
+        #[arg(
+            long,
+            conflicts_with = "wipe_data",
+            help = "On an account switch, keep the previous account's cached data without asking"
+        )]
+        keep_data: bool,
+
+        #[arg(
+            long,
+            help = "On an account switch, delete the previous account's cached data without asking"
+        )]
+        wipe_data: bool,
     },
 
     #[command(about = "Interactive TUI mode")]
@@ -100,6 +321,14 @@ pub enum Commands {
         #[arg(short, long, help = "End date (ISO 8601 or YYYY-MM-DD)")]
         end: Option<String>,
 
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["start", "end"],
+            help = "Use a date range saved with config --add-range"
+        )]
+        range: Option<String>,
+
         #[arg(long, help = "Use cached data (offline mode)")]
         offline: bool,
 
@@ -138,6 +367,18 @@ pub enum Commands {
 
         #[arg(long, help = "Skip confirmation prompt")]
         confirm: bool,
+
+        #[arg(long, help = "Delete without saving a backup copy first")]
+        no_backup: bool,
+    },
+
+    #[command(about = "List backups made by clean, or restore one")]
+    Restore {
+        #[arg(help = "Number of the backup to restore, as shown in the list")]
+        index: Option<usize>,
+
+        #[arg(long, help = "Overwrite an existing file without asking")]
+        confirm: bool,
     },
 
     #[command(about = "Export time entries to CSV format")]
@@ -148,17 +389,183 @@ pub enum Commands {
         #[arg(short, long, help = "End date")]
         end: Option<String>,
 
-        #[arg(short, long, help = "Output file path")]
-        output: String,
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["start", "end"],
+            help = "Use a date range saved with config --add-range"
+        )]
+        range: Option<String>,
 
-        #[arg(long, help = "Include metadata header in export")]
+        #[arg(
+            short,
+            long,
+            help = "Output file path (defaults to stdout; '-' also writes to stdout)"
+        )]
+        output: Option<String>,
+
+        #[arg(long, conflicts_with = "output", help = "Write the export to stdout")]
+        stdout: bool,
+
+        #[arg(
+            long,
+            value_name = "KEY",
+            requires = "output_dir",
+            conflicts_with_all = ["output", "stdout", "group", "group_by_day", "group_by", "include_metadata"],
+            help = "Write one CSV per value of KEY into --output-dir (project | month)"
+        )]
+        split_by: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            requires = "split_by",
+            help = "Directory for the files written by --split-by"
+        )]
+        output_dir: Option<String>,
+
+        #[arg(
+            long,
+            default_value = "csv",
+            value_name = "FORMAT",
+            help = "Output format: csv | parquet (parquet needs the `parquet` build feature and --output)"
+        )]
+        format: String,
+
+        #[arg(long, help = "Include export metadata (date range, user, entry count)")]
         include_metadata: bool,
 
+        #[arg(
+            long,
+            conflicts_with = "include_metadata",
+            help = "Leave out export metadata even when config enables it"
+        )]
+        no_metadata: bool,
+
+        #[arg(
+            long,
+            conflicts_with = "include_metadata",
+            help = "Leave out the CSV header row (for appending to existing files)"
+        )]
+        no_header: bool,
+
+        #[arg(
+            long,
+            default_value = "sidecar",
+            value_name = "STYLE",
+            help = "Where metadata goes: sidecar (<output>.meta.json) | comment (# lines) | inline (# rows padded as CSV cells)"
+        )]
+        metadata_style: String,
+
         #[arg(long, help = "Group entries by description")]
         group: bool,
 
         #[arg(long, help = "Group entries by description and day")]
         group_by_day: bool,
+
+        #[arg(
+            long,
+            requires = "group_by_day",
+            help = "Split entries that cross midnight so each day gets its share (requires --group-by-day)"
+        )]
+        split_midnight: bool,
+
+        #[arg(
+            long,
+            help = "Group descriptions that differ only in case or surrounding whitespace (overrides config)"
+        )]
+        case_insensitive_group: bool,
+
+        #[arg(
+            long,
+            value_name = "MODE",
+            conflicts_with_all = ["group", "group_by_day"],
+            help = "Group entries by: description | client | tag"
+        )]
+        group_by: Option<String>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["group", "group_by_day", "group_by"],
+            help = "Export individual entries even when config sets a default grouping"
+        )]
+        no_group: bool,
+
+        #[arg(
+            long,
+            value_name = "MINUTES",
+            help = "Merge groups shorter than this into a single (other) row (requires a grouping option)"
+        )]
+        merge_below: Option<i64>,
+
+        #[arg(
+            long,
+            value_name = "ORDER",
+            help = "Sort entries or groups by: date-asc | date-desc | duration | description"
+        )]
+        sort: Option<String>,
+
+        #[arg(long, help = "Include the project's hex color as a Color column")]
+        include_color: bool,
+
+        #[arg(
+            long = "tag-id",
+            value_name = "ID",
+            value_delimiter = ',',
+            help = "Filter by tag ID, stable across renames (repeat or comma-separate for several)"
+        )]
+        tag_ids: Vec<i64>,
+
+        #[arg(
+            long,
+            value_name = "PATTERN",
+            help = "Only include entries whose description matches this regular expression"
+        )]
+        description_regex: Option<String>,
+
+        #[arg(
+            long,
+            requires = "description_regex",
+            help = "Match --description-regex case-insensitively"
+        )]
+        case_insensitive: bool,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            help = "Only include entries lasting at least this long, e.g. 15m or 1h (inclusive)"
+        )]
+        min_duration: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "DURATION",
+            help = "Only include entries lasting at most this long, e.g. 1h (inclusive; running entries always pass)"
+        )]
+        max_duration: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "MINUTES",
+            conflicts_with = "no_round",
+            help = "Round durations up to this many minutes (overrides config)"
+        )]
+        round: Option<i64>,
+        #[arg(long, help = "Disable rounding for this run (overrides config)")]
+        no_round: bool,
+
+        #[arg(
+            long,
+            conflicts_with = "no_round",
+            help = "With rounding active, add a Raw (hours) column next to the rounded Duration (hours); ungrouped entries are rounded too"
+        )]
+        with_raw: bool,
+
+        #[arg(
+            long,
+            help = "Excel-friendly CSV: UTF-8 BOM, every field quoted, CRLF rows, local date and time columns"
+        )]
+        excel: bool,
     },
 
     #[command(about = "Start or stop time tracking")]
@@ -166,20 +573,498 @@ pub enum Commands {
         #[command(subcommand)]
         action: TrackAction,
     },
+
+    #[command(about = "Detect duplicate time entries and invalid durations in the local database")]
+    Doctor {
+        #[arg(short, long, help = "Start date (ISO 8601 format or YYYY-MM-DD)")]
+        start: Option<String>,
+
+        #[arg(short, long, help = "End date (ISO 8601 format or YYYY-MM-DD)")]
+        end: Option<String>,
+
+        #[arg(
+            long,
+            help = "Interactively merge duplicates (keeps the longest entry, deletes the rest)"
+        )]
+        merge: bool,
+    },
+
+    #[command(about = "List cached projects")]
+    Projects {
+        #[arg(short, long, help = "Include archived projects")]
+        all: bool,
+
+        #[arg(long, help = "Print projects as JSON")]
+        json: bool,
+    },
+
+    #[command(about = "Print the JSON Schema of a command's --json output")]
+    Schema {
+        #[arg(help = "Command whose --json output to describe: projects")]
+        command: String,
+    },
+
+    #[command(about = "List active projects with no recent time entries")]
+    StaleProjects {
+        #[arg(
+            short,
+            long,
+            default_value_t = 30,
+            help = "Treat projects unused for this many days as stale"
+        )]
+        days: i64,
+    },
+
+    #[command(about = "Assign a project to cached entries whose description matches")]
+    Assign {
+        #[arg(short = 'P', long, help = "Project ID or name to assign")]
+        project: String,
+
+        #[arg(
+            short,
+            long,
+            help = "Case-insensitive substring to match against descriptions"
+        )]
+        description: String,
+
+        #[arg(short, long, help = "Start date (ISO 8601 format or YYYY-MM-DD)")]
+        start: Option<String>,
+
+        #[arg(short, long, help = "End date (ISO 8601 format or YYYY-MM-DD)")]
+        end: Option<String>,
+
+        #[arg(long, help = "Only list matching entries, do not change anything")]
+        dry_run: bool,
+
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Maximum simultaneous API requests (overrides config)"
+        )]
+        concurrency: Option<usize>,
+    },
+
+    #[command(
+        about = "Change the description, project, billable flag or broken duration of a cached entry"
+    )]
+    Edit {
+        #[arg(help = "ID of the time entry to edit")]
+        id: i64,
+
+        #[arg(short, long, help = "New description")]
+        description: Option<String>,
+
+        #[arg(
+            short = 'P',
+            long,
+            conflicts_with = "project_name",
+            help = "Project ID to assign"
+        )]
+        project: Option<i64>,
+
+        #[arg(long, help = "Project name to assign (looked up in the local cache)")]
+        project_name: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "BOOL",
+            help = "Set whether the entry is billable (true/false)"
+        )]
+        billable: Option<bool>,
+
+        #[arg(
+            long,
+            help = "Recompute a zero or negative duration from the entry's start and stop"
+        )]
+        fix_duration: bool,
+    },
+
+    #[command(about = "Show the configured account and a summary of the local database")]
+    Info,
 }
 
-#[derive(Subcommand)]
+/// Options of the `config` command, boxed in `Commands` because there are so many of them.
+#[derive(Args, Clone)]
+pub struct ConfigArgs {
+    #[arg(long, help = "Set Toggl API token")]
+    pub set_token: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "set_token",
+        help = "Remove the API token stored in the configuration"
+    )]
+    pub reset_token: bool,
+
+    #[arg(long, help = "Set default date range in days")]
+    pub set_date_range: Option<i64>,
+
+    #[arg(long, help = "Set rounding duration in minutes")]
+    pub set_round_minutes: Option<i64>,
+
+    #[arg(
+        long,
+        help = "Set project selector sort method (name or usage)",
+        value_name = "METHOD"
+    )]
+    pub set_project_sort: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set the created_with value sent when starting time entries",
+        value_name = "NAME"
+    )]
+    pub set_created_with: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set the API base URL including its version, e.g. https://api.track.toggl.com/api/v10 (empty restores the default; TOGGL_API_BASE_URL overrides)",
+        value_name = "URL"
+    )]
+    pub set_api_base_url: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set the default workspace ID used when starting time entries",
+        value_name = "ID"
+    )]
+    pub set_workspace: Option<i64>,
+
+    #[arg(
+        long,
+        help = "Set the window size in days used to split large syncs",
+        value_name = "DAYS"
+    )]
+    pub set_sync_chunk_days: Option<i64>,
+
+    #[arg(
+        long,
+        help = "Set how durations are displayed (decimal or hm)",
+        value_name = "FORMAT"
+    )]
+    pub set_duration_format: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set the grouping the TUI starts with (none, description or day)",
+        value_name = "MODE"
+    )]
+    pub set_tui_grouping: Option<String>,
+
+    #[arg(
+        long,
+        help = "Check the server for newer changes before TUI edits overwrite an entry",
+        value_name = "BOOL"
+    )]
+    pub set_check_conflicts: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Group descriptions that differ only in case or surrounding whitespace (list, export, TUI)",
+        value_name = "BOOL"
+    )]
+    pub set_case_insensitive_group: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Trim and collapse whitespace in descriptions when caching entries",
+        value_name = "BOOL"
+    )]
+    pub set_normalize_descriptions: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Include metadata in exports unless --no-metadata is given",
+        value_name = "BOOL"
+    )]
+    pub set_export_include_metadata: Option<bool>,
+
+    #[arg(
+        long,
+        help = "Set the grouping export uses when no grouping flag is given (none, description, day, client or tag)",
+        value_name = "MODE"
+    )]
+    pub set_export_group_by: Option<String>,
+
+    #[arg(
+        long,
+        help = "Add a favorite description for quick timer starts",
+        value_name = "DESCRIPTION"
+    )]
+    pub add_favorite: Option<String>,
+
+    #[arg(
+        long,
+        requires = "add_favorite",
+        help = "Project ID to attach to the favorite being added",
+        value_name = "ID"
+    )]
+    pub favorite_project: Option<i64>,
+
+    #[arg(
+        long,
+        help = "Remove a favorite by its number (see --show) or description",
+        value_name = "FAVORITE"
+    )]
+    pub remove_favorite: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set the description used by `track start` without --message; supports {date}, {time} and {weekday} (empty clears)",
+        value_name = "TEMPLATE"
+    )]
+    pub set_default_description: Option<String>,
+
+    #[arg(
+        long,
+        help = "Set how often the TUI re-checks the running timer (0 disables)",
+        value_name = "SECONDS"
+    )]
+    pub set_timer_refresh: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Set how many days of daily log files to keep (0 keeps all)",
+        value_name = "DAYS"
+    )]
+    pub set_log_retention: Option<u32>,
+
+    #[arg(
+        long,
+        help = "Set how many API requests batch operations may run at once",
+        value_name = "N"
+    )]
+    pub set_max_concurrency: Option<usize>,
+
+    #[arg(
+        long,
+        help = "Set the hours to track each week, shown in the TUI and reports (0 clears)",
+        value_name = "HOURS"
+    )]
+    pub set_weekly_target: Option<f64>,
+
+    #[arg(
+        long,
+        help = "Set the hours to track each day, shown by `today` (0 clears)",
+        value_name = "HOURS"
+    )]
+    pub set_daily_target: Option<f64>,
+
+    #[arg(
+        long,
+        requires_all = ["range_start", "range_end"],
+        help = "Save a named date range for --range (use with --range-start and --range-end)",
+        value_name = "NAME"
+    )]
+    pub add_range: Option<String>,
+
+    #[arg(
+        long,
+        requires = "add_range",
+        allow_hyphen_values = true,
+        help = "Start of the range being added: YYYY-MM-DD, today, yesterday, -Nd, -Nw, month-start, last-month-start, last-month-end or year-start",
+        value_name = "DATE"
+    )]
+    pub range_start: Option<String>,
+
+    #[arg(
+        long,
+        requires = "add_range",
+        allow_hyphen_values = true,
+        help = "End of the range being added (same forms as --range-start)",
+        value_name = "DATE"
+    )]
+    pub range_end: Option<String>,
+
+    #[arg(long, help = "Remove a saved date range", value_name = "NAME")]
+    pub remove_range: Option<String>,
+
+    #[arg(
+        long,
+        requires = "template_text",
+        help = "Save a named description template for track start --template (use with --template-text)",
+        value_name = "NAME"
+    )]
+    pub add_template: Option<String>,
+
+    #[arg(
+        long,
+        requires = "add_template",
+        help = "Text of the template being added, e.g. \"{project}: {task}\" ({date}, {time} and {weekday} fill themselves)",
+        value_name = "TEXT"
+    )]
+    pub template_text: Option<String>,
+
+    #[arg(long, help = "Remove a description template", value_name = "NAME")]
+    pub remove_template: Option<String>,
+
+    #[arg(long, help = "Show current configuration")]
+    pub show: bool,
+
+    #[arg(long, help = "Print the resolved configuration file path")]
+    pub path: bool,
+
+    #[arg(long, help = "List the named profiles in the config directory")]
+    pub list_profiles: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Create a named profile with default settings"
+    )]
+    pub new_profile: Option<String>,
+
+    #[arg(long, help = "Print the resolved database file path")]
+    pub db_path: bool,
+}
+
+#[derive(Subcommand, Clone)]
 pub enum TrackAction {
     #[command(about = "Start a new time entry")]
     Start {
         #[arg(short, long, help = "Description for the time entry")]
         message: Option<String>,
+
+        #[arg(
+            short,
+            long,
+            conflicts_with = "message",
+            value_name = "N",
+            help = "Start from favorite number N (see config --show)"
+        )]
+        favorite: Option<usize>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["message", "favorite"],
+            value_name = "NAME",
+            help = "Build the description from a saved template, prompting for missing placeholders"
+        )]
+        template: Option<String>,
+
+        #[arg(
+            long = "var",
+            requires = "template",
+            value_name = "KEY=VALUE",
+            help = "Value for a template placeholder (repeatable)"
+        )]
+        vars: Vec<String>,
+
+        #[arg(
+            long,
+            help = "Override the created_with value reported to Toggl",
+            value_name = "NAME"
+        )]
+        created_with: Option<String>,
     },
 
     #[command(about = "Stop the currently running time entry")]
     Stop,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataStyle {
+    Sidecar,
+    Comment,
+    Inline,
+}
+
+impl std::str::FromStr for MetadataStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "sidecar" => Ok(Self::Sidecar),
+            "comment" => Ok(Self::Comment),
+            "inline" => Ok(Self::Inline),
+            other => Err(anyhow::anyhow!(
+                "invalid metadata style '{other}', expected 'sidecar', 'comment' or 'inline'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    Description,
+    Client,
+    Tag,
+}
+
+impl std::str::FromStr for GroupBy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "description" => Ok(Self::Description),
+            "client" => Ok(Self::Client),
+            "tag" => Ok(Self::Tag),
+            other => Err(anyhow::anyhow!(
+                "invalid grouping '{other}', expected 'description', 'client' or 'tag'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            other => Err(anyhow::anyhow!(
+                "invalid log format '{other}', expected 'text' or 'json'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "parquet" => Ok(Self::Parquet),
+            other => Err(anyhow::anyhow!(
+                "invalid export format '{other}', expected 'csv' or 'parquet'"
+            )),
+        }
+    }
+}
+
+/// Commands with machine-readable (`--json`) output that `schema` can describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaTarget {
+    Projects,
+}
+
+impl std::str::FromStr for SchemaTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "projects" => Ok(Self::Projects),
+            other => Err(anyhow::anyhow!(
+                "no JSON output schema for '{other}', expected 'projects'"
+            )),
+        }
+    }
+}
+
 impl Cli {
     pub fn parse_date(date_str: &str) -> anyhow::Result<DateTime<Utc>> {
         if let Ok(dt) = DateTime::parse_from_rfc3339(date_str) {
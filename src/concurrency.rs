@@ -0,0 +1,50 @@
+use futures_util::stream::{self, StreamExt};
+use std::future::Future;
+
+/// Runs `futures` with at most `limit` of them in flight, returning their outputs in input order.
+///
+/// Batch operations go through this so the number of simultaneous API requests stays under the
+/// configured `max_concurrency` and large runs don't trip Toggl's rate limits.
+pub async fn run_bounded<I, F, T>(futures: I, limit: usize) -> Vec<T>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = T>,
+{
+    stream::iter(futures).buffered(limit.max(1)).collect().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_run_bounded_caps_in_flight_futures_and_keeps_order() {
+        let in_flight = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+
+        let futures = (0..10).map(|i| {
+            let in_flight = &in_flight;
+            let peak = &peak;
+            async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                i
+            }
+        });
+
+        let results = run_bounded(futures, 3).await;
+
+        assert_eq!(results, (0..10).collect::<Vec<_>>());
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_run_bounded_treats_zero_limit_as_sequential() {
+        let results = run_bounded((0..3).map(|i| async move { i * 2 }), 0).await;
+        assert_eq!(results, vec![0, 2, 4]);
+    }
+}
@@ -1,5 +1,12 @@
-use chrono::Duration;
+use anyhow::Context;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeDelta};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Config file given with `--config`; set once at startup, before anything loads the config.
+static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Config {
@@ -13,6 +20,161 @@ pub struct Config {
     pub project_sort_method: ProjectSortMethod,
     #[serde(default)]
     pub saved_filter: PersistedFilter,
+    #[serde(default)]
+    pub created_with: Option<String>,
+    #[serde(default)]
+    pub default_workspace_id: Option<i64>,
+    #[serde(default = "default_sync_chunk_days")]
+    pub sync_chunk_days: i64,
+    #[serde(default)]
+    pub duration_format: DurationFormat,
+    /// Grouping the TUI starts with.
+    #[serde(default)]
+    pub tui_grouping: TuiGrouping,
+    /// Re-fetch entries before TUI edits and ask before overwriting ones changed on the server.
+    #[serde(default)]
+    pub check_conflicts: bool,
+    /// Group descriptions that differ only in case or surrounding whitespace together.
+    #[serde(default)]
+    pub case_insensitive_grouping: bool,
+    /// Trim and collapse whitespace in descriptions when caching entries.
+    #[serde(default)]
+    pub normalize_descriptions: bool,
+    #[serde(default)]
+    pub favorites: Vec<Favorite>,
+    /// Description used by `track start` when no message is given; supports placeholders.
+    #[serde(default)]
+    pub default_description: Option<String>,
+    /// How often the TUI re-checks the running timer, in seconds; 0 disables polling.
+    #[serde(default = "default_timer_refresh_seconds")]
+    pub timer_refresh_seconds: u64,
+    /// Upper bound on simultaneous API requests during batch operations.
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// How many days of daily log files, today included, survive the startup cleanup; 0 keeps
+    /// them all.
+    #[serde(default = "default_log_retention_days")]
+    pub log_retention_days: u32,
+    /// Named date ranges usable as `--range NAME`, keyed by lowercase name.
+    #[serde(default)]
+    pub saved_ranges: BTreeMap<String, SavedRange>,
+    /// Hours to aim for each Monday-to-Sunday week; progress shows in the TUI and reports.
+    #[serde(default)]
+    pub weekly_target_hours: Option<f64>,
+    /// Hours to aim for each day; progress shows in `today`.
+    #[serde(default)]
+    pub daily_target_hours: Option<f64>,
+    /// API base including the version segment; `None` uses the public v9 API.
+    #[serde(default)]
+    pub api_base_url: Option<String>,
+    /// Projects most recently assigned in the TUI, newest first; pinned atop the project selector.
+    #[serde(default)]
+    pub recent_project_ids: Vec<i64>,
+    /// Named description templates for `track start --template`, keyed by lowercase name.
+    #[serde(default)]
+    pub description_templates: BTreeMap<String, String>,
+    /// Add export metadata when `export` is run without `--include-metadata` or `--no-metadata`.
+    #[serde(default)]
+    pub export_include_metadata: bool,
+    /// Grouping `export` uses when no grouping flag is given.
+    #[serde(default)]
+    pub export_group_by: ExportGrouping,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Favorite {
+    pub description: String,
+    #[serde(default)]
+    pub project_id: Option<i64>,
+}
+
+/// A named date range; each bound is a date or a relative expression resolved when it is used.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SavedRange {
+    pub start: String,
+    pub end: String,
+}
+
+impl SavedRange {
+    /// Resolves both bounds against `today`, rejecting a range that ends before it starts.
+    pub fn resolve(&self, today: NaiveDate) -> anyhow::Result<(NaiveDate, NaiveDate)> {
+        let start = resolve_date_expr(&self.start, today)?;
+        let end = resolve_date_expr(&self.end, today)?;
+        if start > end {
+            anyhow::bail!("range start {} is after its end {}", start, end);
+        }
+        Ok((start, end))
+    }
+}
+
+/// Resolves `YYYY-MM-DD`, `today`, `yesterday`, `-Nd`, `-Nw`, `month-start`, `last-month-start`,
+/// `last-month-end` and `year-start` to a date relative to `today`.
+pub fn resolve_date_expr(expr: &str, today: NaiveDate) -> anyhow::Result<NaiveDate> {
+    let expr = expr.trim().to_lowercase();
+    let month_start = today.with_day(1).unwrap_or(today);
+    let last_month_end = month_start - Duration::days(1);
+
+    let date = match expr.as_str() {
+        "today" => today,
+        "yesterday" => today - Duration::days(1),
+        "month-start" => month_start,
+        "last-month-start" => last_month_end.with_day(1).unwrap_or(last_month_end),
+        "last-month-end" => last_month_end,
+        "year-start" => today.with_ordinal(1).unwrap_or(today),
+        other => {
+            if let Some(span) = other
+                .strip_prefix('-')
+                .filter(|span| span.ends_with(['d', 'w']))
+            {
+                parse_span(span)
+                    .and_then(|span| today.checked_sub_signed(span))
+                    .ok_or_else(|| invalid_date_expr(other))?
+            } else {
+                NaiveDate::parse_from_str(other, "%Y-%m-%d")
+                    .map_err(|_| invalid_date_expr(other))?
+            }
+        }
+    };
+    Ok(date)
+}
+
+/// Parses a non-negative count followed by `m`, `h`, `d` or `w`, such as `45m` or `3w`. Returns
+/// `None` for anything else, including spans too long to represent.
+pub fn parse_span(s: &str) -> Option<TimeDelta> {
+    let unit = s.chars().last()?;
+    let amount: i64 = s[..s.len() - unit.len_utf8()].parse().ok()?;
+    if amount < 0 {
+        return None;
+    }
+    match unit.to_ascii_lowercase() {
+        'm' => TimeDelta::try_minutes(amount),
+        'h' => TimeDelta::try_hours(amount),
+        'd' => TimeDelta::try_days(amount),
+        'w' => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+}
+
+fn invalid_date_expr(expr: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "invalid date '{expr}', expected YYYY-MM-DD, today, yesterday, -Nd, -Nw, month-start, last-month-start, last-month-end or year-start"
+    )
+}
+
+fn default_sync_chunk_days() -> i64 {
+    90
+}
+
+fn default_timer_refresh_seconds() -> u64 {
+    30
+}
+
+fn default_log_retention_days() -> u32 {
+    7
+}
+
+fn default_max_concurrency() -> usize {
+    4
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -38,6 +200,77 @@ pub enum ProjectSortMethod {
     Usage,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DurationFormat {
+    #[default]
+    Decimal,
+    HoursMinutes,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TuiGrouping {
+    #[default]
+    None,
+    Description,
+    Day,
+}
+
+impl std::str::FromStr for TuiGrouping {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "description" => Ok(Self::Description),
+            "day" => Ok(Self::Day),
+            other => Err(anyhow::anyhow!(
+                "invalid TUI grouping '{other}', expected 'none', 'description' or 'day'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportGrouping {
+    #[default]
+    None,
+    Description,
+    Day,
+    Client,
+    Tag,
+}
+
+impl std::str::FromStr for ExportGrouping {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(Self::None),
+            "description" => Ok(Self::Description),
+            "day" => Ok(Self::Day),
+            "client" => Ok(Self::Client),
+            "tag" => Ok(Self::Tag),
+            other => Err(anyhow::anyhow!(
+                "invalid export grouping '{other}', expected 'none', 'description', 'day', 'client' or 'tag'"
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for DurationFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "decimal" => Ok(Self::Decimal),
+            "hm" | "hours-minutes" => Ok(Self::HoursMinutes),
+            other => Err(anyhow::anyhow!(
+                "invalid duration format '{other}', expected 'decimal' or 'hm'"
+            )),
+        }
+    }
+}
+
 impl std::str::FromStr for ProjectSortMethod {
     type Err = anyhow::Error;
 
@@ -63,21 +296,383 @@ impl Default for Config {
             current_user_email: None,
             project_sort_method: ProjectSortMethod::Name,
             saved_filter: PersistedFilter::default(),
+            created_with: None,
+            default_workspace_id: None,
+            sync_chunk_days: default_sync_chunk_days(),
+            duration_format: DurationFormat::Decimal,
+            tui_grouping: TuiGrouping::None,
+            check_conflicts: false,
+            case_insensitive_grouping: false,
+            normalize_descriptions: false,
+            favorites: Vec::new(),
+            default_description: None,
+            timer_refresh_seconds: default_timer_refresh_seconds(),
+            max_concurrency: default_max_concurrency(),
+            log_retention_days: default_log_retention_days(),
+            saved_ranges: BTreeMap::new(),
+            weekly_target_hours: None,
+            daily_target_hours: None,
+            api_base_url: None,
+            recent_project_ids: Vec::new(),
+            description_templates: BTreeMap::new(),
+            export_include_metadata: false,
+            export_group_by: ExportGrouping::None,
         }
     }
 }
 
+fn validate_profile_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Invalid profile name '{name}': use only letters, digits, '-' and '_'");
+    }
+    Ok(())
+}
+
+/// Extracts `name` from a `config-<name>.toml` file name.
+fn profile_name_from_file(file_name: &str) -> Option<&str> {
+    file_name
+        .strip_prefix("config-")
+        .and_then(|rest| rest.strip_suffix(".toml"))
+        .filter(|name| validate_profile_name(name).is_ok())
+}
+
 impl Config {
+    /// Makes every later `load`, `save` and `path` use `path` instead of the default location.
+    pub fn use_path(path: PathBuf) {
+        let _ = CONFIG_PATH.set(path);
+    }
+
     pub fn load() -> anyhow::Result<Self> {
-        Ok(confy::load("toggl-timeguru", "config")?)
+        match CONFIG_PATH.get() {
+            Some(path) => Self::load_from(path),
+            None => Ok(confy::load("toggl-timeguru", "config")?),
+        }
     }
 
     pub fn save(&self) -> anyhow::Result<()> {
-        confy::store("toggl-timeguru", "config", self)?;
-        Ok(())
+        match CONFIG_PATH.get() {
+            Some(path) => self.save_to(path),
+            None => {
+                confy::store("toggl-timeguru", "config", self)?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Loads the config from an explicit file, which must already exist.
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            anyhow::bail!("Configuration file {} does not exist", path.display());
+        }
+        confy::load_path(path)
+            .with_context(|| format!("Failed to read configuration file {}", path.display()))
+    }
+
+    pub fn save_to(&self, path: &Path) -> anyhow::Result<()> {
+        confy::store_path(path, self)
+            .with_context(|| format!("Failed to write configuration file {}", path.display()))
+    }
+
+    pub fn path() -> anyhow::Result<PathBuf> {
+        if let Some(path) = CONFIG_PATH.get() {
+            return Ok(path.clone());
+        }
+        Self::default_path()
+    }
+
+    /// Location of the default config file, ignoring `--config` and `--profile`.
+    pub fn default_path() -> anyhow::Result<PathBuf> {
+        Ok(confy::get_configuration_file_path(
+            "toggl-timeguru",
+            "config",
+        )?)
+    }
+
+    /// Path of the named profile: `config-<name>.toml` next to the default config file.
+    pub fn profile_path(name: &str) -> anyhow::Result<PathBuf> {
+        validate_profile_name(name)?;
+        let default = Self::default_path()?;
+        let dir = default
+            .parent()
+            .context("Configuration file path has no parent directory")?;
+        Ok(dir.join(format!("config-{name}.toml")))
+    }
+
+    /// Names of the profiles found next to the default config file, sorted.
+    pub fn list_profiles() -> anyhow::Result<Vec<String>> {
+        let default = Self::default_path()?;
+        let Some(dir) = default.parent() else {
+            return Ok(Vec::new());
+        };
+        let read_dir = match std::fs::read_dir(dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", dir.display()));
+            }
+        };
+
+        let mut names: Vec<String> = read_dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                profile_name_from_file(&entry.file_name().to_string_lossy()).map(str::to_string)
+            })
+            .collect();
+        names.sort();
+        Ok(names)
     }
 
     pub fn default_date_range(&self) -> Duration {
         Duration::days(self.default_date_range_days)
     }
+
+    pub fn saved_range(&self, name: &str) -> anyhow::Result<&SavedRange> {
+        self.saved_ranges
+            .get(&name.trim().to_lowercase())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No saved range named '{}'. Add one with: toggl-timeguru config --add-range NAME --range-start DATE --range-end DATE",
+                    name
+                )
+            })
+    }
+
+    pub fn description_template(&self, name: &str) -> anyhow::Result<&str> {
+        self.description_templates
+            .get(&name.trim().to_lowercase())
+            .map(String::as_str)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No template named '{}'. Add one with: toggl-timeguru config --add-template NAME --template-text TEXT",
+                    name
+                )
+            })
+    }
+
+    /// The configured default description with `{date}`, `{time}` and `{weekday}` expanded.
+    pub fn default_description_at(&self, now: DateTime<Local>) -> Option<String> {
+        self.default_description
+            .as_deref()
+            .map(|template| expand_description(template, now))
+    }
+}
+
+fn expand_description(template: &str, now: DateTime<Local>) -> String {
+    template
+        .replace("{date}", &now.format("%Y-%m-%d").to_string())
+        .replace("{time}", &now.format("%H:%M").to_string())
+        .replace("{weekday}", &now.format("%A").to_string())
+}
+
+/// Names of the `{name}` placeholders in `template`, in order of first appearance.
+/// The built-in `{date}`, `{time}` and `{weekday}` are included like any other name.
+pub fn template_placeholders(template: &str) -> anyhow::Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_ascii_alphanumeric() || c == '_' || c == '-' => {
+                            name.push(c)
+                        }
+                        Some(c) => anyhow::bail!(
+                            "invalid character '{c}' in placeholder in template '{template}'"
+                        ),
+                        None => anyhow::bail!("unclosed '{{' in template '{template}'"),
+                    }
+                }
+                if name.is_empty() {
+                    anyhow::bail!("empty placeholder '{{}}' in template '{template}'");
+                }
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+            '}' => anyhow::bail!("unmatched '}}' in template '{template}'"),
+            _ => {}
+        }
+    }
+
+    Ok(names)
+}
+
+/// Expands `{date}`, `{time}` and `{weekday}`, then fills every other placeholder from `values`.
+/// Fails if a placeholder has no value.
+pub fn fill_template(
+    template: &str,
+    values: &BTreeMap<String, String>,
+    now: DateTime<Local>,
+) -> anyhow::Result<String> {
+    let expanded = expand_description(template, now);
+    let placeholders = template_placeholders(&expanded)?;
+
+    let missing: Vec<&str> = placeholders
+        .iter()
+        .filter(|name| !values.contains_key(*name))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!("missing value for placeholder(s): {}", missing.join(", "));
+    }
+
+    Ok(placeholders.iter().fold(expanded, |text, name| {
+        text.replace(&format!("{{{name}}}"), &values[name])
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_profile_names() {
+        assert!(validate_profile_name("work_2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../work").is_err());
+
+        assert_eq!(profile_name_from_file("config-work.toml"), Some("work"));
+        assert_eq!(profile_name_from_file("config.toml"), None);
+        assert_eq!(profile_name_from_file("config-.toml"), None);
+        assert_eq!(profile_name_from_file("config-work.toml.bak"), None);
+    }
+
+    #[test]
+    fn test_explicit_config_path_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "toggl-timeguru-test-{}-config.toml",
+            std::process::id()
+        ));
+        std::fs::remove_file(&path).ok();
+        assert!(Config::load_from(&path).is_err());
+        assert!(!path.exists());
+
+        let config = Config {
+            sync_chunk_days: 30,
+            ..Config::default()
+        };
+        config.save_to(&path).unwrap();
+        let loaded = Config::load_from(&path).unwrap();
+        assert_eq!(loaded.sync_chunk_days, 30);
+
+        std::fs::write(&path, "sync_chunk_days = \"not a number\"").unwrap();
+        let err = Config::load_from(&path).unwrap_err().to_string();
+        std::fs::remove_file(&path).ok();
+        assert!(err.contains("Failed to read configuration file"));
+    }
+
+    #[test]
+    fn test_default_description_expands_placeholders() {
+        let now = Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap();
+        let config = Config {
+            default_description: Some("Standup {date} {time} ({weekday})".to_string()),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.default_description_at(now).as_deref(),
+            Some("Standup 2025-03-07 09:05 (Friday)")
+        );
+    }
+
+    #[test]
+    fn test_saved_range_resolves_relative_bounds() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        let last_month = SavedRange {
+            start: "last-month-start".to_string(),
+            end: "last-month-end".to_string(),
+        };
+        let recent = SavedRange {
+            start: "-2w".to_string(),
+            end: "Yesterday".to_string(),
+        };
+
+        assert_eq!(
+            last_month.resolve(today).unwrap(),
+            (
+                NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 2, 28).unwrap()
+            )
+        );
+        assert_eq!(
+            recent.resolve(today).unwrap(),
+            (
+                NaiveDate::from_ymd_opt(2025, 2, 21).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 3, 6).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_saved_range_rejects_reversed_or_invalid_bounds() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        let reversed = SavedRange {
+            start: "today".to_string(),
+            end: "2025-01-01".to_string(),
+        };
+        let invalid = SavedRange {
+            start: "last-week".to_string(),
+            end: "today".to_string(),
+        };
+
+        assert!(reversed.resolve(today).is_err());
+        assert!(invalid.resolve(today).is_err());
+    }
+
+    #[test]
+    fn test_relative_date_expr_out_of_range_is_an_error() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 7).unwrap();
+        assert_eq!(
+            resolve_date_expr("-3d", today).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 4).unwrap()
+        );
+        for expr in ["-9999999999d", "-9223372036854775807w", "--3d"] {
+            let err = resolve_date_expr(expr, today).unwrap_err();
+            assert!(err.to_string().starts_with("invalid date"), "{expr}");
+        }
+    }
+
+    #[test]
+    fn test_template_placeholders_are_scanned_in_order() {
+        assert_eq!(
+            template_placeholders("{project}: {task} ({project})").unwrap(),
+            vec!["project", "task"]
+        );
+        assert!(template_placeholders("plain text").unwrap().is_empty());
+        assert!(template_placeholders("{project").is_err());
+        assert!(template_placeholders("project}").is_err());
+        assert!(template_placeholders("{}").is_err());
+        assert!(template_placeholders("{two words}").is_err());
+    }
+
+    #[test]
+    fn test_fill_template_requires_every_value() {
+        let now = Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap();
+        let mut values = BTreeMap::new();
+        values.insert("project".to_string(), "Web".to_string());
+
+        let err = fill_template("{project}: {task}", &values, now).unwrap_err();
+        assert!(err.to_string().contains("task"));
+
+        values.insert("task".to_string(), "Login page".to_string());
+        assert_eq!(
+            fill_template("{project}: {task} on {date}", &values, now).unwrap(),
+            "Web: Login page on 2025-03-07"
+        );
+    }
+
+    #[test]
+    fn test_default_description_unset() {
+        let now = Local.with_ymd_and_hms(2025, 3, 7, 9, 5, 0).unwrap();
+        assert!(Config::default().default_description_at(now).is_none());
+    }
 }
@@ -1,45 +1,170 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use rusqlite::Connection;
-use std::path::PathBuf;
+use rusqlite::{Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-use super::schema::init_database;
-use crate::toggl::models::{Project, TimeEntry};
+use super::schema::{adopt_unowned_projects, init_database};
+use crate::processor::normalize_description;
+use crate::toggl::models::{Project, TimeEntry, Workspace};
 
 pub struct Database {
     conn: Mutex<Connection>,
+    normalize_descriptions: AtomicBool,
+}
+
+/// Usage summary for a single active project across all cached entries.
+#[derive(Debug, Clone)]
+pub struct ProjectActivity {
+    pub project_id: i64,
+    pub last_used: Option<DateTime<Utc>>,
+    pub total_seconds: i64,
+}
+
+/// A workspace remembered from the last sync, enough to pick one without calling the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedWorkspace {
+    pub id: i64,
+    pub name: String,
 }
 
 impl Database {
+    /// Location of the database used when no explicit path is given.
+    pub fn default_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("toggl-timeguru")
+            .join("timeguru.db")
+    }
+
     pub fn new(db_path: Option<PathBuf>) -> Result<Self> {
         let path = db_path.unwrap_or_else(|| {
-            let mut path = dirs::data_dir().unwrap_or_else(|| PathBuf::from("."));
-            path.push("toggl-timeguru");
-            std::fs::create_dir_all(&path).ok();
-            path.push("timeguru.db");
+            let path = Self::default_path();
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).ok();
+            }
             path
         });
 
         let conn = Connection::open(&path)
             .with_context(|| format!("Failed to open database at {:?}", path))?;
 
+        // WAL with NORMAL sync is fast and stays consistent after a crash, though the last
+        // commits may be lost on power failure; `enable_durable_writes` closes that gap.
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))
+            .context("Failed to enable write-ahead logging")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")
+            .context("Failed to set synchronous mode")?;
+
         init_database(&conn)?;
 
         Ok(Self {
             conn: Mutex::new(conn),
+            normalize_descriptions: AtomicBool::new(false),
+        })
+    }
+
+    /// Opens a fresh in-memory database so tests never touch the filesystem.
+    #[cfg(test)]
+    pub fn new_in_memory() -> Result<Self> {
+        let conn = Connection::open_in_memory().context("Failed to open in-memory database")?;
+
+        init_database(&conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            normalize_descriptions: AtomicBool::new(false),
+        })
+    }
+
+    /// The write-ahead log and shared-memory files SQLite keeps next to the database at `path`.
+    pub fn wal_paths(path: &Path) -> [PathBuf; 2] {
+        ["-wal", "-shm"].map(|suffix| {
+            let mut name = path.as_os_str().to_owned();
+            name.push(suffix);
+            PathBuf::from(name)
         })
     }
 
+    /// Makes SQLite sync the write-ahead log on every commit (`synchronous = FULL` instead of
+    /// `NORMAL`), so a commit survives a power failure, at the cost of write speed.
+    pub fn enable_durable_writes(&self) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        conn.pragma_update(None, "synchronous", "FULL")
+            .context("Failed to enable durable writes")?;
+
+        Ok(())
+    }
+
+    /// Copies the write-ahead log back into the database file and truncates it, leaving the
+    /// database file complete on its own. Does nothing for in-memory databases.
+    pub fn checkpoint(&self) -> Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let journal_mode: String = conn
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .context("Failed to read journal mode")?;
+        if journal_mode.eq_ignore_ascii_case("wal") {
+            conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+                .context("Failed to checkpoint the write-ahead log")?;
+        }
+
+        Ok(())
+    }
+
+    /// Gives projects cached without an owner to `user_id`, so they stay visible once an account
+    /// is configured. Returns how many were adopted.
+    pub fn adopt_unowned_projects(&self, user_id: i64) -> Result<usize> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        adopt_unowned_projects(&conn, user_id).context("Failed to assign legacy projects")
+    }
+
+    /// Makes `save_time_entries` trim descriptions and collapse runs of whitespace. A description
+    /// changed this way keeps its as-synced text in the `original_description` column.
+    pub fn set_normalize_descriptions(&self, enabled: bool) {
+        self.normalize_descriptions
+            .store(enabled, Ordering::Relaxed);
+    }
+
+    /// Upserts `entries` in a single transaction, so either all of them are saved or none are.
     pub fn save_time_entries(&self, entries: &[TimeEntry]) -> Result<usize> {
         let mut count = 0;
         let now = Utc::now().to_rfc3339();
-        let conn = self
+        let normalize = self.normalize_descriptions.load(Ordering::Relaxed);
+        let mut conn = self
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
 
+        let tx = conn.transaction()?;
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO time_entries
+            (id, workspace_id, project_id, task_id, billable, start, stop, duration,
+             description, tags, tag_ids, user_id, at, synced_at, original_description)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+        )?;
+
         for entry in entries {
+            let (description, original_description) = match &entry.description {
+                Some(desc) if normalize => {
+                    let normalized = normalize_description(desc);
+                    let original = (normalized != *desc).then(|| desc.clone());
+                    (Some(normalized), original)
+                }
+                other => (other.clone(), None),
+            };
             let tags_json = entry
                 .tags
                 .as_ref()
@@ -49,31 +174,29 @@ impl Database {
                 .as_ref()
                 .map(|t| serde_json::to_string(t).unwrap_or_default());
 
-            conn.execute(
-                "INSERT OR REPLACE INTO time_entries
-                (id, workspace_id, project_id, task_id, billable, start, stop, duration,
-                 description, tags, tag_ids, user_id, at, synced_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
-                rusqlite::params![
-                    entry.id,
-                    entry.workspace_id,
-                    entry.project_id,
-                    entry.task_id,
-                    entry.billable as i32,
-                    entry.start.to_rfc3339(),
-                    entry.stop.as_ref().map(|s| s.to_rfc3339()),
-                    entry.duration,
-                    entry.description,
-                    tags_json,
-                    tag_ids_json,
-                    entry.user_id,
-                    entry.at.to_rfc3339(),
-                    &now,
-                ],
-            )?;
+            stmt.execute(rusqlite::params![
+                entry.id,
+                entry.workspace_id,
+                entry.project_id,
+                entry.task_id,
+                entry.billable as i32,
+                entry.start.to_rfc3339(),
+                entry.stop.as_ref().map(|s| s.to_rfc3339()),
+                entry.duration,
+                description,
+                tags_json,
+                tag_ids_json,
+                entry.user_id,
+                entry.at.to_rfc3339(),
+                &now,
+                original_description,
+            ])
+            .with_context(|| format!("Failed to save time entry {}", entry.id))?;
             count += 1;
         }
 
+        drop(stmt);
+        tx.commit()?;
         Ok(count)
     }
 
@@ -90,13 +213,13 @@ impl Database {
 
         let query = if user_id.is_some() {
             "SELECT id, workspace_id, project_id, task_id, billable, start, stop, duration,
-                    description, tags, tag_ids, user_id, at
+                    description, tags, tag_ids, user_id, at, synced_at
              FROM time_entries
              WHERE start >= ?1 AND start <= ?2 AND user_id = ?3
              ORDER BY start DESC"
         } else {
             "SELECT id, workspace_id, project_id, task_id, billable, start, stop, duration,
-                    description, tags, tag_ids, user_id, at
+                    description, tags, tag_ids, user_id, at, synced_at
              FROM time_entries
              WHERE start >= ?1 AND start <= ?2
              ORDER BY start DESC"
@@ -104,46 +227,15 @@ impl Database {
 
         let mut stmt = conn.prepare(query)?;
 
-        let row_mapper = |row: &rusqlite::Row| {
-            let tags_str: Option<String> = row.get(9)?;
-            let tags = tags_str.and_then(|s| serde_json::from_str(&s).ok());
-
-            let tag_ids_str: Option<String> = row.get(10)?;
-            let tag_ids = tag_ids_str.and_then(|s| serde_json::from_str(&s).ok());
-
-            Ok(TimeEntry {
-                id: row.get(0)?,
-                workspace_id: row.get(1)?,
-                project_id: row.get(2)?,
-                task_id: row.get(3)?,
-                billable: row.get::<_, i32>(4)? != 0,
-                start: row.get::<_, String>(5)?.parse().unwrap(),
-                stop: row
-                    .get::<_, Option<String>>(6)?
-                    .and_then(|s| s.parse().ok()),
-                duration: row.get(7)?,
-                description: row.get(8)?,
-                tags,
-                tag_ids,
-                duronly: false,
-                at: row.get::<_, String>(12)?.parse().unwrap(),
-                server_deleted_at: None,
-                user_id: row.get(11)?,
-                uid: None,
-                wid: None,
-                pid: None,
-            })
-        };
-
         let entries = if let Some(uid) = user_id {
             stmt.query_map(
                 rusqlite::params![start_date.to_rfc3339(), end_date.to_rfc3339(), uid],
-                row_mapper,
+                time_entry_from_row,
             )?
         } else {
             stmt.query_map(
                 rusqlite::params![start_date.to_rfc3339(), end_date.to_rfc3339()],
-                row_mapper,
+                time_entry_from_row,
             )?
         };
 
@@ -152,40 +244,127 @@ impl Database {
             .context("Failed to parse time entries from database")
     }
 
-    pub fn save_projects(&self, projects: &[Project]) -> Result<usize> {
+    /// Looks up a single cached time entry by ID.
+    pub fn get_time_entry(&self, entry_id: i64) -> Result<Option<TimeEntry>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        conn.query_row(
+            "SELECT id, workspace_id, project_id, task_id, billable, start, stop, duration,
+                    description, tags, tag_ids, user_id, at, synced_at
+             FROM time_entries
+             WHERE id = ?1",
+            rusqlite::params![entry_id],
+            time_entry_from_row,
+        )
+        .optional()
+        .context("Failed to read time entry from database")
+    }
+
+    /// Caches projects as belonging to `user_id`, so another account's selector never shows them.
+    /// Upserts `projects` for `user_id` in a single transaction.
+    pub fn save_projects(&self, projects: &[Project], user_id: Option<i64>) -> Result<usize> {
         let mut count = 0;
         let now = Utc::now().to_rfc3339();
-        let conn = self
+        let mut conn = self
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
 
+        let tx = conn.transaction()?;
+        let mut stmt = tx.prepare(
+            "INSERT OR REPLACE INTO projects
+            (id, workspace_id, client_id, name, is_private, active, at, created_at, color, billable, synced_at, user_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+        )?;
+
         for project in projects {
-            conn.execute(
-                "INSERT OR REPLACE INTO projects
-                (id, workspace_id, client_id, name, is_private, active, at, created_at, color, billable, synced_at)
-                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-                rusqlite::params![
-                    project.id,
-                    project.workspace_id,
-                    project.client_id,
-                    project.name,
-                    project.is_private as i32,
-                    project.active as i32,
-                    project.at.to_rfc3339(),
-                    project.created_at.to_rfc3339(),
-                    project.color,
-                    project.billable.map(|b| b as i32),
-                    &now,
-                ],
-            )?;
+            stmt.execute(rusqlite::params![
+                project.id,
+                project.workspace_id,
+                project.client_id,
+                project.name,
+                project.is_private as i32,
+                project.active as i32,
+                project.at.to_rfc3339(),
+                project.created_at.to_rfc3339(),
+                project.color,
+                project.billable.map(|b| b as i32),
+                &now,
+                user_id,
+            ])
+            .with_context(|| format!("Failed to save project {}", project.id))?;
             count += 1;
         }
 
+        drop(stmt);
+        tx.commit()?;
         Ok(count)
     }
 
-    pub fn get_projects(&self) -> Result<Vec<Project>> {
+    /// Replaces the cached workspace list of `user_id`, keeping the order the API returned.
+    pub fn save_workspaces(&self, workspaces: &[Workspace], user_id: Option<i64>) -> Result<usize> {
+        let now = Utc::now().to_rfc3339();
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM workspaces WHERE IFNULL(user_id, 0) = IFNULL(?1, 0)",
+            rusqlite::params![user_id],
+        )?;
+        for workspace in workspaces {
+            tx.execute(
+                "INSERT OR REPLACE INTO workspaces (id, name, user_id, synced_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![workspace.id, workspace.name, user_id, &now],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(workspaces.len())
+    }
+
+    /// Returns the workspaces cached for `user_id` in the order they were synced.
+    pub fn get_workspaces(&self, user_id: Option<i64>) -> Result<Vec<CachedWorkspace>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name FROM workspaces
+             WHERE IFNULL(user_id, 0) = IFNULL(?1, 0)
+             ORDER BY rowid ASC",
+        )?;
+
+        let workspaces = stmt.query_map(rusqlite::params![user_id], |row| {
+            Ok(CachedWorkspace {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?;
+
+        workspaces
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to parse workspaces from database")
+    }
+
+    /// Returns active projects, limited to those cached for `user_id` when one is configured.
+    pub fn get_projects(&self, user_id: Option<i64>) -> Result<Vec<Project>> {
+        self.query_projects(user_id, false)
+    }
+
+    /// Like [`Database::get_projects`], but also returns archived (inactive) projects.
+    pub fn get_all_projects(&self, user_id: Option<i64>) -> Result<Vec<Project>> {
+        self.query_projects(user_id, true)
+    }
+
+    fn query_projects(&self, user_id: Option<i64>, include_archived: bool) -> Result<Vec<Project>> {
         let conn = self
             .conn
             .lock()
@@ -194,11 +373,11 @@ impl Database {
         let mut stmt = conn.prepare(
             "SELECT id, workspace_id, client_id, name, is_private, active, at, created_at, color, billable
              FROM projects
-             WHERE active = 1
+             WHERE (?2 OR active = 1) AND (?1 IS NULL OR user_id = ?1)
              ORDER BY name ASC",
         )?;
 
-        let projects = stmt.query_map([], |row| {
+        let projects = stmt.query_map(rusqlite::params![user_id, include_archived], |row| {
             Ok(Project {
                 id: row.get(0)?,
                 workspace_id: row.get(1)?,
@@ -223,6 +402,98 @@ impl Database {
             .context("Failed to parse projects from database")
     }
 
+    /// Returns the most recent entry start and total tracked time for every active project.
+    ///
+    /// Projects without any cached entries are included with `last_used: None`. Running entries
+    /// (negative duration) are ignored in the total.
+    pub fn get_project_activity(&self, user_id: Option<i64>) -> Result<Vec<ProjectActivity>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT p.id, MAX(t.start),
+                    COALESCE(SUM(CASE WHEN t.duration > 0 THEN t.duration ELSE 0 END), 0)
+             FROM projects p
+             LEFT JOIN time_entries t
+               ON t.project_id = p.id AND (?1 IS NULL OR t.user_id = ?1)
+             WHERE p.active = 1 AND (?1 IS NULL OR p.user_id = ?1)
+             GROUP BY p.id",
+        )?;
+
+        let activity = stmt.query_map(rusqlite::params![user_id], |row| {
+            Ok(ProjectActivity {
+                project_id: row.get(0)?,
+                last_used: row
+                    .get::<_, Option<String>>(1)?
+                    .and_then(|s| s.parse().ok()),
+                total_seconds: row.get(2)?,
+            })
+        })?;
+
+        activity
+            .collect::<Result<Vec<_>, _>>()
+            .context("Failed to read project activity from database")
+    }
+
+    /// Counts cached time entries, optionally limited to a single user.
+    pub fn get_entry_count(&self, user_id: Option<i64>) -> Result<i64> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        conn.query_row(
+            "SELECT COUNT(*) FROM time_entries WHERE ?1 IS NULL OR user_id = ?1",
+            rusqlite::params![user_id],
+            |row| row.get(0),
+        )
+        .context("Failed to count time entries")
+    }
+
+    /// Returns the earliest and latest `start` of the cached entries, or `None` when there are none.
+    pub fn get_date_bounds(
+        &self,
+        user_id: Option<i64>,
+    ) -> Result<Option<(DateTime<Utc>, DateTime<Utc>)>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let (min, max): (Option<String>, Option<String>) = conn
+            .query_row(
+                "SELECT MIN(start), MAX(start) FROM time_entries WHERE ?1 IS NULL OR user_id = ?1",
+                rusqlite::params![user_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .context("Failed to read entry date range")?;
+
+        Ok(min
+            .and_then(|s| s.parse().ok())
+            .zip(max.and_then(|s| s.parse().ok())))
+    }
+
+    /// When the given resource type was last synced, if ever.
+    pub fn get_last_sync(&self, resource_type: &str) -> Result<Option<DateTime<Utc>>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let last_sync: Option<String> = conn
+            .query_row(
+                "SELECT last_sync FROM sync_metadata WHERE resource_type = ?1",
+                rusqlite::params![resource_type],
+                |row| row.get(0),
+            )
+            .optional()
+            .context("Failed to read sync metadata")?;
+
+        Ok(last_sync.and_then(|s| s.parse().ok()))
+    }
+
     pub fn update_sync_metadata(
         &self,
         resource_type: &str,
@@ -295,6 +566,51 @@ impl Database {
         Ok(())
     }
 
+    /// Updates the start, stop and duration of a specific time entry and its `synced_at` timestamp.
+    pub fn update_time_entry_time(
+        &self,
+        entry_id: i64,
+        start: DateTime<Utc>,
+        stop: DateTime<Utc>,
+        duration: i64,
+    ) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        conn.execute(
+            "UPDATE time_entries SET start = ?1, stop = ?2, duration = ?3, synced_at = ?4
+             WHERE id = ?5",
+            rusqlite::params![
+                start.to_rfc3339(),
+                stop.to_rfc3339(),
+                duration,
+                now,
+                entry_id
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    /// Updates the billable flag of a specific time entry and its `synced_at` timestamp.
+    pub fn update_time_entry_billable(&self, entry_id: i64, billable: bool) -> Result<()> {
+        let now = Utc::now().to_rfc3339();
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        conn.execute(
+            "UPDATE time_entries SET billable = ?1, synced_at = ?2 WHERE id = ?3",
+            rusqlite::params![billable as i32, now, entry_id],
+        )?;
+
+        Ok(())
+    }
+
     /// Retrieves IDs of time entries within a specified date range.
     ///
     /// # Parameters
@@ -378,4 +694,518 @@ impl Database {
 
         Ok(count)
     }
+
+    /// Deletes every cached time entry, workspace and project of `user_id`, plus projects cached
+    /// without an owner, in one transaction. Returns the number of time entries removed.
+    pub fn delete_all_for_user(&self, user_id: i64) -> Result<usize> {
+        let mut conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Failed to lock database: {}", e))?;
+
+        let tx = conn.transaction()?;
+        let entries = tx.execute("DELETE FROM time_entries WHERE user_id = ?1", [user_id])?;
+        tx.execute("DELETE FROM workspaces WHERE user_id = ?1", [user_id])?;
+        tx.execute(
+            "DELETE FROM projects WHERE user_id = ?1 OR user_id IS NULL",
+            [user_id],
+        )?;
+        tx.commit()
+            .context("Failed to delete cached data for the previous user")?;
+
+        Ok(entries)
+    }
+}
+
+/// Maps a row selected with the column order used by the time entry queries.
+fn time_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<TimeEntry> {
+    let tags_str: Option<String> = row.get(9)?;
+    let tags = tags_str.and_then(|s| serde_json::from_str(&s).ok());
+
+    let tag_ids_str: Option<String> = row.get(10)?;
+    let tag_ids = tag_ids_str.and_then(|s| serde_json::from_str(&s).ok());
+
+    Ok(TimeEntry {
+        id: row.get(0)?,
+        workspace_id: row.get(1)?,
+        project_id: row.get(2)?,
+        task_id: row.get(3)?,
+        billable: row.get::<_, i32>(4)? != 0,
+        start: row.get::<_, String>(5)?.parse().unwrap(),
+        stop: row
+            .get::<_, Option<String>>(6)?
+            .and_then(|s| s.parse().ok()),
+        duration: row.get(7)?,
+        description: row.get(8)?,
+        tags,
+        tag_ids,
+        duronly: false,
+        at: row.get::<_, String>(12)?.parse().unwrap(),
+        server_deleted_at: None,
+        user_id: row.get(11)?,
+        uid: None,
+        wid: None,
+        pid: None,
+        synced_at: row
+            .get::<_, Option<String>>(13)?
+            .and_then(|s| s.parse().ok()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        create_test_entry, create_test_project, create_test_workspace, in_memory_db,
+    };
+    use chrono::Duration;
+
+    #[test]
+    fn test_project_activity_reports_last_use_and_totals() {
+        let db = in_memory_db();
+        db.save_projects(
+            &[
+                create_test_project(10, "Used"),
+                create_test_project(20, "Unused"),
+            ],
+            Some(1),
+        )
+        .unwrap();
+
+        let older = Utc::now() - Duration::days(40);
+        let newer = Utc::now() - Duration::days(2);
+        let mut first = create_test_entry(1, older);
+        first.project_id = Some(10);
+        let mut second = create_test_entry(2, newer);
+        second.project_id = Some(10);
+        db.save_time_entries(&[first, second]).unwrap();
+
+        let mut activity = db.get_project_activity(Some(1)).unwrap();
+        activity.sort_by_key(|a| a.project_id);
+
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[0].project_id, 10);
+        assert_eq!(activity[0].total_seconds, 7200);
+        assert_eq!(
+            activity[0].last_used.map(|d| d.timestamp()),
+            Some(newer.timestamp())
+        );
+        assert_eq!(activity[1].project_id, 20);
+        assert!(activity[1].last_used.is_none());
+        assert_eq!(activity[1].total_seconds, 0);
+    }
+
+    #[test]
+    fn test_entries_are_isolated_per_user() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(3);
+        let mut other_user = create_test_entry(2, start);
+        other_user.user_id = 2;
+        db.save_time_entries(&[create_test_entry(1, start), other_user])
+            .unwrap();
+
+        let range = (start - Duration::minutes(1), Utc::now());
+        let user_a = db.get_time_entries(range.0, range.1, Some(1)).unwrap();
+        let user_b = db.get_time_entries(range.0, range.1, Some(2)).unwrap();
+
+        assert_eq!(user_a.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(user_b.iter().map(|e| e.id).collect::<Vec<_>>(), vec![2]);
+        assert!(
+            db.get_entry_ids_in_range(range.0, range.1, Some(2))
+                .unwrap()
+                .iter()
+                .all(|id| *id == 2)
+        );
+    }
+
+    #[test]
+    fn test_projects_are_isolated_per_user() {
+        let db = in_memory_db();
+        db.save_projects(&[create_test_project(10, "Mine")], Some(1))
+            .unwrap();
+        db.save_projects(&[create_test_project(20, "Theirs")], Some(2))
+            .unwrap();
+
+        let mine = db.get_projects(Some(1)).unwrap();
+        assert_eq!(mine.len(), 1);
+        assert_eq!(mine[0].name, "Mine");
+        assert!(
+            db.get_project_activity(Some(1))
+                .unwrap()
+                .iter()
+                .all(|a| a.project_id == 10)
+        );
+        assert_eq!(db.get_projects(None).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_unowned_projects_are_adopted_by_current_user() {
+        let db = in_memory_db();
+        db.save_projects(
+            &[
+                create_test_project(10, "Legacy"),
+                create_test_project(20, "Stale copy"),
+            ],
+            None,
+        )
+        .unwrap();
+        db.save_projects(&[create_test_project(20, "Current")], Some(1))
+            .unwrap();
+        assert_eq!(db.get_projects(Some(1)).unwrap().len(), 1);
+
+        assert_eq!(db.adopt_unowned_projects(1).unwrap(), 1);
+
+        let mut names: Vec<String> = db
+            .get_projects(None)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["Current", "Legacy"]);
+        assert_eq!(db.get_projects(Some(1)).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_delete_all_for_user_keeps_other_users() {
+        let db = in_memory_db();
+        let now = Utc::now();
+        let mut theirs = create_test_entry(2, now);
+        theirs.user_id = 2;
+        db.save_time_entries(&[create_test_entry(1, now), theirs])
+            .unwrap();
+        db.save_projects(&[create_test_project(10, "Mine")], Some(1))
+            .unwrap();
+        db.save_projects(&[create_test_project(20, "Theirs")], Some(2))
+            .unwrap();
+        db.save_projects(&[create_test_project(30, "Legacy")], None)
+            .unwrap();
+
+        assert_eq!(db.delete_all_for_user(1).unwrap(), 1);
+        assert_eq!(db.get_entry_count(Some(1)).unwrap(), 0);
+        assert_eq!(db.get_entry_count(Some(2)).unwrap(), 1);
+        let remaining = db.get_projects(None).unwrap();
+        assert_eq!(remaining.iter().map(|p| p.id).collect::<Vec<_>>(), vec![20]);
+    }
+
+    #[test]
+    fn test_save_time_entries_rolls_back_on_failure() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(1);
+        db.conn
+            .lock()
+            .unwrap()
+            .execute_batch(
+                "CREATE TRIGGER reject_entry BEFORE INSERT ON time_entries
+                 WHEN NEW.id = 2 BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
+            )
+            .unwrap();
+
+        let entries = [
+            create_test_entry(1, start),
+            create_test_entry(2, start),
+            create_test_entry(3, start),
+        ];
+        let err = db.save_time_entries(&entries).unwrap_err();
+        assert!(err.to_string().contains("time entry 2"));
+
+        let saved = db
+            .get_time_entries(start - Duration::minutes(1), Utc::now(), None)
+            .unwrap();
+        assert!(saved.is_empty());
+    }
+
+    #[test]
+    fn test_get_all_projects_includes_archived() {
+        let db = in_memory_db();
+        let mut archived = create_test_project(20, "Old");
+        archived.active = false;
+        db.save_projects(&[create_test_project(10, "Current"), archived], Some(1))
+            .unwrap();
+
+        let active = db.get_projects(Some(1)).unwrap();
+        assert_eq!(active.iter().map(|p| p.id).collect::<Vec<_>>(), vec![10]);
+
+        let all = db.get_all_projects(Some(1)).unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|p| p.id == 20 && !p.active));
+    }
+
+    #[test]
+    fn test_shared_project_is_kept_for_each_user() {
+        let db = in_memory_db();
+        db.save_projects(&[create_test_project(10, "Shared")], Some(1))
+            .unwrap();
+        db.save_projects(&[create_test_project(10, "Shared (renamed)")], Some(2))
+            .unwrap();
+        db.save_projects(&[create_test_project(10, "Shared (renamed)")], Some(2))
+            .unwrap();
+
+        let first = db.get_projects(Some(1)).unwrap();
+        let second = db.get_projects(Some(2)).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].name, "Shared");
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].name, "Shared (renamed)");
+    }
+
+    #[test]
+    fn test_time_entries_round_trip() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(4);
+        let mut entry = create_test_entry(1, start);
+        entry.project_id = Some(10);
+        entry.billable = true;
+        entry.tags = Some(vec!["client".to_string(), "review".to_string()]);
+        entry.tag_ids = Some(vec![5, 6]);
+        let mut running = create_test_entry(2, start + Duration::hours(1));
+        running.stop = None;
+        running.duration = -running.start.timestamp();
+        running.description = None;
+
+        assert_eq!(
+            db.save_time_entries(&[entry.clone(), running.clone()])
+                .unwrap(),
+            2
+        );
+
+        let mut loaded = db
+            .get_time_entries(start - Duration::minutes(1), Utc::now(), Some(1))
+            .unwrap();
+        loaded.sort_by_key(|e| e.id);
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].project_id, Some(10));
+        assert!(loaded[0].billable);
+        assert_eq!(loaded[0].start.timestamp(), start.timestamp());
+        assert_eq!(
+            loaded[0].stop.map(|s| s.timestamp()),
+            entry.stop.map(|s| s.timestamp())
+        );
+        assert_eq!(loaded[0].duration, 3600);
+        assert_eq!(loaded[0].description.as_deref(), Some("Entry 1"));
+        assert_eq!(loaded[0].tags, entry.tags);
+        assert_eq!(loaded[0].tag_ids, entry.tag_ids);
+        assert!(loaded[1].stop.is_none());
+        assert_eq!(loaded[1].duration, running.duration);
+        assert!(loaded[1].description.is_none());
+    }
+
+    #[test]
+    fn test_update_time_entry_project_persists() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(2);
+        db.save_time_entries(&[create_test_entry(1, start)])
+            .unwrap();
+        let range = (start - Duration::minutes(1), Utc::now());
+
+        db.update_time_entry_project(1, Some(42)).unwrap();
+        let entries = db.get_time_entries(range.0, range.1, Some(1)).unwrap();
+        assert_eq!(entries[0].project_id, Some(42));
+
+        db.update_time_entry_project(1, None).unwrap();
+        let entries = db.get_time_entries(range.0, range.1, Some(1)).unwrap();
+        assert_eq!(entries[0].project_id, None);
+    }
+
+    #[test]
+    fn test_get_time_entry_and_update_billable() {
+        let db = in_memory_db();
+        db.save_time_entries(&[create_test_entry(1, Utc::now() - Duration::hours(2))])
+            .unwrap();
+
+        db.update_time_entry_billable(1, true).unwrap();
+
+        let entry = db.get_time_entry(1).unwrap().unwrap();
+        assert!(entry.billable);
+        assert!(db.get_time_entry(2).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_normalized_descriptions_collapse_whitespace() {
+        let db = in_memory_db();
+        db.set_normalize_descriptions(true);
+        let start = Utc::now() - Duration::hours(3);
+        let mut messy = create_test_entry(1, start);
+        messy.description = Some(" Task  A ".to_string());
+        let mut clean = create_test_entry(2, start);
+        clean.description = Some("Task A".to_string());
+        db.save_time_entries(&[messy, clean]).unwrap();
+
+        let messy = db.get_time_entry(1).unwrap().unwrap();
+        let clean = db.get_time_entry(2).unwrap().unwrap();
+        assert_eq!(messy.description.as_deref(), Some("Task A"));
+        assert_eq!(messy.description, clean.description);
+
+        let conn = db.conn.lock().unwrap();
+        let original = |id: i64| -> Option<String> {
+            conn.query_row(
+                "SELECT original_description FROM time_entries WHERE id = ?1",
+                [id],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(original(1).as_deref(), Some(" Task  A "));
+        assert_eq!(original(2), None);
+    }
+
+    #[test]
+    fn test_descriptions_are_kept_verbatim_by_default() {
+        let db = in_memory_db();
+        let mut messy = create_test_entry(1, Utc::now());
+        messy.description = Some(" Task  A ".to_string());
+        db.save_time_entries(&[messy]).unwrap();
+
+        let stored = db.get_time_entry(1).unwrap().unwrap();
+        assert_eq!(stored.description.as_deref(), Some(" Task  A "));
+    }
+
+    #[test]
+    fn test_update_time_entry_time_fixes_zero_duration() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(3);
+        let mut entry = create_test_entry(1, start);
+        entry.duration = 0;
+        db.save_time_entries(&[entry]).unwrap();
+
+        let stop = start + Duration::hours(1);
+        db.update_time_entry_time(1, start, stop, 3600).unwrap();
+
+        let entry = db.get_time_entry(1).unwrap().unwrap();
+        assert_eq!(entry.duration, 3600);
+        assert_eq!(entry.stop.map(|s| s.timestamp()), Some(stop.timestamp()));
+    }
+
+    #[test]
+    fn test_durable_writes_set_synchronous_full() {
+        let db = in_memory_db();
+        db.enable_durable_writes().unwrap();
+        db.checkpoint().unwrap();
+
+        let conn = db.conn.lock().unwrap();
+        let synchronous: i64 = conn
+            .pragma_query_value(None, "synchronous", |row| row.get(0))
+            .unwrap();
+        assert_eq!(synchronous, 2);
+    }
+
+    #[test]
+    fn test_file_database_uses_wal_and_durable_checkpoints() {
+        let dir =
+            std::env::temp_dir().join(format!("toggl-timeguru-test-{}-wal", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("timeguru.db");
+        let db = Database::new(Some(path.clone())).unwrap();
+
+        let synchronous = |db: &Database| -> i64 {
+            let conn = db.conn.lock().unwrap();
+            conn.pragma_query_value(None, "synchronous", |row| row.get(0))
+                .unwrap()
+        };
+        let journal_mode: String = db
+            .conn
+            .lock()
+            .unwrap()
+            .pragma_query_value(None, "journal_mode", |row| row.get(0))
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(synchronous(&db), 1);
+
+        db.enable_durable_writes().unwrap();
+        assert_eq!(synchronous(&db), 2);
+
+        db.save_time_entries(&[create_test_entry(1, Utc::now())])
+            .unwrap();
+        let [wal, _] = Database::wal_paths(&path);
+        assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+        db.checkpoint().unwrap();
+        let wal_len = std::fs::metadata(&wal).unwrap().len();
+
+        drop(db);
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(wal_len, 0);
+    }
+
+    #[test]
+    fn test_entry_count_date_bounds_and_last_sync() {
+        let db = in_memory_db();
+        assert_eq!(db.get_entry_count(None).unwrap(), 0);
+        assert!(db.get_date_bounds(None).unwrap().is_none());
+        assert!(db.get_last_sync("time_entries").unwrap().is_none());
+
+        let older = Utc::now() - Duration::days(10);
+        let newer = Utc::now() - Duration::days(1);
+        let mut other_user = create_test_entry(3, older - Duration::days(5));
+        other_user.user_id = 2;
+        db.save_time_entries(&[
+            create_test_entry(1, newer),
+            create_test_entry(2, older),
+            other_user,
+        ])
+        .unwrap();
+        db.update_sync_metadata("time_entries", Some(2)).unwrap();
+
+        assert_eq!(db.get_entry_count(Some(1)).unwrap(), 2);
+        assert_eq!(db.get_entry_count(None).unwrap(), 3);
+        let (min, max) = db.get_date_bounds(Some(1)).unwrap().unwrap();
+        assert_eq!(min.timestamp(), older.timestamp());
+        assert_eq!(max.timestamp(), newer.timestamp());
+        assert!(db.get_last_sync("time_entries").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_workspaces_are_replaced_per_user() {
+        let db = in_memory_db();
+        db.save_workspaces(
+            &[
+                create_test_workspace(5, "Second"),
+                create_test_workspace(3, "First"),
+            ],
+            Some(1),
+        )
+        .unwrap();
+        db.save_workspaces(&[create_test_workspace(9, "Other")], Some(2))
+            .unwrap();
+        assert_eq!(
+            db.get_workspaces(Some(1))
+                .unwrap()
+                .iter()
+                .map(|w| w.id)
+                .collect::<Vec<_>>(),
+            vec![5, 3]
+        );
+
+        db.save_workspaces(&[create_test_workspace(3, "First")], Some(1))
+            .unwrap();
+        assert_eq!(
+            db.get_workspaces(Some(1)).unwrap(),
+            vec![CachedWorkspace {
+                id: 3,
+                name: "First".to_string()
+            }]
+        );
+        assert_eq!(db.get_workspaces(Some(2)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_saved_entry_preserves_synced_at() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::hours(2);
+
+        let before = Utc::now();
+        db.save_time_entries(&[create_test_entry(1, start)])
+            .unwrap();
+        let after = Utc::now();
+
+        let entries = db
+            .get_time_entries(start - Duration::minutes(1), Utc::now(), Some(1))
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+
+        let synced_at = entries[0].synced_at.expect("synced_at should be loaded");
+        assert!(synced_at >= before - Duration::seconds(1));
+        assert!(synced_at <= after + Duration::seconds(1));
+        assert_eq!(entries[0].at.timestamp(), start.timestamp());
+    }
 }
@@ -22,6 +22,8 @@ pub fn init_database(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    add_column_if_missing(conn, "time_entries", "original_description", "TEXT")?;
+
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_time_entries_start ON time_entries(start)",
         [],
@@ -37,23 +39,33 @@ pub fn init_database(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    conn.execute(&projects_table_sql("projects"), [])?;
+
+    add_column_if_missing(conn, "projects", "user_id", "INTEGER")?;
+    rekey_projects_by_user(conn)?;
+
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS projects (
-            id INTEGER PRIMARY KEY,
-            workspace_id INTEGER NOT NULL,
-            client_id INTEGER,
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_projects_user_id_id
+         ON projects(IFNULL(user_id, 0), id)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS workspaces (
+            id INTEGER NOT NULL,
             name TEXT NOT NULL,
-            is_private INTEGER NOT NULL,
-            active INTEGER NOT NULL,
-            at TEXT NOT NULL,
-            created_at TEXT NOT NULL,
-            color TEXT NOT NULL,
-            billable INTEGER,
+            user_id INTEGER,
             synced_at TEXT NOT NULL
         )",
         [],
     )?;
 
+    conn.execute(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_workspaces_user_id_id
+         ON workspaces(IFNULL(user_id, 0), id)",
+        [],
+    )?;
+
     conn.execute(
         "CREATE TABLE IF NOT EXISTS sync_metadata (
             resource_type TEXT PRIMARY KEY,
@@ -65,3 +77,172 @@ pub fn init_database(conn: &Connection) -> Result<()> {
 
     Ok(())
 }
+
+/// Projects are keyed by `(user_id, id)` through a unique index rather than by `id` alone, so a
+/// project shared by two cached accounts is stored once per account.
+fn projects_table_sql(table: &str) -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER NOT NULL,
+            workspace_id INTEGER NOT NULL,
+            client_id INTEGER,
+            name TEXT NOT NULL,
+            is_private INTEGER NOT NULL,
+            active INTEGER NOT NULL,
+            at TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            color TEXT NOT NULL,
+            billable INTEGER,
+            synced_at TEXT NOT NULL,
+            user_id INTEGER
+        )",
+        table
+    )
+}
+
+/// Rebuilds a projects table created with `id` as its primary key, which let one account's sync
+/// overwrite another account's copy of a shared project.
+fn rekey_projects_by_user(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("PRAGMA table_info(projects)")?;
+    let keyed_by_id = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(1)?, row.get::<_, i64>(5)?))
+        })?
+        .filter_map(|column| column.ok())
+        .any(|(name, pk)| name == "id" && pk > 0);
+
+    if keyed_by_id {
+        conn.execute_batch(&format!(
+            "BEGIN;
+             {};
+             INSERT INTO projects_rekeyed
+                 (id, workspace_id, client_id, name, is_private, active, at, created_at, color,
+                  billable, synced_at, user_id)
+             SELECT id, workspace_id, client_id, name, is_private, active, at, created_at, color,
+                    billable, synced_at, user_id
+             FROM projects;
+             DROP TABLE projects;
+             ALTER TABLE projects_rekeyed RENAME TO projects;
+             COMMIT;",
+            projects_table_sql("projects_rekeyed")
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Hands projects cached without an owner (by versions before projects were keyed per account,
+/// or before any account was configured) to `user_id`. Unowned copies of projects the user
+/// already has are dropped instead, so no hidden duplicates linger.
+pub fn adopt_unowned_projects(conn: &Connection, user_id: i64) -> Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM projects
+         WHERE user_id IS NULL
+           AND id IN (SELECT id FROM projects WHERE user_id = ?1)",
+        [user_id],
+    )?;
+    let adopted = tx.execute(
+        "UPDATE projects SET user_id = ?1 WHERE user_id IS NULL",
+        [user_id],
+    )?;
+    tx.commit()?;
+    Ok(adopted)
+}
+
+/// Adds a column to a table created by an older version of the schema.
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, kind: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, kind),
+            [],
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A projects table as created before projects were keyed per account, holding one project.
+    fn pre_user_projects_db() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            "CREATE TABLE projects (
+                id INTEGER PRIMARY KEY,
+                workspace_id INTEGER NOT NULL,
+                client_id INTEGER,
+                name TEXT NOT NULL,
+                is_private INTEGER NOT NULL,
+                active INTEGER NOT NULL,
+                at TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                color TEXT NOT NULL,
+                billable INTEGER,
+                synced_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .unwrap();
+        conn.execute(
+            "INSERT INTO projects VALUES (10, 1, NULL, 'Legacy', 0, 1, 'a', 'a', '#000', NULL, 'a')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_legacy_projects_table_is_rekeyed_by_user() {
+        let conn = pre_user_projects_db();
+
+        init_database(&conn).unwrap();
+        init_database(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO projects (id, workspace_id, name, is_private, active, at, created_at, color, synced_at, user_id)
+             VALUES (10, 1, 'Shared', 0, 1, 'a', 'a', '#000', 'a', 2)",
+            [],
+        )
+        .unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM projects WHERE id = 10", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn test_legacy_projects_are_matched_by_the_next_sync() {
+        let conn = pre_user_projects_db();
+        init_database(&conn).unwrap();
+
+        assert_eq!(adopt_unowned_projects(&conn, 1).unwrap(), 1);
+
+        // The same upsert save_projects runs when the account syncs the project again.
+        conn.execute(
+            "INSERT OR REPLACE INTO projects
+                 (id, workspace_id, name, is_private, active, at, created_at, color, synced_at, user_id)
+             VALUES (10, 1, 'Renamed', 0, 1, 'b', 'a', '#000', 'b', 1)",
+            [],
+        )
+        .unwrap();
+        let rows: Vec<(String, Option<i64>)> = conn
+            .prepare("SELECT name, user_id FROM projects WHERE id = 10")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(rows, vec![("Renamed".to_string(), Some(1))]);
+    }
+}
@@ -1,8 +1,16 @@
+mod backup;
 mod cli;
+mod concurrency;
 mod config;
 mod db;
+#[cfg(feature = "parquet")]
+mod parquet_export;
 mod processor;
 mod report;
+mod staged_file;
+mod sync;
+#[cfg(test)]
+mod test_support;
 mod toggl;
 mod ui;
 
@@ -14,23 +22,71 @@ use crossterm::{
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{Terminal, backend::CrosstermBackend};
-use std::io;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::Instrument;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use cli::{Cli, Commands, TrackAction};
-use config::{Config, ProjectSortMethod};
+use cli::{
+    Cli, Commands, ConfigArgs, ExportFormat, GroupBy, LogFormat, MetadataStyle, SchemaTarget,
+    TrackAction,
+};
+use config::{
+    Config, DurationFormat, ExportGrouping, Favorite, ProjectSortMethod, SavedRange, TuiGrouping,
+};
 use db::Database;
 use processor::{
-    filter_by_project, filter_by_tag, group_by_description, group_by_description_and_day,
+    OTHER_GROUP_LABEL, SortOrder, corrected_duration, elapsed_seconds, filter_by_description_regex,
+    filter_by_duration_range, filter_by_project, filter_by_projects, filter_by_tag,
+    filter_by_tag_id, filter_by_workspace, filter_newer_than, filter_older_than, filter_running,
+    find_duplicates, find_invalid_durations, format_hours, format_hours_cell, group_by_client,
+    group_by_description, group_by_description_and_day, group_by_tag, is_running,
+    merge_small_groups, parse_duration, sort_entries, sort_groups, spans_midnight,
+    split_at_midnight, top_groups,
 };
-use toggl::TogglClient;
+use staged_file::StagedFile;
+use toggl::client::TimeEntryUpdate;
+use toggl::{TogglApi, TogglClient};
 use ui::App;
 
+/// Set once from `--verbose-errors`; read by every client built through `new_client`.
+static VERBOSE_ERRORS: AtomicBool = AtomicBool::new(false);
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    init_tracing(cli.verbose);
+    if let Some(path) = &cli.config {
+        let path = std::path::PathBuf::from(path);
+        if !path.exists() {
+            // Only `config` may start a new file, e.g. `--config new.toml config --set-token`.
+            if !matches!(cli.command, Some(Commands::Config(_))) {
+                anyhow::bail!(
+                    "Config file {} does not exist. Create it with: toggl-timeguru --config {} config --set-token YOUR_TOKEN",
+                    path.display(),
+                    path.display()
+                );
+            }
+            Config::default().save_to(&path)?;
+            eprintln!("Created config file {}", path.display());
+        }
+        Config::use_path(path);
+    } else if let Some(name) = &cli.profile {
+        let path = Config::profile_path(name)?;
+        if !path.exists() {
+            anyhow::bail!(
+                "Profile '{name}' does not exist. Create it with: toggl-timeguru config --new-profile {name}"
+            );
+        }
+        Config::use_path(path);
+    }
+
+    // A broken config shouldn't stop logging; the command itself reports the load error.
+    let log_retention_days = Config::load()
+        .map(|config| config.log_retention_days)
+        .unwrap_or_else(|_| Config::default().log_retention_days);
+    init_tracing(cli.verbose, cli.log_format.parse()?, log_retention_days);
+    VERBOSE_ERRORS.store(cli.verbose_errors, Ordering::Relaxed);
 
     std::panic::set_hook(Box::new(|panic_info| {
         tracing::error!("========================================");
@@ -58,92 +114,351 @@ async fn main() -> Result<()> {
     }));
 
     if let Some(command) = cli.command {
-        match command {
-            Commands::Config {
-                set_token,
-                set_date_range,
-                set_round_minutes,
-                set_project_sort,
-                show,
-            } => {
-                handle_config(
-                    set_token,
-                    set_date_range,
-                    set_round_minutes,
-                    set_project_sort,
-                    show,
-                )
-                .await?
+        match run_command(command.clone(), cli.api_token, cli.workspace)
+            .instrument(tracing::info_span!("command"))
+            .await
+        {
+            Err(e) if is_auth_error(&e) && io::stdin().is_terminal() => {
+                eprintln!("Error: {}", e);
+                let token = prompt_for_new_token().await?;
+                run_command(command, Some(token), cli.workspace).await?;
             }
+            result => result?,
+        }
+    } else {
+        println!("Toggl TimeGuru - Use --help for usage information");
+        println!("\nQuick start:");
+        println!("  1. Set your API token: toggl-timeguru config --set-token YOUR_TOKEN");
+        println!("  2. Sync your time entries: toggl-timeguru sync");
+        println!("  3. View entries: toggl-timeguru tui");
+    }
+
+    Ok(())
+}
+
+async fn run_command(
+    command: Commands,
+    api_token: Option<String>,
+    workspace: Option<i64>,
+) -> Result<()> {
+    match command {
+        Commands::Config(args) => handle_config(*args).await?,
+
+        Commands::Standup {
+            date,
+            copy,
+            offline,
+            round,
+            no_round,
+        } => handle_standup(date, copy, offline, round, no_round, api_token).await?,
+
+        Commands::Today { yesterday, offline } => {
+            handle_today(yesterday, offline, api_token).await?
+        }
 
-            Commands::List {
+        Commands::List {
+            start,
+            end,
+            project,
+            range,
+            tag,
+            tag_ids,
+            description_regex,
+            case_insensitive,
+            group,
+            case_insensitive_group,
+            group_by,
+            top,
+            merge_below,
+            sort,
+            only_running,
+            newer_than,
+            older_than,
+            min_duration,
+            max_duration,
+            offline,
+            round,
+            no_round,
+        } => {
+            handle_list(
                 start,
                 end,
+                range,
                 project,
                 tag,
+                tag_ids,
+                description_regex,
+                case_insensitive,
+                workspace,
                 group,
+                case_insensitive_group,
+                group_by,
+                top,
+                merge_below,
+                sort,
+                only_running,
+                newer_than,
+                older_than,
+                min_duration,
+                max_duration,
                 offline,
-            } => handle_list(start, end, project, tag, group, offline, cli.api_token).await?,
-
-            Commands::Sync { start, end } => handle_sync(start, end, cli.api_token).await?,
+                round,
+                no_round,
+                api_token,
+            )
+            .await?
+        }
 
-            Commands::Tui { start, end } => handle_tui(start, end, cli.api_token).await?,
+        Commands::Sync {
+            start,
+            end,
+            entries_only,
+            projects_only,
+            reconcile,
+            durable,
+            concurrency,
+            chunk_days,
+            keep_data,
+            wipe_data,
+        } => {
+            handle_sync(
+                start,
+                end,
+                entries_only,
+                projects_only,
+                reconcile,
+                durable,
+                concurrency,
+                chunk_days,
+                wipe_data.then_some(true).or(keep_data.then_some(false)),
+                api_token,
+                workspace,
+            )
+            .await?
+        }
 
-            Commands::Report {
+        Commands::Tui { start, end } => handle_tui(start, end, api_token, workspace).await?,
+
+        Commands::Report {
+            period,
+            project,
+            start,
+            end,
+            range,
+            offline,
+            round,
+            round_minutes,
+            round_mode,
+        } => {
+            handle_report(
                 period,
                 project,
                 start,
                 end,
+                range,
                 offline,
                 round,
                 round_minutes,
                 round_mode,
-            } => {
-                handle_report(
-                    period,
-                    project,
-                    start,
-                    end,
-                    offline,
-                    round,
-                    round_minutes,
-                    round_mode,
-                    cli.api_token,
-                )
-                .await?
-            }
-
-            Commands::Clean {
-                all,
-                data,
-                config,
-                confirm,
-            } => handle_clean(all, data, config, confirm).await?,
+                api_token,
+            )
+            .await?
+        }
 
-            Commands::Export {
+        Commands::Clean {
+            all,
+            data,
+            config,
+            confirm,
+            no_backup,
+        } => handle_clean(all, data, config, confirm, no_backup).await?,
+
+        Commands::Restore { index, confirm } => handle_restore(index, confirm)?,
+
+        Commands::Export {
+            start,
+            end,
+            range,
+            output,
+            stdout,
+            split_by,
+            output_dir,
+            format,
+            include_metadata,
+            no_metadata,
+            no_header,
+            metadata_style,
+            group,
+            group_by_day,
+            split_midnight,
+            case_insensitive_group,
+            group_by,
+            no_group,
+            merge_below,
+            sort,
+            round,
+            no_round,
+            with_raw,
+            include_color,
+            tag_ids,
+            description_regex,
+            case_insensitive,
+            min_duration,
+            max_duration,
+            excel,
+        } => {
+            handle_export(
                 start,
                 end,
-                output,
+                range,
+                if stdout { None } else { output },
+                split_by.zip(output_dir),
+                format,
                 include_metadata,
+                no_metadata,
+                no_header,
+                metadata_style,
                 group,
                 group_by_day,
-            } => handle_export(start, end, output, include_metadata, group, group_by_day).await?,
+                split_midnight,
+                case_insensitive_group,
+                group_by,
+                no_group,
+                merge_below,
+                sort,
+                round,
+                no_round,
+                with_raw,
+                include_color,
+                tag_ids,
+                description_regex,
+                case_insensitive,
+                min_duration,
+                max_duration,
+                excel,
+                workspace,
+            )
+            .await?
+        }
 
-            Commands::Track { action } => handle_track(action, cli.api_token).await?,
+        Commands::Track { action } => handle_track(action, api_token, workspace).await?,
+
+        Commands::Doctor { start, end, merge } => {
+            handle_doctor(start, end, merge, api_token).await?
         }
-    } else {
-        println!("Toggl TimeGuru - Use --help for usage information");
-        println!("\nQuick start:");
-        println!("  1. Set your API token: toggl-timeguru config --set-token YOUR_TOKEN");
-        println!("  2. Sync your time entries: toggl-timeguru sync");
-        println!("  3. View entries: toggl-timeguru tui");
+
+        Commands::Projects { all, json } => handle_projects(all, json)?,
+
+        Commands::Schema { command } => handle_schema(command.parse()?)?,
+
+        Commands::StaleProjects { days } => handle_stale_projects(days)?,
+
+        Commands::Assign {
+            project,
+            description,
+            start,
+            end,
+            dry_run,
+            concurrency,
+        } => {
+            handle_assign(
+                project,
+                description,
+                start,
+                end,
+                dry_run,
+                concurrency,
+                api_token,
+            )
+            .await?
+        }
+
+        Commands::Edit {
+            id,
+            description,
+            project,
+            project_name,
+            billable,
+            fix_duration,
+        } => {
+            handle_edit(
+                id,
+                description,
+                project,
+                project_name,
+                billable,
+                fix_duration,
+                api_token,
+            )
+            .await?
+        }
+
+        Commands::Info => handle_info()?,
     }
 
     Ok(())
 }
 
-fn init_tracing(verbose: bool) {
+fn is_auth_error(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.to_string().starts_with("Authentication failed"))
+}
+
+/// Asks for a replacement API token, verifies it against `/me` and saves it to the config.
+async fn prompt_for_new_token() -> Result<String> {
+    use std::io::Write;
+
+    print!("Your API token appears to be invalid or expired. Enter a new token (blank to abort): ");
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let token = input.trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("Aborted: no API token entered");
+    }
+
+    let mut config = Config::load()?;
+    let email = new_client(token.clone(), &config)?
+        .get_current_user_email()
+        .await
+        .context("The new API token could not be verified")?;
+
+    config.api_token_encrypted = Some(token.clone().into_bytes());
+    config.save()?;
+    println!("API token updated for {}. Retrying...", email);
+
+    Ok(token)
+}
+
+/// Deletes `app.log.YYYY-MM-DD` files in `log_dir` outside the last `keep_days` days, counting
+/// `today` as the first. Best-effort: unreadable entries and failed deletions are skipped.
+fn prune_old_logs(log_dir: &std::path::Path, keep_days: u32, today: chrono::NaiveDate) -> usize {
+    if keep_days == 0 {
+        return 0;
+    }
+    let cutoff = today - Duration::days(i64::from(keep_days) - 1);
+    let Ok(entries) = std::fs::read_dir(log_dir) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("app.log."))
+                .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+                .is_some_and(|date| date < cutoff)
+        })
+        .filter(|entry| std::fs::remove_file(entry.path()).is_ok())
+        .count()
+}
+
+fn init_tracing(verbose: bool, format: LogFormat, log_retention_days: u32) {
     use tracing_appender::rolling::{RollingFileAppender, Rotation};
+    use tracing_subscriber::Layer;
     use tracing_subscriber::fmt::writer::MakeWriterExt;
 
     let default_level = if verbose { "debug" } else { "info" };
@@ -153,11 +468,21 @@ fn init_tracing(verbose: bool) {
 
     let log_dir = std::env::temp_dir().join("toggl-timeguru");
     std::fs::create_dir_all(&log_dir).ok();
+    let pruned = prune_old_logs(&log_dir, log_retention_days, Utc::now().date_naive());
 
     let file_appender = RollingFileAppender::new(Rotation::DAILY, log_dir.clone(), "app.log");
 
+    // With --verbose, every closed span (API fetch, DB save, grouping, rendering) is logged
+    // with its wall-clock timings.
+    let span_events = if verbose {
+        tracing_subscriber::fmt::format::FmtSpan::CLOSE
+    } else {
+        tracing_subscriber::fmt::format::FmtSpan::NONE
+    };
+
     let file_layer = tracing_subscriber::fmt::layer()
         .with_writer(file_appender)
+        .with_span_events(span_events)
         .with_target(true)
         .with_thread_ids(true)
         .with_file(true)
@@ -171,26 +496,77 @@ fn init_tracing(verbose: bool) {
         .with_file(true)
         .with_line_number(true);
 
+    type Filtered = tracing_subscriber::layer::Layered<
+        tracing_subscriber::EnvFilter,
+        tracing_subscriber::Registry,
+    >;
+    let layers: Vec<Box<dyn Layer<Filtered> + Send + Sync>> = match format {
+        LogFormat::Text => vec![file_layer.boxed(), stderr_layer.boxed()],
+        LogFormat::Json => vec![
+            file_layer.json().boxed(),
+            stderr_layer.with_ansi(false).json().boxed(),
+        ],
+    };
+
     tracing_subscriber::registry()
         .with(filter)
-        .with(file_layer)
-        .with(stderr_layer)
+        .with(layers)
         .init();
 
     tracing::info!("========================================");
     tracing::info!("Toggl TimeGuru starting");
     tracing::info!("Log file location: {}/app.log", log_dir.display());
     tracing::info!("Tracing initialized with level: {}", default_level);
+    if pruned > 0 {
+        tracing::info!(
+            "Removed {} log file(s) older than {} days",
+            pruned,
+            log_retention_days
+        );
+    }
     tracing::info!("========================================");
 }
 
-async fn handle_config(
-    set_token: Option<String>,
-    set_date_range: Option<i64>,
-    set_round_minutes: Option<i64>,
-    set_project_sort: Option<String>,
-    show: bool,
-) -> Result<()> {
+async fn handle_config(args: ConfigArgs) -> Result<()> {
+    let ConfigArgs {
+        set_token,
+        reset_token,
+        set_date_range,
+        set_round_minutes,
+        set_project_sort,
+        set_created_with,
+        set_api_base_url,
+        set_workspace,
+        set_sync_chunk_days,
+        set_duration_format,
+        set_tui_grouping,
+        set_check_conflicts,
+        set_case_insensitive_group,
+        set_normalize_descriptions,
+        set_export_include_metadata,
+        set_export_group_by,
+        add_favorite,
+        favorite_project,
+        remove_favorite,
+        set_default_description,
+        set_timer_refresh,
+        set_log_retention,
+        set_max_concurrency,
+        set_weekly_target,
+        set_daily_target,
+        add_range,
+        range_start,
+        range_end,
+        remove_range,
+        add_template,
+        template_text,
+        remove_template,
+        show,
+        path,
+        list_profiles,
+        new_profile,
+        db_path,
+    } = args;
     use std::str::FromStr;
     let mut config = Config::load()?;
 
@@ -200,13 +576,25 @@ async fn handle_config(
         println!("API token saved successfully");
     }
 
+    if reset_token {
+        config.api_token_encrypted = None;
+        config.save()?;
+        println!("Stored API token removed");
+    }
+
     if let Some(days) = set_date_range {
+        if days < 1 {
+            anyhow::bail!("--set-date-range must be at least 1 day, got {days}");
+        }
         config.default_date_range_days = days;
         config.save()?;
         println!("Default date range set to {} days", days);
     }
 
     if let Some(minutes) = set_round_minutes {
+        if minutes < 1 {
+            anyhow::bail!("--set-round-minutes must be at least 1 minute, got {minutes}");
+        }
         config.round_duration_minutes = Some(minutes);
         config.save()?;
         println!("Rounding duration set to {} minutes", minutes);
@@ -219,127 +607,550 @@ async fn handle_config(
         println!("Project sort method set to {:?}", method);
     }
 
-    if show {
-        println!("\nCurrent Configuration:");
+    if let Some(created_with) = set_created_with {
+        let trimmed = created_with.trim();
+        if trimmed.is_empty() {
+            anyhow::bail!("--set-created-with must not be empty");
+        }
+        config.created_with = Some(trimmed.to_string());
+        config.save()?;
+        println!("created_with set to '{}'", trimmed);
+    }
+
+    if let Some(base_url) = set_api_base_url {
+        let trimmed = base_url.trim().trim_end_matches('/');
+        if trimmed.is_empty() {
+            config.api_base_url = None;
+            config.save()?;
+            println!(
+                "API base URL reset to {}",
+                toggl::client::DEFAULT_API_BASE_URL
+            );
+        } else {
+            if !trimmed.starts_with("http://") && !trimmed.starts_with("https://") {
+                anyhow::bail!(
+                    "API base URL must start with http:// or https://, got '{}'",
+                    trimmed
+                );
+            }
+            config.api_base_url = Some(trimmed.to_string());
+            config.save()?;
+            println!("API base URL set to {}", trimmed);
+        }
+    }
+
+    if let Some(workspace_id) = set_workspace {
+        config.default_workspace_id = Some(workspace_id);
+        config.save()?;
+        println!("Default workspace set to {}", workspace_id);
+    }
+
+    if let Some(days) = set_sync_chunk_days {
+        if days <= 0 {
+            anyhow::bail!("--set-sync-chunk-days must be a positive integer, got {days}");
+        }
+        config.sync_chunk_days = days;
+        config.save()?;
+        println!("Sync chunk window set to {} days", days);
+    }
+
+    if let Some(format_str) = set_duration_format {
+        let format = DurationFormat::from_str(&format_str)?;
+        config.duration_format = format;
+        config.save()?;
+        println!("Duration format set to {:?}", format);
+    }
+
+    if let Some(grouping_str) = set_tui_grouping {
+        let grouping = TuiGrouping::from_str(&grouping_str)?;
+        config.tui_grouping = grouping;
+        config.save()?;
+        println!("TUI grouping at launch set to {:?}", grouping);
+    }
+
+    if let Some(check) = set_check_conflicts {
+        config.check_conflicts = check;
+        config.save()?;
         println!(
-            "  Default date range: {} days",
-            config.default_date_range_days
+            "Conflict check before TUI edits {}",
+            if check { "enabled" } else { "disabled" }
         );
-        println!("  Report format: {:?}", config.preferred_report_format);
+    }
+
+    if let Some(fold) = set_case_insensitive_group {
+        config.case_insensitive_grouping = fold;
+        config.save()?;
         println!(
-            "  Round duration: {:?} minutes",
-            config.round_duration_minutes
+            "Case-insensitive grouping {}",
+            if fold { "enabled" } else { "disabled" }
         );
-        println!("  Project sort method: {:?}", config.project_sort_method);
+    }
+
+    if let Some(normalize) = set_normalize_descriptions {
+        config.normalize_descriptions = normalize;
+        config.save()?;
         println!(
-            "  API token configured: {}",
-            config.api_token_encrypted.is_some()
+            "Description whitespace normalization {} (applies from the next sync)",
+            if normalize { "enabled" } else { "disabled" }
         );
     }
 
-    Ok(())
-}
+    if let Some(include) = set_export_include_metadata {
+        config.export_include_metadata = include;
+        config.save()?;
+        println!(
+            "Export metadata by default {}",
+            if include { "enabled" } else { "disabled" }
+        );
+    }
 
-#[allow(clippy::too_many_arguments)]
-async fn handle_report(
-    period: String,
-    project: Option<i64>,
-    start: Option<String>,
-    end: Option<String>,
-    offline: bool,
-    round: bool,
-    round_minutes_flag: Option<i64>,
-    round_mode: String,
-    cli_api_token: Option<String>,
-) -> Result<()> {
-    use std::str::FromStr;
+    if let Some(grouping_str) = set_export_group_by {
+        let grouping = ExportGrouping::from_str(&grouping_str)?;
+        config.export_group_by = grouping;
+        config.save()?;
+        println!("Default export grouping set to {:?}", grouping);
+    }
 
-    let report_period = report::ReportPeriod::from_str(&period)?;
-    let rounding_mode = report::RoundingMode::from_str(&round_mode)?;
-    let config = Config::load()?;
-    let db = Database::new(None)?;
+    if let Some(description) = add_favorite {
+        let description = description.trim().to_string();
+        if description.is_empty() {
+            anyhow::bail!("--add-favorite must not be empty");
+        }
+        let favorite = Favorite {
+            description,
+            project_id: favorite_project,
+        };
+        if config.favorites.contains(&favorite) {
+            println!("'{}' is already a favorite", favorite.description);
+        } else {
+            println!("Added favorite '{}'", favorite.description);
+            config.favorites.push(favorite);
+            config.save()?;
+        }
+    }
 
-    let round_minutes = match round_minutes_flag {
-        Some(n) if n > 0 => Some(n),
-        Some(n) => anyhow::bail!("--round-minutes must be a positive integer, got {n}"),
-        None if round => Some(config.round_duration_minutes.unwrap_or(15)),
-        None => None,
-    };
+    if let Some(target) = remove_favorite {
+        let index = match target.trim().parse::<usize>() {
+            Ok(n) if n >= 1 && n <= config.favorites.len() => n - 1,
+            _ => config
+                .favorites
+                .iter()
+                .position(|f| f.description.eq_ignore_ascii_case(target.trim()))
+                .ok_or_else(|| anyhow::anyhow!("Favorite '{}' not found", target))?,
+        };
+        let removed = config.favorites.remove(index);
+        config.save()?;
+        println!("Removed favorite '{}'", removed.description);
+    }
 
-    let end_date = if let Some(end_str) = end {
-        if is_date_only(&end_str) {
-            parse_local_date_end(&end_str)?
+    if let Some(template) = set_default_description {
+        let trimmed = template.trim();
+        if trimmed.is_empty() {
+            config.default_description = None;
+            println!("Default description cleared");
         } else {
-            Cli::parse_date(&end_str)?
+            config.default_description = Some(trimmed.to_string());
+            println!("Default description set to '{}'", trimmed);
         }
-    } else {
-        Utc::now()
-    };
-    let start_date = if let Some(start_str) = start {
-        if is_date_only(&start_str) {
-            parse_local_date_start(&start_str)?
+        config.save()?;
+    }
+
+    if let Some(seconds) = set_timer_refresh {
+        config.timer_refresh_seconds = seconds;
+        config.save()?;
+        if seconds == 0 {
+            println!("Running timer refresh disabled");
         } else {
-            Cli::parse_date(&start_str)?
+            println!("Running timer refresh set to every {} seconds", seconds);
         }
-    } else {
-        end_date - config.default_date_range()
-    };
+    }
 
-    if start_date > end_date {
-        anyhow::bail!(
-            "--start ({}) must not be after --end ({})",
-            start_date
-                .with_timezone(&chrono::Local)
-                .format("%Y-%m-%d %H:%M"),
-            end_date
-                .with_timezone(&chrono::Local)
-                .format("%Y-%m-%d %H:%M"),
-        );
+    if let Some(days) = set_log_retention {
+        config.log_retention_days = days;
+        config.save()?;
+        if days == 0 {
+            println!("Log retention disabled; log files are kept");
+        } else {
+            println!("Log files older than {} days will be deleted", days);
+        }
     }
 
-    let mut entries = if offline {
-        db.get_time_entries(start_date, end_date, config.current_user_id)?
-    } else {
-        let api_token = get_api_token(cli_api_token, &config)?;
-        let client = TogglClient::new(api_token)?;
-        let fetched = client.get_time_entries(start_date, end_date).await?;
-        db.save_time_entries(&fetched)?;
+    if let Some(limit) = set_max_concurrency {
+        config.max_concurrency = resolve_concurrency(Some(limit), &config)?;
+        config.save()?;
+        println!("Max concurrency set to {} requests", limit);
+    }
 
-        if db.get_projects().map(|p| p.is_empty()).unwrap_or(true)
-            && let Ok(workspaces) = client.get_workspaces().await
-        {
-            for workspace in workspaces {
-                if let Ok(projects) = client.get_projects(workspace.id).await {
-                    let _ = db.save_projects(&projects);
-                }
-            }
-        }
+    if let Some(hours) = set_weekly_target {
+        config.weekly_target_hours = resolve_target_hours(hours, "Weekly")?;
+        config.save()?;
+    }
 
-        fetched
-    };
+    if let Some(hours) = set_daily_target {
+        config.daily_target_hours = resolve_target_hours(hours, "Daily")?;
+        config.save()?;
+    }
 
-    if let Some(project_id) = project {
-        entries = filter_by_project(entries, project_id);
+    if let Some(name) = add_range {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            anyhow::bail!("Range name must not be empty");
+        }
+        let range = SavedRange {
+            start: range_start.unwrap_or_default(),
+            end: range_end.unwrap_or_default(),
+        };
+        let (from, to) = range.resolve(chrono::Local::now().date_naive())?;
+        config.saved_ranges.insert(name.clone(), range);
+        config.save()?;
+        println!("Saved range '{}' (currently {} to {})", name, from, to);
     }
 
-    let projects = db.get_projects().unwrap_or_default();
-    let report = report::generate(
-        &entries,
-        &projects,
-        report_period,
-        start_date,
-        end_date,
-        round_minutes,
-        rounding_mode,
-    );
-    report::print_text(&report);
+    if let Some(name) = remove_range {
+        if config
+            .saved_ranges
+            .remove(&name.trim().to_lowercase())
+            .is_none()
+        {
+            anyhow::bail!("No saved range named '{}'", name);
+        }
+        config.save()?;
+        println!("Removed range '{}'", name.trim());
+    }
 
-    Ok(())
-}
+    if let Some((name, text)) = add_template.zip(template_text) {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            anyhow::bail!("Template name must not be empty");
+        }
+        let placeholders = config::template_placeholders(&text)?;
+        config.description_templates.insert(name.clone(), text);
+        config.save()?;
+        if placeholders.is_empty() {
+            println!("Saved template '{}' (no placeholders)", name);
+        } else {
+            println!(
+                "Saved template '{}' (placeholders: {})",
+                name,
+                placeholders.join(", ")
+            );
+        }
+    }
 
-fn is_date_only(s: &str) -> bool {
-    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").is_ok()
-}
+    if let Some(name) = remove_template {
+        if config
+            .description_templates
+            .remove(&name.trim().to_lowercase())
+            .is_none()
+        {
+            anyhow::bail!("No template named '{}'", name);
+        }
+        config.save()?;
+        println!("Removed template '{}'", name.trim());
+    }
+
+    if show {
+        println!("\nCurrent Configuration:");
+        println!(
+            "  Default date range: {} days",
+            config.default_date_range_days
+        );
+        println!("  Report format: {:?}", config.preferred_report_format);
+        println!(
+            "  Round duration: {:?} minutes",
+            config.round_duration_minutes
+        );
+        println!("  Project sort method: {:?}", config.project_sort_method);
+        println!(
+            "  Created with: {}",
+            config
+                .created_with
+                .as_deref()
+                .unwrap_or(toggl::client::DEFAULT_CREATED_WITH)
+        );
+        println!(
+            "  API base URL: {}",
+            config
+                .api_base_url
+                .as_deref()
+                .unwrap_or(toggl::client::DEFAULT_API_BASE_URL)
+        );
+        match config.default_workspace_id {
+            Some(id) => println!("  Default workspace: {}", id),
+            None => println!("  Default workspace: (first available)"),
+        }
+        println!("  Sync chunk window: {} days", config.sync_chunk_days);
+        println!("  Duration format: {:?}", config.duration_format);
+        println!("  TUI grouping at launch: {:?}", config.tui_grouping);
+        println!(
+            "  Conflict check before TUI edits: {}",
+            if config.check_conflicts { "on" } else { "off" }
+        );
+        println!(
+            "  Case-insensitive grouping: {}",
+            if config.case_insensitive_grouping {
+                "on"
+            } else {
+                "off"
+            }
+        );
+        println!(
+            "  Normalize description whitespace: {}",
+            if config.normalize_descriptions {
+                "on"
+            } else {
+                "off"
+            }
+        );
+        println!(
+            "  Export metadata by default: {}",
+            if config.export_include_metadata {
+                "on"
+            } else {
+                "off"
+            }
+        );
+        println!("  Default export grouping: {:?}", config.export_group_by);
+        if config.favorites.is_empty() {
+            println!("  Favorites: (none)");
+        } else {
+            println!("  Favorites:");
+            for (i, favorite) in config.favorites.iter().enumerate() {
+                match favorite.project_id {
+                    Some(pid) => {
+                        println!("    {}. {} (project {})", i + 1, favorite.description, pid)
+                    }
+                    None => println!("    {}. {}", i + 1, favorite.description),
+                }
+            }
+        }
+        println!(
+            "  Default description: {}",
+            config.default_description.as_deref().unwrap_or("(none)")
+        );
+        match config.timer_refresh_seconds {
+            0 => println!("  Timer refresh: disabled"),
+            seconds => println!("  Timer refresh: every {} seconds", seconds),
+        }
+        println!("  Max concurrency: {}", config.max_concurrency);
+        match config.log_retention_days {
+            0 => println!("  Log retention: keep all"),
+            days => println!("  Log retention: {} days", days),
+        }
+        match config.weekly_target_hours {
+            Some(hours) => println!("  Weekly target: {:.2}h", hours),
+            None => println!("  Weekly target: (none)"),
+        }
+        match config.daily_target_hours {
+            Some(hours) => println!("  Daily target: {:.2}h", hours),
+            None => println!("  Daily target: (none)"),
+        }
+        if config.saved_ranges.is_empty() {
+            println!("  Saved ranges: (none)");
+        } else {
+            println!("  Saved ranges:");
+            for (name, range) in &config.saved_ranges {
+                println!("    {}: {} to {}", name, range.start, range.end);
+            }
+        }
+        if config.description_templates.is_empty() {
+            println!("  Description templates: (none)");
+        } else {
+            println!("  Description templates:");
+            for (name, text) in &config.description_templates {
+                println!("    {}: {}", name, text);
+            }
+        }
+        println!(
+            "  API token configured: {}",
+            config.api_token_encrypted.is_some()
+        );
+    }
+
+    if path {
+        println!("{}", Config::path()?.display());
+    }
+
+    if let Some(name) = new_profile {
+        let profile_path = Config::profile_path(&name)?;
+        if profile_path.exists() {
+            anyhow::bail!(
+                "Profile '{}' already exists at {}",
+                name,
+                profile_path.display()
+            );
+        }
+        Config::default().save_to(&profile_path)?;
+        println!("Created profile '{}' at {}", name, profile_path.display());
+        println!(
+            "Set its token with: toggl-timeguru --profile {} config --set-token YOUR_TOKEN",
+            name
+        );
+    }
+
+    if list_profiles {
+        let active = Config::path()?;
+        println!(
+            "{} default",
+            if active == Config::default_path()? {
+                "*"
+            } else {
+                " "
+            }
+        );
+        for name in Config::list_profiles()? {
+            let marker = if active == Config::profile_path(&name)? {
+                "*"
+            } else {
+                " "
+            };
+            println!("{} {}", marker, name);
+        }
+    }
+
+    if db_path {
+        println!("{}", Database::default_path().display());
+    }
+
+    Ok(())
+}
+
+/// Fetches and caches the report's entries, skipping those deleted on the server, and caches
+/// projects too when none are cached yet so the report can name them.
+async fn fetch_report_entries(
+    client: &TogglClient,
+    db: &Database,
+    start_date: chrono::DateTime<Utc>,
+    end_date: chrono::DateTime<Utc>,
+    user_id: Option<i64>,
+) -> Result<Vec<toggl::models::TimeEntry>> {
+    let fetched = sync::fetch_and_cache_entries(client, db, start_date, end_date).await?;
+
+    if db
+        .get_projects(user_id)
+        .map(|p| p.is_empty())
+        .unwrap_or(true)
+        && let Ok(workspaces) = client.get_workspaces().await
+    {
+        for workspace in workspaces {
+            if let Ok(projects) = client.get_projects(workspace.id).await {
+                let _ = db.save_projects(&projects, user_id);
+            }
+        }
+    }
+
+    Ok(fetched)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_report(
+    period: String,
+    project: Option<i64>,
+    start: Option<String>,
+    end: Option<String>,
+    range: Option<String>,
+    offline: bool,
+    round: bool,
+    round_minutes_flag: Option<i64>,
+    round_mode: String,
+    cli_api_token: Option<String>,
+) -> Result<()> {
+    use std::str::FromStr;
+
+    let report_period = report::ReportPeriod::from_str(&period)?;
+    let rounding_mode = report::RoundingMode::from_str(&round_mode)?;
+    let config = Config::load()?;
+    let (start, end) = apply_saved_range(range, start, end, &config)?;
+    let db = open_database(&config)?;
+
+    let round_minutes = match round_minutes_flag {
+        Some(n) if n > 0 => Some(n),
+        Some(n) => anyhow::bail!("--round-minutes must be a positive integer, got {n}"),
+        None if round => Some(config.round_duration_minutes.unwrap_or(15)),
+        None => None,
+    };
+
+    let end_date = if let Some(end_str) = end {
+        if is_date_only(&end_str) {
+            parse_local_date_end(&end_str)?
+        } else {
+            Cli::parse_date(&end_str)?
+        }
+    } else {
+        Utc::now()
+    };
+    let start_date = if let Some(start_str) = start {
+        if is_date_only(&start_str) {
+            parse_local_date_start(&start_str)?
+        } else {
+            Cli::parse_date(&start_str)?
+        }
+    } else {
+        end_date - config.default_date_range()
+    };
+
+    if start_date > end_date {
+        anyhow::bail!(
+            "--start ({}) must not be after --end ({})",
+            start_date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+            end_date
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M"),
+        );
+    }
+
+    let mut entries = if offline {
+        let entries = db.get_time_entries(start_date, end_date, config.current_user_id)?;
+        if let Some(last_synced) = entries.iter().filter_map(|e| e.synced_at).max() {
+            println!(
+                "Using cached data (last synced {})",
+                last_synced
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            );
+        }
+        entries
+    } else {
+        let api_token = get_api_token(cli_api_token, &config)?;
+        let client = new_client(api_token, &config)?;
+        fetch_report_entries(&client, &db, start_date, end_date, config.current_user_id).await?
+    };
+
+    if let Some(project_id) = project {
+        entries = filter_by_project(entries, project_id);
+    }
+
+    let projects = db.get_projects(config.current_user_id).unwrap_or_default();
+    let report = report::generate(
+        &entries,
+        &projects,
+        report_period,
+        start_date,
+        end_date,
+        round_minutes,
+        rounding_mode,
+    );
+    report::print_text(&report);
+
+    if let Some(target) = config.weekly_target_hours {
+        let now = chrono::Local::now();
+        let (week_start, week_end) = report::current_week_bounds(now);
+        let week_entries = db.get_time_entries(week_start, week_end, config.current_user_id)?;
+        report::print_target_progress(
+            "This week",
+            &report::weekly_progress(&week_entries, target, now),
+            io::stdout().is_terminal(),
+        );
+    }
+
+    Ok(())
+}
+
+fn is_date_only(s: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").is_ok()
+}
 
 fn parse_local_date_start(s: &str) -> Result<chrono::DateTime<Utc>> {
     use chrono::{Local, TimeZone};
@@ -375,17 +1186,44 @@ fn parse_local_date_end(s: &str) -> Result<chrono::DateTime<Utc>> {
     Ok(local_next.with_timezone(&Utc) - Duration::seconds(1))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn handle_list(
     start: Option<String>,
     end: Option<String>,
-    project: Option<i64>,
+    range: Option<String>,
+    project: Vec<i64>,
     tag: Option<String>,
+    tag_ids: Vec<i64>,
+    description_regex: Option<String>,
+    case_insensitive: bool,
+    workspace: Option<i64>,
     group: bool,
+    case_insensitive_group: bool,
+    group_by: Option<String>,
+    top: Option<usize>,
+    merge_below: Option<i64>,
+    sort: Option<String>,
+    only_running: bool,
+    newer_than: Option<String>,
+    older_than: Option<String>,
+    min_duration: Option<String>,
+    max_duration: Option<String>,
     offline: bool,
+    round: Option<i64>,
+    no_round: bool,
     cli_api_token: Option<String>,
 ) -> Result<()> {
     let config = Config::load()?;
-    let db = Database::new(None)?;
+    let (start, end) = apply_saved_range(range, start, end, &config)?;
+    let round_minutes = resolve_round_minutes(round, no_round, &config)?;
+    let merge_below = resolve_merge_below(merge_below)?;
+    let group_by = resolve_group_by(group, group_by)?;
+    let sort = sort.map(|s| s.parse::<SortOrder>()).transpose()?;
+    let description_regex = resolve_description_regex(description_regex, case_insensitive)?;
+    let newer_than = newer_than.as_deref().map(parse_duration).transpose()?;
+    let older_than = older_than.as_deref().map(parse_duration).transpose()?;
+    let (min_secs, max_secs) = resolve_duration_range(min_duration, max_duration)?;
+    let db = open_database(&config)?;
 
     let end_date = if let Some(end_str) = end {
         Cli::parse_date(&end_str)?
@@ -400,66 +1238,173 @@ async fn handle_list(
     };
 
     let mut entries = if offline {
-        db.get_time_entries(start_date, end_date, config.current_user_id)?
+        let entries = db.get_time_entries(start_date, end_date, config.current_user_id)?;
+        if let Some(last_synced) = entries.iter().filter_map(|e| e.synced_at).max() {
+            println!(
+                "Using cached data (last synced {})",
+                last_synced
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+            );
+        }
+        entries
     } else {
         let api_token = get_api_token(cli_api_token, &config)?;
-        let client = TogglClient::new(api_token)?;
-
-        let entries = client.get_time_entries(start_date, end_date).await?;
-        db.save_time_entries(&entries)?;
-        db.update_sync_metadata("time_entries", entries.last().map(|e| e.id))?;
+        let client = new_client(api_token, &config)?;
 
-        entries
+        sync::fetch_and_cache_entries(&client, &db, start_date, end_date).await?
     };
 
-    if let Some(project_id) = project {
-        entries = filter_by_project(entries, project_id);
-    }
+    entries = filter_by_projects(entries, &project.into_iter().collect());
 
     if let Some(tag_name) = tag {
         entries = filter_by_tag(entries, &tag_name);
     }
+    entries = filter_by_tag_id(entries, &tag_ids.into_iter().collect());
+
+    if let Some(pattern) = &description_regex {
+        entries = filter_by_description_regex(entries, pattern);
+    }
+
+    if let Some(workspace_id) = workspace {
+        entries = filter_by_workspace(entries, workspace_id);
+    }
+
+    let now = Utc::now();
+    if let Some(age) = newer_than {
+        entries = filter_newer_than(entries, age, now);
+    }
+    if let Some(age) = older_than {
+        entries = filter_older_than(entries, age, now);
+    }
+    if min_secs.is_some() || max_secs.is_some() {
+        entries = filter_by_duration_range(entries, min_secs, max_secs);
+    }
 
-    if group {
-        let grouped = group_by_description(entries);
-        println!("\nGrouped Time Entries ({} groups):", grouped.len());
-        println!("{:<60} {:>10} {:>10}", "Description", "Duration", "Entries");
+    if only_running {
+        entries = filter_running(entries);
+        if entries.len() > 1 {
+            println!(
+                "Warning: {} timers are running at once; stop the ones you forgot with 'track stop' or in Toggl",
+                entries.len()
+            );
+        }
+    }
+
+    if let Some(mode) = group_by {
+        let mut grouped = tracing::info_span!("group", ?mode).in_scope(|| -> Result<_> {
+            Ok(match mode {
+                GroupBy::Description => group_by_description(
+                    entries,
+                    case_insensitive_group || config.case_insensitive_grouping,
+                ),
+                GroupBy::Client => {
+                    group_by_client(entries, &db.get_projects(config.current_user_id)?)
+                }
+                GroupBy::Tag => group_by_tag(entries),
+            })
+        })?;
+        let _render = tracing::info_span!("render").entered();
+        let label = match mode {
+            GroupBy::Description => "Description",
+            GroupBy::Client => "Client",
+            GroupBy::Tag => "Tag",
+        };
+        let mut merged_groups = 0;
+        if let Some(minutes) = merge_below {
+            (grouped, merged_groups) = merge_small_groups(grouped, minutes * 60);
+        }
+        let total_groups = grouped.len();
+        if let Some(n) = top {
+            grouped = top_groups(grouped, n);
+        }
+        if let Some(order) = sort {
+            grouped = sort_groups(grouped, order);
+        }
+        println!("\nGrouped Time Entries ({} groups):", total_groups);
+        println!("{:<60} {:>10} {:>10}", label, "Duration", "Entries");
         println!("{}", "-".repeat(82));
 
-        for entry in grouped {
+        for entry in &grouped {
             let desc = entry
                 .description
                 .clone()
                 .unwrap_or_else(|| "(No description)".to_string());
-            let hours = if let Some(round_min) = config.round_duration_minutes {
-                entry.rounded_hours(round_min)
+            let seconds = if let Some(round_min) = round_minutes {
+                entry.rounded_duration(round_min)
             } else {
-                entry.total_hours()
+                entry.total_duration
             };
 
             println!(
-                "{:<60} {:>9.2}h {:>10}",
+                "{:<60} {:>10} {:>10}",
                 truncate(&desc, 60),
-                hours,
+                format_hours(seconds, config.duration_format),
                 entry.entries.len()
             );
         }
+
+        if grouped.len() < total_groups {
+            println!("(showing top {} of {})", grouped.len(), total_groups);
+        }
+        if merged_groups > 0 {
+            println!(
+                "({} groups under {} min merged into {})",
+                merged_groups,
+                merge_below.unwrap_or_default(),
+                OTHER_GROUP_LABEL
+            );
+        }
+        if mode == GroupBy::Tag {
+            println!("(entries with several tags are counted under each tag)");
+        }
     } else {
+        if let Some(order) = sort {
+            entries = sort_entries(entries, order);
+        }
+        let _render = tracing::info_span!("render").entered();
+        let project_map: std::collections::HashMap<i64, String> = db
+            .get_projects(config.current_user_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| (p.id, p.name))
+            .collect();
+        let rows: Vec<(&toggl::models::TimeEntry, &str, &str)> = entries
+            .iter()
+            .map(|entry| {
+                let project = entry
+                    .project_id
+                    .and_then(|pid| project_map.get(&pid))
+                    .map_or("", String::as_str);
+                let desc = entry.description.as_deref().unwrap_or("(No description)");
+                (entry, project, desc)
+            })
+            .collect();
+        let project_width = column_width(rows.iter().map(|r| r.1), "Project", 30);
+        let desc_width = column_width(rows.iter().map(|r| r.2), "Description", 60);
+
         println!("\nTime Entries ({}):", entries.len());
-        println!("{:<20} {:<60} {:>10}", "Date", "Description", "Duration");
-        println!("{}", "-".repeat(92));
+        println!(
+            "{:<20} {:<project_width$} {:<desc_width$} {:>10}",
+            "Date", "Project", "Description", "Duration"
+        );
+        println!("{}", "-".repeat(20 + project_width + desc_width + 13));
 
-        for entry in entries {
-            let desc = entry
-                .description
-                .unwrap_or_else(|| "(No description)".to_string());
-            let hours = entry.duration as f64 / 3600.0;
+        for (entry, project, desc) in rows {
+            let seconds = if is_running(entry) {
+                elapsed_seconds(entry, now)
+            } else if let Some(round_min) = round_minutes {
+                entry.rounded_duration(round_min)
+            } else {
+                entry.duration
+            };
 
             println!(
-                "{:<20} {:<60} {:>9.2}h",
+                "{:<20} {:<project_width$} {:<desc_width$} {:>10}",
                 entry.start.format("%Y-%m-%d %H:%M"),
-                truncate(&desc, 60),
-                hours
+                truncate(project, project_width),
+                truncate(desc, desc_width),
+                format_hours(seconds, config.duration_format)
             );
         }
     }
@@ -467,98 +1412,396 @@ async fn handle_list(
     Ok(())
 }
 
-async fn handle_sync(
-    start: Option<String>,
-    end: Option<String>,
+async fn handle_standup(
+    date: String,
+    copy: bool,
+    offline: bool,
+    round: Option<i64>,
+    no_round: bool,
     cli_api_token: Option<String>,
 ) -> Result<()> {
-    let mut config = Config::load()?;
-    let api_token = get_api_token(cli_api_token, &config)?;
-    let client = TogglClient::new(api_token)?;
-    let db = Database::new(None)?;
-
-    let user_id = client.get_current_user_id().await?;
-    let user_email = client.get_current_user_email().await?;
-
-    if config.current_user_id.is_none() {
-        config.current_user_id = Some(user_id);
-        config.current_user_email = Some(user_email.clone());
-        config.save()?;
-        println!("Configured for user: {}", user_email);
-    } else if config.current_user_id != Some(user_id) {
-        println!("Switching to new user account: {}", user_email);
-        println!("Previous data will not be visible.");
-        println!("Use 'toggl-timeguru clean --data' to remove old data if needed.");
-        config.current_user_id = Some(user_id);
-        config.current_user_email = Some(user_email);
-        config.save()?;
-    }
-
-    let end_date = if let Some(end_str) = end {
-        Cli::parse_date(&end_str)?
+    let config = Config::load()?;
+    let round_minutes = resolve_round_minutes(round, no_round, &config)?;
+    let day = config::resolve_date_expr(&date, chrono::Local::now().date_naive())?;
+    let day_str = day.format("%Y-%m-%d").to_string();
+    let start_date = parse_local_date_start(&day_str)?;
+    let end_date = parse_local_date_end(&day_str)?;
+    let db = open_database(&config)?;
+
+    let entries = if offline {
+        db.get_time_entries(start_date, end_date, config.current_user_id)?
     } else {
-        Utc::now()
+        let api_token = get_api_token(cli_api_token, &config)?;
+        let client = new_client(api_token, &config)?;
+        sync::fetch_and_cache_entries(&client, &db, start_date, end_date).await?
     };
 
-    let start_date = if let Some(start_str) = start {
-        Cli::parse_date(&start_str)?
-    } else {
-        end_date - Duration::days(90)
-    };
+    if entries.is_empty() {
+        println!("No time entries on {}.", day_str);
+        return Ok(());
+    }
 
-    println!(
-        "Syncing time entries from {} to {}...",
-        start_date.format("%Y-%m-%d"),
-        end_date.format("%Y-%m-%d")
+    let projects: std::collections::HashMap<i64, toggl::models::Project> = db
+        .get_all_projects(config.current_user_id)?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    let notes = report::standup_summary(
+        &entries,
+        &projects,
+        round_minutes,
+        config.case_insensitive_grouping,
+        Utc::now(),
     );
+    print!("{}", notes);
+
+    if copy {
+        match ui::app::copy_text_before_exit(&notes) {
+            ui::app::CopyOutcome::Clipboard => eprintln!("Copied to clipboard"),
+            ui::app::CopyOutcome::Terminal => eprintln!("Copied to clipboard (via terminal)"),
+            ui::app::CopyOutcome::File(path) => {
+                eprintln!("Clipboard unavailable; saved to {}", path.display())
+            }
+            ui::app::CopyOutcome::Failed => eprintln!("Clipboard unavailable"),
+        }
+    }
 
-    let local_ids = db.get_entry_ids_in_range(start_date, end_date, config.current_user_id)?;
-
-    let entries = client.get_time_entries(start_date, end_date).await?;
+    Ok(())
+}
 
-    let api_ids: std::collections::HashSet<i64> = entries.iter().map(|e| e.id).collect();
+async fn handle_today(yesterday: bool, offline: bool, cli_api_token: Option<String>) -> Result<()> {
+    let config = Config::load()?;
+    let db = open_database(&config)?;
+    let now = Utc::now();
+    let day = chrono::Local::now().date_naive() - Duration::days(i64::from(yesterday));
+    let day_str = day.format("%Y-%m-%d").to_string();
+    let start_date = parse_local_date_start(&day_str)?;
+    let end_date = parse_local_date_end(&day_str)?;
+    let label = if yesterday { "Yesterday" } else { "Today" };
+
+    let (mut entries, running) = if offline {
+        let entries = db.get_time_entries(start_date, end_date, config.current_user_id)?;
+        let running = entries.iter().find(|e| e.duration < 0).cloned();
+        (entries, running)
+    } else {
+        let api_token = get_api_token(cli_api_token, &config)?;
+        let client = new_client(api_token, &config)?;
+        let entries = sync::fetch_and_cache_entries(&client, &db, start_date, end_date).await?;
+        let running = if yesterday {
+            None
+        } else {
+            client.get_current_time_entry().await?
+        };
+        (entries, running)
+    };
+    entries = sort_entries(entries, SortOrder::DateAsc);
 
-    let deleted_ids: Vec<i64> = local_ids
-        .into_iter()
-        .filter(|id| !api_ids.contains(id))
-        .collect();
+    println!("\n{} ({}): {} entries", label, day_str, entries.len());
+    println!("{:<7} {:<60} {:>10}", "Start", "Description", "Duration");
+    println!("{}", "-".repeat(79));
+    for entry in &entries {
+        let seconds = elapsed_seconds(entry, now);
+        println!(
+            "{:<7} {:<60} {:>10}",
+            entry.start.with_timezone(&chrono::Local).format("%H:%M"),
+            truncate(
+                entry.description.as_deref().unwrap_or("(No description)"),
+                60
+            ),
+            format_hours(seconds, config.duration_format)
+        );
+    }
 
-    if !deleted_ids.is_empty() {
-        let deleted_count = db.delete_entries_by_ids(&deleted_ids)?;
+    if let Some(entry) = &running {
         println!(
-            "Deleted {} time entries that were removed from Toggl",
-            deleted_count
+            "\nRunning: {} (since {}, {})",
+            entry.description.as_deref().unwrap_or("(No description)"),
+            entry.start.with_timezone(&chrono::Local).format("%H:%M"),
+            format_hours(
+                (now - entry.start).num_seconds().max(0),
+                config.duration_format
+            )
         );
     }
 
-    let count = db.save_time_entries(&entries)?;
-    db.update_sync_metadata("time_entries", entries.last().map(|e| e.id))?;
+    let mut counted = entries;
+    if let Some(entry) = running
+        && !counted.iter().any(|e| e.id == entry.id)
+    {
+        counted.push(entry);
+    }
+    let tracked = report::tracked_seconds(&counted, start_date, end_date, now);
+    match config.daily_target_hours {
+        Some(target) => {
+            println!();
+            report::print_target_progress(
+                label,
+                &report::TargetProgress::new(tracked, target),
+                io::stdout().is_terminal(),
+            );
+        }
+        None => println!(
+            "\nTotal: {}\n",
+            format_hours(tracked, config.duration_format)
+        ),
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn handle_sync(
+    start: Option<String>,
+    end: Option<String>,
+    entries_only: bool,
+    projects_only: bool,
+    reconcile: bool,
+    durable: bool,
+    concurrency: Option<usize>,
+    chunk_days: Option<i64>,
+    wipe_on_switch: Option<bool>,
+    cli_api_token: Option<String>,
+    workspace_override: Option<i64>,
+) -> Result<()> {
+    let mut config = Config::load()?;
+    let concurrency = resolve_concurrency(concurrency, &config)?;
+    let chunk_days = resolve_chunk_days(chunk_days, &config)?;
+    let api_token = get_api_token(cli_api_token, &config)?;
+    let client = new_client(api_token, &config)?;
+    let db = open_database(&config)?;
+    if durable {
+        db.enable_durable_writes()?;
+    }
+
+    sync_account(
+        &client,
+        &db,
+        &mut config,
+        start,
+        end,
+        workspace_override,
+        !projects_only,
+        !entries_only,
+        reconcile,
+        concurrency,
+        chunk_days,
+        wipe_on_switch,
+    )
+    .await?;
 
-    println!("Successfully synced {} time entries", count);
+    if durable {
+        db.checkpoint()?;
+    }
 
-    println!("Syncing projects and workspaces...");
+    Ok(())
+}
 
-    let workspaces = client.get_workspaces().await?;
-    let mut total_projects = 0;
+#[allow(clippy::too_many_arguments)]
+async fn sync_account(
+    client: &impl TogglApi,
+    db: &Database,
+    config: &mut Config,
+    start: Option<String>,
+    end: Option<String>,
+    workspace_override: Option<i64>,
+    entries: bool,
+    projects: bool,
+    reconcile: bool,
+    concurrency: usize,
+    chunk_days: i64,
+    wipe_on_switch: Option<bool>,
+) -> Result<()> {
+    use std::io::Write;
+
+    let user_id = client.get_current_user_id().await?;
+    let user_email = client.get_current_user_email().await?;
+
+    if config.current_user_id.is_none() {
+        config.current_user_id = Some(user_id);
+        config.current_user_email = Some(user_email.clone());
+        config.save()?;
+        println!("Configured for user: {}", user_email);
+    } else if let Some(previous_id) = config.current_user_id.filter(|id| *id != user_id) {
+        let previous_email = config
+            .current_user_email
+            .clone()
+            .unwrap_or_else(|| format!("user {}", previous_id));
+        println!("Switching to new user account: {}", user_email);
+
+        // Without --keep-data/--wipe-data, ask when someone can answer and keep the data otherwise.
+        let wipe = match wipe_on_switch {
+            Some(wipe) => wipe,
+            None if io::stdin().is_terminal() => {
+                print!("Delete the cached data of {} now? (y/N): ", previous_email);
+                io::stdout().flush()?;
+                let mut input = String::new();
+                io::stdin().read_line(&mut input)?;
+                input.trim().eq_ignore_ascii_case("y")
+            }
+            None => false,
+        };
+
+        if wipe {
+            let removed = db.delete_all_for_user(previous_id)?;
+            println!(
+                "Removed {} cached time entries, workspaces and projects of {}",
+                removed, previous_email
+            );
+        } else {
+            println!("Previous data will not be visible.");
+            println!(
+                "Use 'toggl-timeguru sync --wipe-data' or 'toggl-timeguru clean --data' to remove old data if needed."
+            );
+        }
+        config.current_user_id = Some(user_id);
+        config.current_user_email = Some(user_email);
+        config.save()?;
+    }
+
+    // Checked before anything is synced so a mistyped --workspace fails fast.
+    let requested_workspace = match workspace_override {
+        Some(workspace_id) => {
+            let workspaces = client.get_workspaces().await?;
+            db.save_workspaces(&workspaces, config.current_user_id)?;
+            Some(resolve_workspace(&workspaces, Some(workspace_id))?.clone())
+        }
+        None => None,
+    };
+
+    if entries {
+        let end_date = if let Some(end_str) = end {
+            Cli::parse_date(&end_str)?
+        } else {
+            Utc::now()
+        };
+
+        let start_date = if let Some(start_str) = start {
+            Cli::parse_date(&start_str)?
+        } else {
+            end_date - Duration::days(90)
+        };
+
+        println!(
+            "Syncing time entries from {} to {}...",
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        );
+
+        let windows = sync::sync_windows(start_date, end_date, chunk_days);
+        let mut count = 0;
+        let mut stale_count = 0;
+        let mut deleted_count = 0;
+        let mut by_workspace: std::collections::BTreeMap<i64, usize> =
+            std::collections::BTreeMap::new();
+
+        for (index, (window_start, window_end)) in windows.iter().copied().enumerate() {
+            if windows.len() > 1 {
+                print!(
+                    "  [{}/{}] {} to {}...",
+                    index + 1,
+                    windows.len(),
+                    window_start.format("%Y-%m-%d"),
+                    window_end.format("%Y-%m-%d")
+                );
+                io::stdout().flush()?;
+            }
+
+            let result = sync::sync_window(
+                client,
+                db,
+                window_start,
+                window_end,
+                config.current_user_id,
+                reconcile,
+            )
+            .await?;
+            if windows.len() > 1 {
+                println!(" {} entries", result.saved);
+            }
+            count += result.saved;
+            stale_count += result.stale;
+            deleted_count += result.deleted;
+            for (workspace_id, entries) in result.by_workspace {
+                *by_workspace.entry(workspace_id).or_default() += entries;
+            }
+        }
+
+        if reconcile {
+            println!(
+                "Removed {} stale time entries that were deleted on Toggl",
+                deleted_count
+            );
+        } else if stale_count > 0 {
+            println!(
+                "{} cached time entries no longer exist on Toggl. Run 'sync --reconcile' to remove them.",
+                stale_count
+            );
+        }
 
-    for workspace in workspaces {
-        let projects = client.get_projects(workspace.id).await?;
-        let project_count = db.save_projects(&projects)?;
-        total_projects += project_count;
+        println!("Successfully synced {} time entries", count);
+        print_workspace_counts(db, config.current_user_id, &by_workspace);
     }
 
-    println!("Successfully synced {} projects", total_projects);
+    if projects {
+        println!("Syncing projects and workspaces...");
+
+        let workspaces = match requested_workspace {
+            Some(workspace) => vec![workspace],
+            None => {
+                let workspaces = client.get_workspaces().await?;
+                db.save_workspaces(&workspaces, config.current_user_id)?;
+                workspaces
+            }
+        };
+        let total_projects =
+            sync::sync_projects(client, db, &workspaces, config.current_user_id, concurrency)
+                .await?;
+
+        println!("Successfully synced {} projects", total_projects);
+    }
 
     Ok(())
 }
 
+/// Entries synced per workspace, named from the workspace cache where possible.
+fn print_workspace_counts(
+    db: &Database,
+    user_id: Option<i64>,
+    counts: &std::collections::BTreeMap<i64, usize>,
+) {
+    if counts.is_empty() {
+        return;
+    }
+    let names: std::collections::HashMap<i64, String> = db
+        .get_workspaces(user_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|w| (w.id, w.name))
+        .collect();
+    let name_width = column_width(names.values().map(String::as_str), "Workspace", 40);
+
+    println!();
+    println!("{:<12} {:<name_width$} {:>8}", "ID", "Workspace", "Entries");
+    println!("{}", "-".repeat(12 + name_width + 8 + 2));
+    for (workspace_id, entries) in counts {
+        let name = names.get(workspace_id).map(String::as_str).unwrap_or("-");
+        println!(
+            "{:<12} {:<name_width$} {:>8}",
+            workspace_id,
+            truncate(name, name_width),
+            entries
+        );
+    }
+}
+
 async fn handle_tui(
     start: Option<String>,
     end: Option<String>,
     cli_api_token: Option<String>,
+    workspace_override: Option<i64>,
 ) -> Result<()> {
     let config = Config::load()?;
-    let db = std::sync::Arc::new(Database::new(None)?);
+    let db = std::sync::Arc::new(open_database(&config)?);
 
     let end_date = if let Some(end_str) = end {
         Cli::parse_date(&end_str)?
@@ -572,8 +1815,8 @@ async fn handle_tui(
         end_date - config.default_date_range()
     };
 
-    let entries = db
-        .get_time_entries(start_date, end_date, config.current_user_id)
+    let entries = tracing::info_span!("db_load")
+        .in_scope(|| db.get_time_entries(start_date, end_date, config.current_user_id))
         .context("Failed to load time entries. Try running 'sync' first.")?;
 
     if entries.is_empty() {
@@ -581,7 +1824,7 @@ async fn handle_tui(
         return Ok(());
     }
 
-    let projects = db.get_projects().unwrap_or_default();
+    let projects = db.get_projects(config.current_user_id).unwrap_or_default();
 
     let usage_window_start = Utc::now() - Duration::days(30);
     let usage_entries = db
@@ -595,13 +1838,41 @@ async fn handle_tui(
     }
 
     let client = match get_api_token(cli_api_token, &config) {
-        Ok(token) => match TogglClient::new(token) {
-            Ok(c) => Some(std::sync::Arc::new(c)),
+        Ok(token) => match new_client(token, &config) {
+            Ok(c) => {
+                // stderr is hidden behind the alternate screen; API errors reach the status line.
+                let c = c.with_verbose_errors(false);
+                let c = match config.created_with.clone() {
+                    Some(name) => c.with_created_with(name),
+                    None => c,
+                };
+                Some(std::sync::Arc::new(c))
+            }
             Err(_) => None,
         },
         Err(_) => None,
     };
 
+    let running_entry = match &client {
+        Some(c) => c.get_current_time_entry().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to fetch running time entry: {}", e);
+            None
+        }),
+        None => None,
+    };
+
+    let weekly_progress = match config.weekly_target_hours {
+        Some(target) => {
+            let now = chrono::Local::now();
+            let (week_start, week_end) = report::current_week_bounds(now);
+            let week_entries = db
+                .get_time_entries(week_start, week_end, config.current_user_id)
+                .unwrap_or_default();
+            Some(report::weekly_progress(&week_entries, target, now))
+        }
+        None => None,
+    };
+
     let runtime_handle = Some(tokio::runtime::Handle::current());
 
     enable_raw_mode()?;
@@ -625,8 +1896,17 @@ async fn handle_tui(
         config.project_sort_method,
         config.saved_filter.clone(),
     );
-    let grouped = group_by_description(app.time_entries.clone());
-    app.grouped_entries = grouped;
+    app.case_insensitive_grouping = config.case_insensitive_grouping;
+    app.set_grouping(config.tui_grouping);
+    app.running_entry = running_entry;
+    app.default_workspace_id = workspace_override.or(config.default_workspace_id);
+    app.duration_format = config.duration_format;
+    app.check_conflicts = config.check_conflicts;
+    app.favorites = config.favorites.clone();
+    app.weekly_progress = weekly_progress;
+    app.current_user_id = config.current_user_id;
+    app.set_recent_projects(config.recent_project_ids.clone());
+    app.start_timer_refresh(std::time::Duration::from_secs(config.timer_refresh_seconds));
 
     let res = app.run(&mut terminal);
 
@@ -636,6 +1916,10 @@ async fn handle_tui(
 
     let mut updated_config = Config::load().unwrap_or(config);
     updated_config.saved_filter = app.persisted_filter();
+    updated_config.recent_project_ids = app.recent_project_ids.clone();
+    if app.save_rounding {
+        updated_config.round_duration_minutes = app.display_rounding();
+    }
     if let Err(e) = updated_config.save() {
         tracing::warn!("Failed to persist filter state: {}", e);
     }
@@ -647,7 +1931,13 @@ async fn handle_tui(
     Ok(())
 }
 
-async fn handle_clean(all: bool, data: bool, config: bool, confirm: bool) -> Result<()> {
+async fn handle_clean(
+    all: bool,
+    data: bool,
+    config: bool,
+    confirm: bool,
+    no_backup: bool,
+) -> Result<()> {
     use std::io::{self, Write};
 
     let delete_data = all || data;
@@ -661,13 +1951,8 @@ async fn handle_clean(all: bool, data: bool, config: bool, confirm: bool) -> Res
         return Ok(());
     }
 
-    let db_path = dirs::data_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("toggl-timeguru")
-        .join("timeguru.db");
-
-    let config_path = confy::get_configuration_file_path("toggl-timeguru", "config")
-        .unwrap_or_else(|_| std::path::PathBuf::from("~/.config/toggl-timeguru/config.toml"));
+    let db_path = Database::default_path();
+    let config_path = Config::path().context("Failed to resolve configuration file path")?;
 
     println!("\nThe following will be deleted:");
     if delete_data {
@@ -690,12 +1975,33 @@ async fn handle_clean(all: bool, data: bool, config: bool, confirm: bool) -> Res
         }
     }
 
+    if !no_backup {
+        if delete_data && db_path.exists() {
+            // Fold the write-ahead log into the database file so the backup is complete.
+            Database::new(Some(db_path.clone()))?
+                .checkpoint()
+                .context("Backup failed, nothing was deleted (use --no-backup to skip it)")?;
+        }
+        let targets = [(delete_data, &db_path), (delete_config, &config_path)];
+        for (_, path) in targets
+            .iter()
+            .filter(|(delete, path)| *delete && path.exists())
+        {
+            let copy = backup::backup_before_delete(path)
+                .context("Backup failed, nothing was deleted (use --no-backup to skip it)")?;
+            println!("Backed up {} to {}", path.display(), copy.display());
+        }
+    }
+
     let mut deleted_items = Vec::new();
     let mut errors = Vec::new();
 
     if delete_data && db_path.exists() {
         match std::fs::remove_file(&db_path) {
             Ok(_) => {
+                for leftover in Database::wal_paths(&db_path) {
+                    let _ = std::fs::remove_file(leftover);
+                }
                 deleted_items.push(format!("Database: {}", db_path.display()));
                 let parent_dir = db_path.parent();
                 if let Some(dir) = parent_dir
@@ -752,18 +2058,181 @@ async fn handle_clean(all: bool, data: bool, config: bool, confirm: bool) -> Res
     Ok(())
 }
 
+fn handle_restore(index: Option<usize>, confirm: bool) -> Result<()> {
+    use std::io::Write;
+
+    let dir = backup::backup_dir();
+    let backups = backup::list_backups(&dir)?;
+    if backups.is_empty() {
+        println!("No backups found in {}", dir.display());
+        return Ok(());
+    }
+
+    let Some(index) = index else {
+        println!("Backups in {} (newest first):", dir.display());
+        for (i, b) in backups.iter().enumerate() {
+            println!(
+                "  {:>2}. {:<20} {}",
+                i + 1,
+                b.original_name,
+                b.created.format("%Y-%m-%d %H:%M:%S")
+            );
+        }
+        println!("\nRestore one with: toggl-timeguru restore <number>");
+        return Ok(());
+    };
+
+    let chosen = index
+        .checked_sub(1)
+        .and_then(|i| backups.get(i))
+        .with_context(|| format!("No backup number {index}; there are {}", backups.len()))?;
+
+    let db_path = Database::default_path();
+    let config_path = Config::path()?;
+    let restoring_db = db_path.file_name().and_then(|n| n.to_str()) == Some(&chosen.original_name);
+    let target = if restoring_db {
+        db_path
+    } else if config_path.file_name().and_then(|n| n.to_str()) == Some(&chosen.original_name) {
+        config_path
+    } else {
+        Config::default_path()?
+            .parent()
+            .context("Configuration file path has no parent directory")?
+            .join(&chosen.original_name)
+    };
+
+    if target.exists() && !confirm {
+        print!("{} already exists. Overwrite it? (y/N): ", target.display());
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    if restoring_db {
+        // A leftover write-ahead log belongs to the database being replaced.
+        for leftover in Database::wal_paths(&target) {
+            let _ = std::fs::remove_file(leftover);
+        }
+    }
+    std::fs::copy(&chosen.path, &target).with_context(|| {
+        format!(
+            "Failed to restore {} to {}",
+            chosen.path.display(),
+            target.display()
+        )
+    })?;
+    println!(
+        "Restored {} from {} to {}",
+        chosen.original_name,
+        chosen.created.format("%Y-%m-%d %H:%M:%S"),
+        target.display()
+    );
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn handle_export(
     start: Option<String>,
     end: Option<String>,
-    output: String,
+    range: Option<String>,
+    output: Option<String>,
+    split: Option<(String, String)>,
+    format: String,
     include_metadata: bool,
+    no_metadata: bool,
+    no_header: bool,
+    metadata_style: String,
     group: bool,
     group_by_day: bool,
+    split_midnight: bool,
+    case_insensitive_group: bool,
+    group_by: Option<String>,
+    no_group: bool,
+    merge_below: Option<i64>,
+    sort: Option<String>,
+    round: Option<i64>,
+    no_round: bool,
+    with_raw: bool,
+    include_color: bool,
+    tag_ids: Vec<i64>,
+    description_regex: Option<String>,
+    case_insensitive: bool,
+    min_duration: Option<String>,
+    max_duration: Option<String>,
+    excel: bool,
+    workspace: Option<i64>,
 ) -> Result<()> {
-    use std::fs::File;
+    use std::io::Write;
+    use std::str::FromStr;
 
+    let format = ExportFormat::from_str(&format)?;
+    let metadata_style = MetadataStyle::from_str(&metadata_style)?;
     let config = Config::load()?;
-    let db = Database::new(None)?;
+
+    // Config defaults fill in for flags that weren't given, where they can apply to this export.
+    let csv_file_or_stream = format == ExportFormat::Csv && split.is_none();
+    let mut group_by_day = group_by_day;
+    let mut group_by = group_by;
+    let mut group = group;
+    if csv_file_or_stream && !no_group && !group && !group_by_day && group_by.is_none() {
+        match config.export_group_by {
+            ExportGrouping::None => {}
+            ExportGrouping::Description => group = true,
+            ExportGrouping::Day => group_by_day = true,
+            ExportGrouping::Client => group_by = Some("client".to_string()),
+            ExportGrouping::Tag => group_by = Some("tag".to_string()),
+        }
+    }
+    let to_file = output.as_deref().is_some_and(|path| path != "-");
+    let include_metadata = include_metadata
+        || (config.export_include_metadata
+            && csv_file_or_stream
+            && !no_metadata
+            && !no_header
+            && (to_file || metadata_style != MetadataStyle::Sidecar));
+
+    let merge_below = resolve_merge_below(merge_below)?;
+    let group_by = resolve_group_by(group, group_by)?;
+    let sort = sort.map(|s| s.parse::<SortOrder>()).transpose()?;
+    let description_regex = resolve_description_regex(description_regex, case_insensitive)?;
+    let (min_secs, max_secs) = resolve_duration_range(min_duration, max_duration)?;
+    let group = group_by == Some(GroupBy::Description);
+    let rollup = group_by.filter(|mode| *mode != GroupBy::Description);
+    if format == ExportFormat::Parquet
+        && (group
+            || group_by_day
+            || rollup.is_some()
+            || include_metadata
+            || include_color
+            || with_raw)
+    {
+        anyhow::bail!(
+            "--format parquet writes individual entries; grouping, metadata, color and raw duration options are CSV-only"
+        );
+    }
+    if format == ExportFormat::Parquet && excel {
+        anyhow::bail!("--excel only applies to CSV output");
+    }
+    if merge_below.is_some() && !group && !group_by_day && rollup.is_none() {
+        anyhow::bail!("--merge-below requires --group, --group-by-day or --group-by");
+    }
+    let (start, end) = apply_saved_range(range, start, end, &config)?;
+    let round_minutes = resolve_round_minutes(round, no_round, &config)?;
+    let fold_case = case_insensitive_group || config.case_insensitive_grouping;
+    // Rounding minutes for the Duration column when a Raw column goes next to it.
+    let raw_column_round = round_minutes.filter(|_| with_raw);
+    if with_raw && raw_column_round.is_none() {
+        eprintln!("Note: --with-raw has no effect without rounding (use --round or config)");
+    }
+    let db = open_database(&config)?;
 
     let end_date = if let Some(end_str) = end {
         Cli::parse_date(&end_str)?
@@ -777,76 +2246,224 @@ async fn handle_export(
         end_date - config.default_date_range()
     };
 
-    let entries = db.get_time_entries(start_date, end_date, config.current_user_id)?;
+    let mut entries = tracing::info_span!("db_load")
+        .in_scope(|| db.get_time_entries(start_date, end_date, config.current_user_id))?;
+    if let Some(workspace_id) = workspace {
+        entries = filter_by_workspace(entries, workspace_id);
+    }
+    entries = filter_by_tag_id(entries, &tag_ids.into_iter().collect());
+    if let Some(pattern) = &description_regex {
+        entries = filter_by_description_regex(entries, pattern);
+    }
+    if min_secs.is_some() || max_secs.is_some() {
+        entries = filter_by_duration_range(entries, min_secs, max_secs);
+    }
+    if let Some(order) = sort {
+        entries = sort_entries(entries, order);
+    }
+
+    let output = output.filter(|path| path != "-");
 
     if entries.is_empty() {
-        println!("No time entries found for the specified date range.");
+        if output.is_some() {
+            println!("No time entries found for the specified date range.");
+        } else {
+            eprintln!("No time entries found for the specified date range.");
+        }
         return Ok(());
     }
 
-    let file = File::create(&output)
-        .with_context(|| format!("Failed to create output file: {}", output))?;
-    let mut wtr = csv::Writer::from_writer(file);
+    if let Some((key, dir)) = split {
+        let by_month = match key.trim().to_lowercase().as_str() {
+            "project" => false,
+            "month" => true,
+            _ => anyhow::bail!("invalid --split-by '{key}', expected 'project' or 'month'"),
+        };
+        if format != ExportFormat::Csv {
+            anyhow::bail!("--split-by only supports CSV output");
+        }
+        let projects = db.get_projects(config.current_user_id).unwrap_or_default();
+        let options = CsvFileOptions {
+            include_color,
+            excel,
+            no_header,
+            raw_column_round,
+            format: config.duration_format,
+        };
+        let dir = std::path::Path::new(&dir);
+        return if by_month {
+            write_split_by_month(dir, entries, &projects, options)
+        } else {
+            write_split_by_project(dir, entries, &projects, options)
+        };
+    }
+
+    if format == ExportFormat::Parquet {
+        let path = output.context("--format parquet needs an output file; use --output")?;
+        return write_parquet_export(&path, &entries);
+    }
 
-    let max_metadata_cols = 6;
+    if include_metadata && metadata_style == MetadataStyle::Sidecar && output.is_none() {
+        anyhow::bail!(
+            "Sidecar metadata needs an output file; use --output or --metadata-style comment/inline"
+        );
+    }
 
-    if include_metadata {
-        let mut row = vec![String::new(); max_metadata_cols];
-        row[0] = "# Toggl TimeGuru Export".to_string();
-        wtr.write_record(&row)?;
+    // Written through a temp file so a failed export leaves an existing file untouched.
+    let staged = output
+        .as_ref()
+        .map(|path| StagedFile::new(std::path::Path::new(path)));
+    let mut sink: Box<dyn std::io::Write> = match &staged {
+        Some(staged) => Box::new(staged.create()?),
+        None => Box::new(std::io::stdout()),
+    };
+    if excel {
+        sink.write_all(UTF8_BOM)?;
+    }
+
+    let base_cols = if rollup.is_some() {
+        4
+    } else if group && !group_by_day {
+        5
+    } else {
+        6
+    };
+    let max_metadata_cols =
+        base_cols + include_color as usize + raw_column_round.is_some() as usize;
 
-        row.fill(String::new());
-        row[0] = format!(
-            "# Date Range: {} to {}",
+    let mut metadata_lines = vec![
+        "Toggl TimeGuru Export".to_string(),
+        format!(
+            "Date Range: {} to {}",
             start_date.format("%Y-%m-%d"),
             end_date.format("%Y-%m-%d")
-        );
-        wtr.write_record(&row)?;
-
-        row.fill(String::new());
-        row[0] = format!("# Total Entries: {}", entries.len());
-        wtr.write_record(&row)?;
+        ),
+        format!("Total Entries: {}", entries.len()),
+    ];
+    if let Some(user_email) = &config.current_user_email {
+        metadata_lines.push(format!("User: {}", user_email));
+    }
 
-        if let Some(user_email) = &config.current_user_email {
-            row.fill(String::new());
-            row[0] = format!("# User: {}", user_email);
-            wtr.write_record(&row)?;
+    if include_metadata && metadata_style == MetadataStyle::Comment {
+        for line in &metadata_lines {
+            writeln!(sink, "# {}", line)?;
         }
+    }
 
-        row.fill(String::new());
-        wtr.write_record(&row)?;
+    let mut wtr = export_csv_builder(excel).from_writer(sink);
+
+    if include_metadata {
+        match metadata_style {
+            MetadataStyle::Inline => {
+                let mut row = vec![String::new(); max_metadata_cols];
+                for line in &metadata_lines {
+                    row[0] = format!("# {}", line);
+                    wtr.write_record(&row)?;
+                }
+                row.fill(String::new());
+                wtr.write_record(&row)?;
+            }
+            MetadataStyle::Sidecar => {
+                if let Some(path) = &output {
+                    let meta_path = std::path::Path::new(path).with_extension("meta.json");
+                    let meta = serde_json::json!({
+                        "generator": "toggl-timeguru",
+                        "exported_at": Utc::now().to_rfc3339(),
+                        "start_date": start_date.format("%Y-%m-%d").to_string(),
+                        "end_date": end_date.format("%Y-%m-%d").to_string(),
+                        "total_entries": entries.len(),
+                        "user": config.current_user_email,
+                        "csv": path,
+                    });
+                    staged_file::write(&meta_path, serde_json::to_string_pretty(&meta)?)
+                        .with_context(|| {
+                            format!("Failed to write metadata file: {}", meta_path.display())
+                        })?;
+                }
+            }
+            MetadataStyle::Comment => {}
+        }
     }
 
-    let projects = db.get_projects().unwrap_or_default();
+    let projects = db.get_projects(config.current_user_id).unwrap_or_default();
+    let color_map: std::collections::HashMap<i64, String> =
+        projects.iter().map(|p| (p.id, p.color.clone())).collect();
     let project_map: std::collections::HashMap<i64, String> =
-        projects.into_iter().map(|p| (p.id, p.name)).collect();
+        projects.iter().map(|p| (p.id, p.name.clone())).collect();
+    let project_color = |project_id: Option<i64>| {
+        project_id
+            .and_then(|pid| color_map.get(&pid).cloned())
+            .unwrap_or_default()
+    };
 
-    if group || group_by_day {
-        let grouped = if group_by_day {
-            group_by_description_and_day(entries)
-        } else {
-            group_by_description(entries)
-        };
+    let mut merged_groups = 0;
+    let mut midnight_entries = 0;
+    let rows_written;
+    if group || group_by_day || rollup.is_some() {
+        let mut grouped = tracing::info_span!("group").in_scope(|| {
+            if rollup == Some(GroupBy::Tag) {
+                group_by_tag(entries)
+            } else if rollup.is_some() {
+                group_by_client(entries, &projects)
+            } else if group_by_day {
+                midnight_entries = entries.iter().filter(|e| spans_midnight(e)).count();
+                if split_midnight {
+                    group_by_description_and_day(split_at_midnight(&entries), fold_case)
+                } else {
+                    group_by_description_and_day(entries, fold_case)
+                }
+            } else {
+                group_by_description(entries, fold_case)
+            }
+        });
+        if let Some(order) = sort {
+            grouped = sort_groups(grouped, order);
+        }
+        if let Some(minutes) = merge_below {
+            (grouped, merged_groups) = merge_small_groups(grouped, minutes * 60);
+        }
 
-        if group_by_day {
-            wtr.write_record([
+        let mut header = if let Some(mode) = rollup {
+            let label = if mode == GroupBy::Tag {
+                "Tag"
+            } else {
+                "Client"
+            };
+            vec![label, "Duration (hours)", "Entry Count", "Billable"]
+        } else if group_by_day {
+            vec![
                 "Date",
                 "Description",
                 "Project",
                 "Duration (hours)",
                 "Entry Count",
                 "Billable",
-            ])?;
+            ]
         } else {
-            wtr.write_record([
+            vec![
                 "Description",
                 "Project",
                 "Duration (hours)",
                 "Entry Count",
                 "Billable",
-            ])?;
+            ]
+        };
+        if raw_column_round.is_some() {
+            let at = header
+                .iter()
+                .position(|h| *h == "Duration (hours)")
+                .unwrap_or(0);
+            header.insert(at + 1, "Raw (hours)");
+        }
+        if include_color {
+            header.push("Color");
+        }
+        if !no_header {
+            wtr.write_record(&header)?;
         }
 
+        rows_written = grouped.len();
+        let progress = export_progress_bar(grouped.len(), output.is_some());
         for entry in grouped {
             let desc = entry
                 .description
@@ -856,10 +2473,10 @@ async fn handle_export(
                 .project_id
                 .and_then(|pid| project_map.get(&pid).cloned())
                 .unwrap_or_else(String::new);
-            let hours = if let Some(round_min) = config.round_duration_minutes {
-                entry.rounded_hours(round_min)
+            let seconds = if let Some(round_min) = round_minutes {
+                entry.rounded_duration(round_min)
             } else {
-                entry.total_hours()
+                entry.total_duration
             };
             let billable = if entry.entries.iter().all(|e| e.billable) {
                 "Yes"
@@ -869,83 +2486,159 @@ async fn handle_export(
                 "Mixed"
             };
 
+            let mut record = Vec::with_capacity(max_metadata_cols);
             if group_by_day {
-                let date_str = entry
-                    .date
-                    .map(|d| d.format("%Y-%m-%d").to_string())
-                    .unwrap_or_else(String::new);
-                wtr.write_record([
-                    &date_str,
-                    &desc,
-                    &project_name,
-                    &format!("{:.2}", hours),
-                    &entry.entries.len().to_string(),
-                    billable,
-                ])?;
-            } else {
-                wtr.write_record([
-                    &desc,
-                    &project_name,
-                    &format!("{:.2}", hours),
-                    &entry.entries.len().to_string(),
-                    billable,
-                ])?;
+                record.push(
+                    entry
+                        .date
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_else(String::new),
+                );
+            }
+            record.push(desc);
+            if rollup.is_none() {
+                record.push(project_name);
             }
+            record.push(format_hours_cell(seconds, config.duration_format));
+            if raw_column_round.is_some() {
+                record.push(format_hours_cell(
+                    entry.total_duration,
+                    config.duration_format,
+                ));
+            }
+            record.extend([entry.entries.len().to_string(), billable.to_string()]);
+            if include_color {
+                record.push(project_color(entry.project_id));
+            }
+            wtr.write_record(&record)?;
+            progress.inc(1);
         }
+        progress.finish_and_clear();
     } else {
-        wtr.write_record([
-            "Date",
-            "Time",
-            "Description",
-            "Project",
-            "Duration (hours)",
-            "Billable",
-        ])?;
-
-        for entry in entries {
-            let desc = entry
-                .description
-                .unwrap_or_else(|| "(No description)".to_string());
-            let project_name = entry
-                .project_id
-                .and_then(|pid| project_map.get(&pid).cloned())
-                .unwrap_or_else(String::new);
-            let hours = entry.duration as f64 / 3600.0;
-            let billable = if entry.billable { "Yes" } else { "No" };
-
-            wtr.write_record([
-                &entry.start.format("%Y-%m-%d").to_string(),
-                &entry.start.format("%H:%M").to_string(),
-                &desc,
-                &project_name,
-                &format!("{:.2}", hours),
-                billable,
-            ])?;
-        }
+        rows_written = entries.len();
+        let progress = export_progress_bar(entries.len(), output.is_some());
+        let options = CsvFileOptions {
+            include_color,
+            excel,
+            no_header,
+            raw_column_round,
+            format: config.duration_format,
+        };
+        write_entry_rows(&mut wtr, entries, &projects, options, &progress)?;
+        progress.finish_and_clear();
     }
 
     wtr.flush()?;
-    println!("Successfully exported to: {}", output);
-    Ok(())
-}
-
-async fn handle_track(action: TrackAction, cli_api_token: Option<String>) -> Result<()> {
-    let config = Config::load()?;
-    let api_token = get_api_token(cli_api_token, &config)?;
-    let client = TogglClient::new(api_token)?;
-
-    let workspaces = client.get_workspaces().await?;
-    let workspace_id = workspaces
-        .first()
-        .ok_or_else(|| anyhow::anyhow!("No workspace found for your account"))?
-        .id;
-
-    match action {
-        TrackAction::Start { message } => {
-            println!("Starting time tracking...");
-
-            let time_entry = client
-                .start_time_entry(workspace_id, message.clone())
+    drop(wtr);
+    if let Some(staged) = staged {
+        staged.commit()?;
+    }
+    match output {
+        Some(path) => {
+            let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            println!(
+                "Successfully exported {} rows ({}) to: {}",
+                rows_written,
+                indicatif::HumanBytes(size),
+                path
+            );
+        }
+        None => eprintln!("Successfully exported {} rows to stdout", rows_written),
+    }
+    if merged_groups > 0 {
+        eprintln!(
+            "Merged {} groups under {} min into {}",
+            merged_groups,
+            merge_below.unwrap_or_default(),
+            OTHER_GROUP_LABEL
+        );
+    }
+    if rollup == Some(GroupBy::Tag) {
+        eprintln!("Note: entries with several tags are counted under each tag");
+    }
+    if midnight_entries > 0 {
+        if split_midnight {
+            eprintln!("Split {} entries at midnight", midnight_entries);
+        } else {
+            eprintln!(
+                "Note: {} entries cross midnight and count toward their start day (use --split-midnight to divide them)",
+                midnight_entries
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn handle_track(
+    action: TrackAction,
+    cli_api_token: Option<String>,
+    workspace_override: Option<i64>,
+) -> Result<()> {
+    let config = Config::load()?;
+    let api_token = get_api_token(cli_api_token, &config)?;
+    let client = new_client(api_token, &config)?;
+    let db = open_database(&config)?;
+
+    // An explicit --workspace is checked for every action, not only where it is used.
+    let workspace_override = match workspace_override {
+        Some(id) => Some(track_workspace_id(&client, &db, Some(id), config.current_user_id).await?),
+        None => None,
+    };
+
+    match action {
+        TrackAction::Start {
+            message,
+            favorite,
+            template,
+            vars,
+            created_with,
+        } => {
+            let workspace_id = match workspace_override {
+                Some(id) => id,
+                None => {
+                    track_workspace_id(
+                        &client,
+                        &db,
+                        config.default_workspace_id,
+                        config.current_user_id,
+                    )
+                    .await?
+                }
+            };
+
+            let (message, project_id) = match (favorite, template) {
+                (Some(n), _) => {
+                    let favorite = n
+                        .checked_sub(1)
+                        .and_then(|i| config.favorites.get(i))
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Favorite {} not found ({} configured)",
+                                n,
+                                config.favorites.len()
+                            )
+                        })?;
+                    (Some(favorite.description.clone()), favorite.project_id)
+                }
+                (None, Some(name)) => {
+                    let text = config.description_template(&name)?;
+                    (Some(description_from_template(text, &vars)?), None)
+                }
+                (None, None) => (
+                    message.or_else(|| config.default_description_at(chrono::Local::now())),
+                    None,
+                ),
+            };
+
+            println!("Starting time tracking...");
+
+            let client = match created_with.or_else(|| config.created_with.clone()) {
+                Some(name) => client.with_created_with(name),
+                None => client,
+            };
+
+            let time_entry = client
+                .start_time_entry(workspace_id, message.clone(), project_id)
                 .await?;
 
             println!("✓ Time tracking started successfully!");
@@ -964,11 +2657,7 @@ async fn handle_track(action: TrackAction, cli_api_token: Option<String>) -> Res
         TrackAction::Stop => {
             println!("Stopping time tracking...");
 
-            let current_entry = client.get_current_time_entry().await?;
-
-            if let Some(entry) = current_entry {
-                let stopped_entry = client.stop_time_entry(workspace_id, entry.id).await?;
-
+            if let Some(stopped_entry) = client.stop_current_time_entry().await? {
                 println!("✓ Time tracking stopped successfully!");
                 if let Some(desc) = stopped_entry.description {
                     println!("  Description: {}", desc);
@@ -982,8 +2671,10 @@ async fn handle_track(action: TrackAction, cli_api_token: Option<String>) -> Res
                 if let Some(stop) = stopped_entry.stop {
                     println!("  Stopped at: {}", stop.format("%Y-%m-%d %H:%M:%S"));
                 }
-                let duration_hours = stopped_entry.duration as f64 / 3600.0;
-                println!("  Duration: {:.2}h", duration_hours);
+                println!(
+                    "  Duration: {}",
+                    format_hours(stopped_entry.duration, config.duration_format)
+                );
             } else {
                 println!("No time entry is currently running.");
             }
@@ -993,24 +2684,1405 @@ async fn handle_track(action: TrackAction, cli_api_token: Option<String>) -> Res
     Ok(())
 }
 
+async fn handle_doctor(
+    start: Option<String>,
+    end: Option<String>,
+    merge: bool,
+    cli_api_token: Option<String>,
+) -> Result<()> {
+    use std::io::{self, Write};
+
+    let config = Config::load()?;
+    let db = open_database(&config)?;
+
+    let end_date = if let Some(end_str) = end {
+        Cli::parse_date(&end_str)?
+    } else {
+        Utc::now()
+    };
+
+    let start_date = if let Some(start_str) = start {
+        Cli::parse_date(&start_str)?
+    } else {
+        end_date - config.default_date_range()
+    };
+
+    let entries = db.get_time_entries(start_date, end_date, config.current_user_id)?;
+
+    let invalid = find_invalid_durations(&entries);
+    if !invalid.is_empty() {
+        println!(
+            "\nFound {} stopped entr{} with a zero or negative duration:",
+            invalid.len(),
+            if invalid.len() == 1 { "y" } else { "ies" }
+        );
+        for entry in &invalid {
+            println!(
+                "   #{:<12} {}  stored {}s, should be {}  {}",
+                entry.id,
+                entry.start.format("%Y-%m-%d %H:%M"),
+                entry.duration,
+                format_hours(
+                    corrected_duration(entry).unwrap_or_default(),
+                    config.duration_format
+                ),
+                truncate(
+                    entry.description.as_deref().unwrap_or("(No description)"),
+                    40
+                )
+            );
+        }
+        println!("Fix one with: toggl-timeguru edit <ID> --fix-duration");
+    }
+
+    let duplicates = find_duplicates(&entries);
+
+    if duplicates.is_empty() {
+        println!(
+            "No duplicate time entries found between {} and {}.",
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\nFound {} group(s) of possible duplicates:",
+        duplicates.len()
+    );
+
+    for (i, cluster) in duplicates.iter().enumerate() {
+        let desc = cluster[0]
+            .description
+            .clone()
+            .unwrap_or_else(|| "(No description)".to_string());
+        println!("\n{}. {}", i + 1, truncate(&desc, 60));
+        for entry in cluster {
+            println!(
+                "   #{:<12} {}  {:>8}",
+                entry.id,
+                entry.start.format("%Y-%m-%d %H:%M"),
+                format_hours(entry.duration, config.duration_format)
+            );
+        }
+    }
+
+    if !merge {
+        println!("\nRun with --merge to interactively remove duplicates.");
+        return Ok(());
+    }
+
+    let api_token = get_api_token(cli_api_token, &config)?;
+    let client = new_client(api_token, &config)?;
+    let mut deleted_total = 0;
+
+    for (i, cluster) in duplicates.iter().enumerate() {
+        let keep = cluster
+            .iter()
+            .max_by_key(|e| e.duration)
+            .expect("duplicate clusters are never empty");
+
+        print!(
+            "\nGroup {}: keep #{} ({}) and delete {} other(s)? (y/N): ",
+            i + 1,
+            keep.id,
+            format_hours(keep.duration, config.duration_format),
+            cluster.len() - 1
+        );
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        if input.trim().to_lowercase() != "y" {
+            println!("Skipped.");
+            continue;
+        }
+
+        let mut deleted_ids = Vec::new();
+        for entry in cluster.iter().filter(|e| e.id != keep.id) {
+            match client.delete_time_entry(entry.workspace_id, entry.id).await {
+                Ok(()) => deleted_ids.push(entry.id),
+                Err(e) => println!("  ✗ Failed to delete #{}: {}", entry.id, e),
+            }
+        }
+
+        deleted_total += db.delete_entries_by_ids(&deleted_ids)?;
+        println!("  ✓ Deleted {} duplicate entries", deleted_ids.len());
+    }
+
+    println!("\nMerge complete: {} entries deleted.", deleted_total);
+    Ok(())
+}
+
+fn handle_info() -> Result<()> {
+    let config = Config::load()?;
+    let db_path = Database::default_path();
+    let db = open_database(&config)?;
+    let user_id = config.current_user_id;
+
+    println!("\nAccount:");
+    println!(
+        "  User: {}",
+        config.current_user_email.as_deref().unwrap_or("(not set)")
+    );
+    let workspaces = db.get_workspaces(user_id)?;
+    match config.default_workspace_id {
+        Some(id) => match workspaces.iter().find(|w| w.id == id) {
+            Some(workspace) => println!("  Default workspace: {} ({})", workspace.name, id),
+            None => println!("  Default workspace: {}", id),
+        },
+        None => println!("  Default workspace: (first available)"),
+    }
+
+    println!("\nLocal database:");
+    println!("  Path: {}", db_path.display());
+    let size = std::fs::metadata(&db_path)
+        .with_context(|| format!("Failed to read database file {:?}", db_path))?
+        .len();
+    println!("  Size: {}", format_file_size(size));
+    println!("  Time entries: {}", db.get_entry_count(user_id)?);
+    println!("  Projects: {}", db.get_projects(user_id)?.len());
+    println!("  Workspaces: {}", workspaces.len());
+    match db.get_date_bounds(user_id)? {
+        Some((first, last)) => println!(
+            "  Entry range: {} to {}",
+            first.with_timezone(&chrono::Local).format("%Y-%m-%d"),
+            last.with_timezone(&chrono::Local).format("%Y-%m-%d")
+        ),
+        None => println!("  Entry range: (no entries)"),
+    }
+    match db.get_last_sync("time_entries")? {
+        Some(last_sync) => println!(
+            "  Last sync: {}",
+            last_sync
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        ),
+        None => println!("  Last sync: never"),
+    }
+
+    Ok(())
+}
+
+fn format_file_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// One element of the `projects --json` array.
+#[derive(serde::Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct ProjectRow<'a> {
+    id: i64,
+    workspace_id: i64,
+    name: &'a str,
+    /// Toggl client ID; client names are not cached.
+    client_id: Option<i64>,
+    /// Hex color such as `#06aaf5`.
+    color: &'a str,
+    /// `false` for archived projects (only listed with `--all`).
+    active: bool,
+}
+
+impl<'a> From<&'a toggl::models::Project> for ProjectRow<'a> {
+    fn from(p: &'a toggl::models::Project) -> Self {
+        Self {
+            id: p.id,
+            workspace_id: p.workspace_id,
+            name: &p.name,
+            client_id: p.client_id,
+            color: &p.color,
+            active: p.active,
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+fn handle_schema(target: SchemaTarget) -> Result<()> {
+    let schema = match target {
+        SchemaTarget::Projects => schemars::schema_for!(Vec<ProjectRow>),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+#[cfg(not(feature = "schema"))]
+fn handle_schema(_target: SchemaTarget) -> Result<()> {
+    anyhow::bail!("JSON schemas are not available in this build; rebuild with --features schema")
+}
+
+fn handle_projects(all: bool, json: bool) -> Result<()> {
+    let config = Config::load()?;
+    let db = open_database(&config)?;
+
+    let projects = if all {
+        db.get_all_projects(config.current_user_id)?
+    } else {
+        db.get_projects(config.current_user_id)?
+    };
+
+    if json {
+        let rows: Vec<_> = projects.iter().map(ProjectRow::from).collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+
+    if projects.is_empty() {
+        println!("No cached projects. Run 'toggl-timeguru sync' first.");
+        return Ok(());
+    }
+
+    let name_width = column_width(projects.iter().map(|p| p.name.as_str()), "Name", 40);
+    println!(
+        "{:<12} {:<name_width$} {:<12} {:<8} {:<8}",
+        "ID", "Name", "Client", "Color", "Status"
+    );
+    println!("{}", "-".repeat(12 + name_width + 12 + 8 + 8 + 4));
+
+    for project in &projects {
+        let client = project
+            .client_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        println!(
+            "{:<12} {:<name_width$} {:<12} {:<8} {:<8}",
+            project.id,
+            truncate(&project.name, name_width),
+            client,
+            project.color,
+            if project.active { "active" } else { "archived" }
+        );
+    }
+    println!("\nTotal: {} project(s)", projects.len());
+
+    Ok(())
+}
+
+fn handle_stale_projects(days: i64) -> Result<()> {
+    if days <= 0 {
+        anyhow::bail!("--days must be a positive integer, got {days}");
+    }
+
+    let config = Config::load()?;
+    let db = open_database(&config)?;
+
+    let projects: std::collections::HashMap<i64, toggl::models::Project> = db
+        .get_projects(config.current_user_id)?
+        .into_iter()
+        .map(|p| (p.id, p))
+        .collect();
+    let cutoff = Utc::now() - Duration::days(days);
+
+    let mut stale: Vec<_> = db
+        .get_project_activity(config.current_user_id)?
+        .into_iter()
+        .filter(|a| a.last_used.is_none_or(|last| last < cutoff))
+        .filter_map(|a| projects.get(&a.project_id).map(|p| (p, a)))
+        .collect();
+    stale.sort_by(|(pa, a), (pb, b)| {
+        a.last_used
+            .cmp(&b.last_used)
+            .then_with(|| pa.name.to_lowercase().cmp(&pb.name.to_lowercase()))
+    });
+
+    if stale.is_empty() {
+        println!(
+            "All active projects have entries in the last {} days.",
+            days
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\nProjects with no entries in the last {} days ({}):",
+        days,
+        stale.len()
+    );
+    println!("{:<50} {:<12} {:>10}", "Project", "Last used", "Total");
+    println!("{}", "-".repeat(74));
+
+    for (project, activity) in stale {
+        let last_used = activity
+            .last_used
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        println!(
+            "{:<50} {:<12} {:>10}",
+            truncate(&project.name, 50),
+            last_used,
+            format_hours(activity.total_seconds, config.duration_format)
+        );
+    }
+
+    Ok(())
+}
+
+async fn handle_assign(
+    project: String,
+    description: String,
+    start: Option<String>,
+    end: Option<String>,
+    dry_run: bool,
+    concurrency: Option<usize>,
+    cli_api_token: Option<String>,
+) -> Result<()> {
+    use std::io::{self, Write};
+
+    let config = Config::load()?;
+    let concurrency = resolve_concurrency(concurrency, &config)?;
+    let db = open_database(&config)?;
+
+    let projects = db.get_projects(config.current_user_id)?;
+    let target = resolve_project(&projects, &project)?;
+
+    let end_date = if let Some(end_str) = end {
+        Cli::parse_date(&end_str)?
+    } else {
+        Utc::now()
+    };
+
+    let start_date = if let Some(start_str) = start {
+        Cli::parse_date(&start_str)?
+    } else {
+        end_date - config.default_date_range()
+    };
+
+    let needle = description.to_lowercase();
+    let matches: Vec<_> = db
+        .get_time_entries(start_date, end_date, config.current_user_id)?
+        .into_iter()
+        .filter(|e| e.project_id != Some(target.id))
+        .filter(|e| {
+            e.description
+                .as_deref()
+                .is_some_and(|d| d.to_lowercase().contains(&needle))
+        })
+        .collect();
+
+    if matches.is_empty() {
+        println!(
+            "No entries matching '{}' need reassignment between {} and {}.",
+            description,
+            start_date.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        );
+        return Ok(());
+    }
+
+    println!(
+        "\n{} entries match '{}' (target project: {} [{}]):",
+        matches.len(),
+        description,
+        target.name,
+        target.id
+    );
+    for entry in &matches {
+        println!(
+            "   #{:<12} {}  {}",
+            entry.id,
+            entry.start.format("%Y-%m-%d %H:%M"),
+            truncate(entry.description.as_deref().unwrap_or_default(), 60)
+        );
+    }
+
+    if dry_run {
+        println!("\nDry run: no changes made.");
+        return Ok(());
+    }
+
+    print!(
+        "\nAssign {} entries to '{}'? (y/N): ",
+        matches.len(),
+        target.name
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    if input.trim().to_lowercase() != "y" {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    let api_token = get_api_token(cli_api_token, &config)?;
+    let client = new_client(api_token, &config)?;
+
+    let mut by_workspace: std::collections::BTreeMap<i64, Vec<i64>> =
+        std::collections::BTreeMap::new();
+    for entry in &matches {
+        by_workspace
+            .entry(entry.workspace_id)
+            .or_default()
+            .push(entry.id);
+    }
+
+    let mut success_count = 0;
+    let mut fail_count = 0;
+
+    let batches: Vec<(i64, &[i64])> = by_workspace
+        .iter()
+        .flat_map(|(workspace_id, entry_ids)| {
+            entry_ids
+                .chunks(100)
+                .map(move |chunk| (*workspace_id, chunk))
+        })
+        .collect();
+    let client = &client;
+    let results = concurrency::run_bounded(
+        batches.iter().map(|(workspace_id, chunk)| async move {
+            client
+                .bulk_assign_project(*workspace_id, chunk, Some(target.id))
+                .await
+        }),
+        concurrency,
+    )
+    .await;
+
+    for ((_, chunk), result) in batches.iter().zip(results) {
+        match result {
+            Ok(result) => {
+                for entry_id in &result.success {
+                    db.update_time_entry_project(*entry_id, Some(target.id))?;
+                }
+                for failure in &result.failure {
+                    println!("  ✗ Failed to update #{}: {}", failure.id, failure.message);
+                }
+                success_count += result.success.len();
+                fail_count += result.failure.len();
+            }
+            Err(e) => {
+                println!("  ✗ Failed to update {} entries: {}", chunk.len(), e);
+                fail_count += chunk.len();
+            }
+        }
+    }
+
+    println!(
+        "\nAssigned {} entries to '{}' ({} failed).",
+        success_count, target.name, fail_count
+    );
+    Ok(())
+}
+
+async fn handle_edit(
+    id: i64,
+    description: Option<String>,
+    project: Option<i64>,
+    project_name: Option<String>,
+    billable: Option<bool>,
+    fix_duration: bool,
+    cli_api_token: Option<String>,
+) -> Result<()> {
+    if description.is_none()
+        && project.is_none()
+        && project_name.is_none()
+        && billable.is_none()
+        && !fix_duration
+    {
+        anyhow::bail!(
+            "Nothing to edit. Pass --description, --project, --project-name, --billable or --fix-duration."
+        );
+    }
+
+    let config = Config::load()?;
+    let db = open_database(&config)?;
+
+    let entry = db.get_time_entry(id)?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "Time entry {} not found in the local cache. Run 'toggl-timeguru sync' first.",
+            id
+        )
+    })?;
+
+    let project_id = match project_name {
+        Some(name) => {
+            let projects = db.get_projects(config.current_user_id)?;
+            Some(resolve_project(&projects, &name)?.id)
+        }
+        None => project,
+    };
+
+    let fixed_duration = if fix_duration {
+        let seconds = corrected_duration(&entry).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Time entry {} does not need a duration fix (it is running, its duration is already positive, or its stop is not after its start).",
+                id
+            )
+        })?;
+        entry.stop.map(|stop| (stop, seconds))
+    } else {
+        None
+    };
+
+    let api_token = get_api_token(cli_api_token, &config)?;
+    let client = new_client(api_token, &config)?;
+
+    let update = TimeEntryUpdate {
+        description: description.clone(),
+        project_id,
+        billable,
+        start: fixed_duration.map(|_| entry.start),
+        stop: fixed_duration.map(|(stop, _)| stop),
+        duration: fixed_duration.map(|(_, seconds)| seconds),
+    };
+    client
+        .update_time_entry(entry.workspace_id, id, &update)
+        .await?;
+
+    if let Some(description) = description {
+        db.update_time_entry_description(id, description)?;
+    }
+    if let Some(project_id) = project_id {
+        db.update_time_entry_project(id, Some(project_id))?;
+    }
+    if let Some(billable) = billable {
+        db.update_time_entry_billable(id, billable)?;
+    }
+    if let Some((stop, seconds)) = fixed_duration {
+        db.update_time_entry_time(id, entry.start, stop, seconds)?;
+    }
+
+    let entry = db
+        .get_time_entry(id)?
+        .context("Time entry disappeared from the local cache")?;
+    let project_name = match entry.project_id {
+        Some(pid) => db
+            .get_projects(config.current_user_id)?
+            .into_iter()
+            .find(|p| p.id == pid)
+            .map(|p| p.name)
+            .unwrap_or_else(|| format!("Project {}", pid)),
+        None => "(No project)".to_string(),
+    };
+
+    println!("✓ Time entry {} updated", entry.id);
+    println!(
+        "  Description: {}",
+        entry.description.as_deref().unwrap_or("(No description)")
+    );
+    println!("  Project: {}", project_name);
+    println!("  Billable: {}", if entry.billable { "yes" } else { "no" });
+    println!(
+        "  Started at: {}",
+        entry
+            .start
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+    );
+    println!(
+        "  Duration: {}",
+        format_hours(entry.duration, config.duration_format)
+    );
+
+    Ok(())
+}
+
+/// Fills a description template from `--var KEY=VALUE` flags, prompting on stdin for any
+/// placeholder that was not given.
+fn description_from_template(template: &str, vars: &[String]) -> Result<String> {
+    use std::io::Write;
+
+    let now = chrono::Local::now();
+    let mut values = std::collections::BTreeMap::new();
+    for var in vars {
+        let (key, value) = var
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid --var '{}', expected KEY=VALUE", var))?;
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+
+    let builtin = ["date", "time", "weekday"];
+    for name in config::template_placeholders(template)? {
+        if builtin.contains(&name.as_str()) || values.contains_key(&name) {
+            continue;
+        }
+        print!("{}: ", name);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+        let value = input.trim();
+        if value.is_empty() {
+            anyhow::bail!("Placeholder '{}' needs a value", name);
+        }
+        values.insert(name, value.to_string());
+    }
+
+    config::fill_template(template, &values, now)
+}
+
+/// Finds a project by numeric ID or by case-insensitive name, rejecting ambiguous names.
+fn resolve_project<'a>(
+    projects: &'a [toggl::models::Project],
+    query: &str,
+) -> Result<&'a toggl::models::Project> {
+    if let Ok(id) = query.trim().parse::<i64>()
+        && let Some(project) = projects.iter().find(|p| p.id == id)
+    {
+        return Ok(project);
+    }
+
+    let needle = query.trim().to_lowercase();
+    let matches: Vec<_> = projects
+        .iter()
+        .filter(|p| p.name.to_lowercase() == needle)
+        .collect();
+
+    match matches.as_slice() {
+        [project] => Ok(project),
+        [] => anyhow::bail!(
+            "Project '{}' not found in the local cache. Run 'toggl-timeguru sync' first.",
+            query
+        ),
+        _ => anyhow::bail!(
+            "Project name '{}' is ambiguous: {}",
+            query,
+            matches
+                .iter()
+                .map(|p| format!("{} ({})", p.name, p.id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Picks the requested workspace from the fetched list, or the first one when none is requested.
+fn resolve_workspace(
+    workspaces: &[toggl::models::Workspace],
+    requested: Option<i64>,
+) -> Result<&toggl::models::Workspace> {
+    match requested {
+        Some(id) => workspaces.iter().find(|w| w.id == id).ok_or_else(|| {
+            let available = workspaces
+                .iter()
+                .map(|w| format!("{} ({})", w.id, w.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            anyhow::anyhow!(
+                "Workspace {} not found. Available workspaces: {}",
+                id,
+                available
+            )
+        }),
+        None => workspaces
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("No workspace found for your account")),
+    }
+}
+
+/// Picks the workspace for `track start` from the cached list when it has the requested (or, with
+/// none requested, any) workspace, and only asks the API when the cache can't answer.
+async fn track_workspace_id(
+    client: &TogglClient,
+    db: &Database,
+    requested: Option<i64>,
+    user_id: Option<i64>,
+) -> Result<i64> {
+    let cached = db.get_workspaces(user_id)?;
+    let hit = match requested {
+        Some(id) => cached.iter().find(|w| w.id == id),
+        None => cached.first(),
+    };
+    if let Some(workspace) = hit {
+        tracing::debug!(
+            "Using cached workspace {} ({})",
+            workspace.id,
+            workspace.name
+        );
+        return Ok(workspace.id);
+    }
+
+    let workspaces = client.get_workspaces().await?;
+    db.save_workspaces(&workspaces, user_id)?;
+    Ok(resolve_workspace(&workspaces, requested)?.id)
+}
+
+/// Resolves the rounding period for a single run: `--no-round` wins, then `--round`, then config.
+fn resolve_round_minutes(
+    round: Option<i64>,
+    no_round: bool,
+    config: &Config,
+) -> Result<Option<i64>> {
+    if no_round {
+        return Ok(None);
+    }
+    match round {
+        Some(n) if n > 0 => Ok(Some(n)),
+        Some(n) => anyhow::bail!("--round must be a positive integer, got {n}"),
+        None => Ok(config.round_duration_minutes),
+    }
+}
+
+fn entry_csv_header(include_color: bool, with_raw: bool) -> Vec<&'static str> {
+    let mut header = vec!["Date", "Time", "Description", "Project", "Duration (hours)"];
+    if with_raw {
+        header.push("Raw (hours)");
+    }
+    header.push("Billable");
+    if include_color {
+        header.push("Color");
+    }
+    header
+}
+
+/// Byte order mark that makes Excel read a CSV file as UTF-8.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// CSV settings for exports; `excel` quotes every field and ends rows with CRLF.
+fn export_csv_builder(excel: bool) -> csv::WriterBuilder {
+    let mut builder = csv::WriterBuilder::new();
+    if excel {
+        builder
+            .quote_style(csv::QuoteStyle::Always)
+            .terminator(csv::Terminator::CRLF);
+    }
+    builder
+}
+
+/// `excel` writes the start in local time with seconds, which Excel parses as a date and a
+/// time in any locale. The duration is the tracked one unless `raw_column_round` is set; then
+/// it is rounded to that many minutes and followed by the tracked duration, which is how
+/// `--with-raw` brings rounding to an otherwise unrounded ungrouped export.
+fn entry_csv_record(
+    entry: toggl::models::TimeEntry,
+    project_name: String,
+    color: Option<String>,
+    format: DurationFormat,
+    excel: bool,
+    raw_column_round: Option<i64>,
+) -> Vec<String> {
+    let rounded = raw_column_round.map(|minutes| entry.rounded_duration(minutes));
+    let desc = entry
+        .description
+        .unwrap_or_else(|| "(No description)".to_string());
+    let billable = if entry.billable { "Yes" } else { "No" };
+    let (date, time) = if excel {
+        let local = entry.start.with_timezone(&chrono::Local);
+        (
+            local.format("%Y-%m-%d").to_string(),
+            local.format("%H:%M:%S").to_string(),
+        )
+    } else {
+        (
+            entry.start.format("%Y-%m-%d").to_string(),
+            entry.start.format("%H:%M").to_string(),
+        )
+    };
+
+    let mut record = vec![date, time, desc, project_name];
+    match rounded {
+        Some(seconds) => record.extend([
+            format_hours_cell(seconds, format),
+            format_hours_cell(entry.duration, format),
+        ]),
+        None => record.push(format_hours_cell(entry.duration, format)),
+    }
+    record.push(billable.to_string());
+    record.extend(color);
+    record
+}
+
+/// Turns a project name into a safe file stem: lowercase ASCII letters and digits separated by
+/// single dashes.
+fn sanitize_file_stem(name: &str) -> String {
+    let mut stem = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            stem.push(c.to_ascii_lowercase());
+        } else if !stem.is_empty() && !stem.ends_with('-') {
+            stem.push('-');
+        }
+    }
+    stem.trim_end_matches('-').to_string()
+}
+
+/// How ungrouped entry rows are written, in the main export and in each `--split-by` file.
+#[derive(Clone, Copy)]
+struct CsvFileOptions {
+    include_color: bool,
+    excel: bool,
+    no_header: bool,
+    raw_column_round: Option<i64>,
+    format: DurationFormat,
+}
+
+/// Writes `entries` as a standalone CSV file in the same layout as an ungrouped export.
+fn write_entries_csv_file(
+    path: &std::path::Path,
+    entries: Vec<toggl::models::TimeEntry>,
+    projects: &[toggl::models::Project],
+    options: CsvFileOptions,
+) -> Result<()> {
+    use std::io::Write;
+
+    let staged = StagedFile::new(path);
+    let mut file = staged.create()?;
+    if options.excel {
+        file.write_all(UTF8_BOM)?;
+    }
+    let mut wtr = export_csv_builder(options.excel).from_writer(file);
+    write_entry_rows(
+        &mut wtr,
+        entries,
+        projects,
+        options,
+        &indicatif::ProgressBar::hidden(),
+    )?;
+    wtr.flush()?;
+    drop(wtr);
+    staged.commit()
+}
+
+/// Writes the header, unless `options.no_header` is set, followed by one row per entry.
+fn write_entry_rows<W: io::Write>(
+    wtr: &mut csv::Writer<W>,
+    entries: Vec<toggl::models::TimeEntry>,
+    projects: &[toggl::models::Project],
+    options: CsvFileOptions,
+    progress: &indicatif::ProgressBar,
+) -> Result<()> {
+    if !options.no_header {
+        wtr.write_record(entry_csv_header(
+            options.include_color,
+            options.raw_column_round.is_some(),
+        ))?;
+    }
+    for entry in entries {
+        let project = entry
+            .project_id
+            .and_then(|pid| projects.iter().find(|p| p.id == pid));
+        let project_name = project.map(|p| p.name.clone()).unwrap_or_default();
+        let color = options
+            .include_color
+            .then(|| project.map(|p| p.color.clone()).unwrap_or_default());
+        wtr.write_record(entry_csv_record(
+            entry,
+            project_name,
+            color,
+            options.format,
+            options.excel,
+            options.raw_column_round,
+        ))?;
+        progress.inc(1);
+    }
+    Ok(())
+}
+
+fn print_split_files(dir: &std::path::Path, written: &[(std::path::PathBuf, usize)]) {
+    println!("Wrote {} files to {}:", written.len(), dir.display());
+    for (path, count) in written {
+        println!("  {} ({} entries)", path.display(), count);
+    }
+}
+
+/// Writes one CSV per project (plus `unassigned.csv`) into `dir` and lists the files written.
+fn write_split_by_project(
+    dir: &std::path::Path,
+    entries: Vec<toggl::models::TimeEntry>,
+    projects: &[toggl::models::Project],
+    options: CsvFileOptions,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+
+    let mut project_ids: Vec<Option<i64>> = entries.iter().map(|e| e.project_id).collect();
+    project_ids.sort();
+    project_ids.dedup();
+
+    let mut used_stems = std::collections::HashSet::new();
+    let mut written = Vec::new();
+
+    for project_id in project_ids {
+        let project = project_id.and_then(|pid| projects.iter().find(|p| p.id == pid));
+        let (project_entries, stem) = match project_id {
+            Some(pid) => {
+                let name = project
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| format!("project-{}", pid));
+                let mut stem = sanitize_file_stem(&name);
+                if stem.is_empty() || stem == "unassigned" || used_stems.contains(&stem) {
+                    stem = format!(
+                        "{}-{}",
+                        if stem.is_empty() { "project" } else { &stem },
+                        pid
+                    );
+                }
+                (filter_by_project(entries.clone(), pid), stem)
+            }
+            None => (
+                entries
+                    .iter()
+                    .filter(|e| e.project_id.is_none())
+                    .cloned()
+                    .collect(),
+                "unassigned".to_string(),
+            ),
+        };
+        used_stems.insert(stem.clone());
+
+        let path = dir.join(format!("{}.csv", stem));
+        let count = project_entries.len();
+        write_entries_csv_file(&path, project_entries, projects, options)?;
+        written.push((path, count));
+    }
+
+    print_split_files(dir, &written);
+    Ok(())
+}
+
+/// Writes one CSV per local calendar month (`timeguru_YYYY-MM.csv`) into `dir` and lists the
+/// files written.
+fn write_split_by_month(
+    dir: &std::path::Path,
+    entries: Vec<toggl::models::TimeEntry>,
+    projects: &[toggl::models::Project],
+    options: CsvFileOptions,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create output directory: {}", dir.display()))?;
+
+    let mut written = Vec::new();
+    for (month, month_entries) in report::bucket_by_month(entries) {
+        let path = dir.join(format!("timeguru_{}.csv", month));
+        let count = month_entries.len();
+        write_entries_csv_file(&path, month_entries, projects, options)?;
+        written.push((path, count));
+    }
+
+    print_split_files(dir, &written);
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet_export(path: &str, entries: &[toggl::models::TimeEntry]) -> Result<()> {
+    let staged = StagedFile::new(std::path::Path::new(path));
+    let rows = parquet_export::write_entries(staged.temp_path(), entries)?;
+    staged.commit()?;
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "Successfully exported {} rows ({}) to: {}",
+        rows,
+        indicatif::HumanBytes(size),
+        path
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet_export(_path: &str, _entries: &[toggl::models::TimeEntry]) -> Result<()> {
+    anyhow::bail!("Parquet export is not available in this build; rebuild with --features parquet")
+}
+
+/// Progress bar for export rows; hidden when writing to stdout or when stderr is not a terminal.
+fn export_progress_bar(total: usize, to_file: bool) -> indicatif::ProgressBar {
+    if !to_file || !io::stderr().is_terminal() {
+        return indicatif::ProgressBar::hidden();
+    }
+    let bar = indicatif::ProgressBar::new(total as u64);
+    if let Ok(style) =
+        indicatif::ProgressStyle::with_template("{bar:40} {pos}/{len} rows ({eta} remaining)")
+    {
+        bar.set_style(style);
+    }
+    bar
+}
+
+/// Combines `--group` and `--group-by` into a single grouping mode.
+fn resolve_group_by(group: bool, group_by: Option<String>) -> Result<Option<GroupBy>> {
+    use std::str::FromStr;
+    match group_by {
+        Some(mode) => Ok(Some(GroupBy::from_str(&mode)?)),
+        None if group => Ok(Some(GroupBy::Description)),
+        None => Ok(None),
+    }
+}
+
+fn resolve_merge_below(merge_below: Option<i64>) -> Result<Option<i64>> {
+    match merge_below {
+        Some(n) if n <= 0 => anyhow::bail!("--merge-below must be a positive integer, got {n}"),
+        other => Ok(other),
+    }
+}
+
+/// Parses `--min-duration`/`--max-duration` into seconds, rejecting a minimum above the maximum.
+fn resolve_duration_range(
+    min: Option<String>,
+    max: Option<String>,
+) -> Result<(Option<i64>, Option<i64>)> {
+    let min = min
+        .as_deref()
+        .map(parse_duration)
+        .transpose()?
+        .map(|d| d.num_seconds());
+    let max = max
+        .as_deref()
+        .map(parse_duration)
+        .transpose()?
+        .map(|d| d.num_seconds());
+    if let (Some(lo), Some(hi)) = (min, max)
+        && lo > hi
+    {
+        anyhow::bail!("--min-duration must not be longer than --max-duration");
+    }
+    Ok((min, max))
+}
+
+fn resolve_description_regex(
+    pattern: Option<String>,
+    case_insensitive: bool,
+) -> Result<Option<regex::Regex>> {
+    pattern
+        .map(|pattern| {
+            regex::RegexBuilder::new(&pattern)
+                .case_insensitive(case_insensitive)
+                .build()
+                .with_context(|| format!("Invalid --description-regex pattern '{}'", pattern))
+        })
+        .transpose()
+}
+
+/// Replaces `--start`/`--end` with the bounds of a saved range, covering whole local days.
+fn apply_saved_range(
+    range: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    config: &Config,
+) -> Result<(Option<String>, Option<String>)> {
+    let Some(name) = range else {
+        return Ok((start, end));
+    };
+    let (from, to) = config
+        .saved_range(&name)?
+        .resolve(chrono::Local::now().date_naive())
+        .with_context(|| format!("Saved range '{}' is invalid", name))?;
+    let start = parse_local_date_start(&from.format("%Y-%m-%d").to_string())?;
+    let end = parse_local_date_end(&to.format("%Y-%m-%d").to_string())?;
+    Ok((Some(start.to_rfc3339()), Some(end.to_rfc3339())))
+}
+
+/// Picks the request limit for batch operations: the `--concurrency` flag, then the config.
+fn resolve_concurrency(cli: Option<usize>, config: &Config) -> Result<usize> {
+    match cli.unwrap_or(config.max_concurrency) {
+        0 => anyhow::bail!("Concurrency must be at least 1"),
+        limit => Ok(limit),
+    }
+}
+
+fn resolve_chunk_days(cli: Option<i64>, config: &Config) -> Result<i64> {
+    match cli.unwrap_or(config.sync_chunk_days) {
+        days if days <= 0 => anyhow::bail!("--chunk-days must be a positive integer, got {days}"),
+        days => Ok(days),
+    }
+}
+
+/// Resolves the API token with precedence: `--api-token` flag, then `TOGGL_API_TOKEN`, then config.
 fn get_api_token(cli_token: Option<String>, config: &Config) -> Result<String> {
     if let Some(token) = cli_token {
         return Ok(token);
     }
 
+    if let Ok(token) = std::env::var("TOGGL_API_TOKEN")
+        && !token.trim().is_empty()
+    {
+        return Ok(token.trim().to_string());
+    }
+
     if let Some(encrypted) = &config.api_token_encrypted {
-        return String::from_utf8(encrypted.clone()).context("Failed to decode API token");
+        // Reported as an auth failure so interactive runs prompt for the raw token again.
+        return String::from_utf8(encrypted.clone()).map_err(|_| {
+            anyhow::anyhow!(
+                "Authentication failed: the stored API token could not be decoded. Set it again or clear it with: toggl-timeguru config --reset-token"
+            )
+        });
     }
 
     anyhow::bail!(
-        "No API token provided. Set it with: toggl-timeguru config --set-token YOUR_TOKEN"
+        "No API token provided. Set it with: toggl-timeguru config --set-token YOUR_TOKEN (or export TOGGL_API_TOKEN)"
     )
 }
 
+/// Validates a target from `config --set-*-target`; 0 clears it.
+fn resolve_target_hours(hours: f64, label: &str) -> Result<Option<f64>> {
+    if !hours.is_finite() || hours < 0.0 {
+        anyhow::bail!(
+            "{} target must be a non-negative number of hours, got {}",
+            label,
+            hours
+        );
+    }
+    if hours == 0.0 {
+        println!("{} target cleared", label);
+        Ok(None)
+    } else {
+        println!("{} target set to {:.2}h", label, hours);
+        Ok(Some(hours))
+    }
+}
+
+/// Opens the local database with the config's cache settings applied.
+fn open_database(config: &Config) -> Result<Database> {
+    let db = Database::new(None)?;
+    db.set_normalize_descriptions(config.normalize_descriptions);
+    if let Some(user_id) = config.current_user_id {
+        db.adopt_unowned_projects(user_id)?;
+    }
+    Ok(db)
+}
+
+/// Builds an API client against `TOGGL_API_BASE_URL`, then the configured base URL, then the
+/// default v9 API.
+fn new_client(api_token: String, config: &Config) -> Result<TogglClient> {
+    let client =
+        TogglClient::new(api_token)?.with_verbose_errors(VERBOSE_ERRORS.load(Ordering::Relaxed));
+    let base_url = std::env::var("TOGGL_API_BASE_URL")
+        .ok()
+        .filter(|url| !url.trim().is_empty())
+        .or_else(|| config.api_base_url.clone());
+
+    Ok(match base_url {
+        Some(url) => client.with_base_url(url.trim()),
+        None => client,
+    })
+}
+
+/// Width of a text column: its widest value or header, capped at `cap` (longer values are
+/// truncated when printed).
+fn column_width<'a>(values: impl Iterator<Item = &'a str>, header: &str, cap: usize) -> usize {
+    values
+        .map(|v| v.chars().count())
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or_default()
+        .min(cap)
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}...", &s[..max_len - 3])
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{create_test_entry, create_test_project};
+    use chrono::TimeZone;
+
+    fn csv_options(no_header: bool) -> CsvFileOptions {
+        CsvFileOptions {
+            include_color: false,
+            excel: false,
+            no_header,
+            raw_column_round: None,
+            format: DurationFormat::Decimal,
+        }
+    }
+
+    fn written_records(options: CsvFileOptions) -> Vec<csv::StringRecord> {
+        let start = Utc.with_ymd_and_hms(2025, 3, 3, 9, 0, 0).unwrap();
+        let entry = toggl::models::TimeEntry {
+            project_id: Some(10),
+            ..create_test_entry(1, start)
+        };
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        write_entry_rows(
+            &mut wtr,
+            vec![entry],
+            &[create_test_project(10, "Client work")],
+            options,
+            &indicatif::ProgressBar::hidden(),
+        )
+        .unwrap();
+        let bytes = wtr.into_inner().unwrap();
+        csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(bytes.as_slice())
+            .records()
+            .map(|r| r.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_no_header_starts_with_a_data_row() {
+        let with_header = written_records(csv_options(false));
+        assert_eq!(with_header.len(), 2);
+        assert_eq!(&with_header[0][0], "Date");
+
+        let without_header = written_records(csv_options(true));
+        assert_eq!(without_header.len(), 1);
+        assert_eq!(
+            without_header[0].iter().collect::<Vec<_>>(),
+            vec![
+                "2025-03-03",
+                "09:00",
+                "Entry 1",
+                "Client work",
+                "1.00",
+                "No"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_raw_adds_a_raw_column_next_to_the_rounded_duration() {
+        let start = Utc.with_ymd_and_hms(2025, 3, 3, 9, 0, 0).unwrap();
+        let entry = toggl::models::TimeEntry {
+            duration: 20 * 60,
+            ..create_test_entry(1, start)
+        };
+        let header = entry_csv_header(false, true);
+        let record = entry_csv_record(
+            entry,
+            String::new(),
+            None,
+            DurationFormat::Decimal,
+            false,
+            Some(15),
+        );
+        assert_eq!(record.len(), header.len());
+
+        let column = |name: &str| header.iter().position(|h| *h == name).unwrap();
+        let rounded = &record[column("Duration (hours)")];
+        let raw = &record[column("Raw (hours)")];
+        assert_eq!(rounded, "0.50");
+        assert_eq!(raw, "0.33");
+        assert_ne!(rounded, raw);
+    }
+
+    #[test]
+    fn test_prune_old_logs_keeps_exactly_keep_days_files() {
+        let dir = std::env::temp_dir().join(format!(
+            "toggl-timeguru-test-{}-prune-logs",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let today = chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap();
+        for age in 0..10 {
+            let date = today - Duration::days(age);
+            std::fs::write(
+                dir.join(format!("app.log.{}", date.format("%Y-%m-%d"))),
+                b"",
+            )
+            .unwrap();
+        }
+        std::fs::write(dir.join("notes.txt"), b"").unwrap();
+
+        let pruned = prune_old_logs(&dir, 7, today);
+        let mut kept: Vec<String> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        kept.sort();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(pruned, 3);
+        assert_eq!(kept.len(), 8);
+        assert_eq!(kept.first().map(String::as_str), Some("app.log.2025-03-04"));
+        assert_eq!(kept.last().map(String::as_str), Some("notes.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_report_fetch_skips_server_deleted_entries() {
+        let mut server = mockito::Server::new_async().await;
+        let client = TogglClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/api/v9", server.url()));
+        let _entries = server
+            .mock("GET", "/api/v9/me/time_entries")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                {"id":1,"workspace_id":1,"start":"2025-01-01T09:00:00Z",
+                "stop":"2025-01-01T10:00:00Z","duration":3600,"at":"2025-01-01T10:00:00Z",
+                "user_id":1},
+                {"id":2,"workspace_id":1,"start":"2025-01-02T09:00:00Z",
+                "stop":"2025-01-02T10:00:00Z","duration":3600,"at":"2025-01-02T11:00:00Z",
+                "server_deleted_at":"2025-01-02T11:00:00Z","user_id":1}
+                ]"#,
+            )
+            .create_async()
+            .await;
+        let _workspaces = server
+            .mock("GET", "/api/v9/workspaces")
+            .with_status(200)
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let db = crate::test_support::in_memory_db();
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 3, 0, 0, 0).unwrap();
+        let entries = fetch_report_entries(&client, &db, start, end, Some(1))
+            .await
+            .unwrap();
+
+        assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(db.get_entry_count(Some(1)).unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sync_rejects_unknown_workspace_before_syncing_entries() {
+        let mut server = mockito::Server::new_async().await;
+        let client = TogglClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/api/v9", server.url()));
+        let _me = server
+            .mock("GET", "/api/v9/me")
+            .with_status(200)
+            .with_body(r#"{"id":1,"email":"user@example.com"}"#)
+            .create_async()
+            .await;
+        let workspaces = vec![crate::test_support::create_test_workspace(1, "Main")];
+        let _workspaces = server
+            .mock("GET", "/api/v9/workspaces")
+            .with_status(200)
+            .with_body(serde_json::to_string(&workspaces).unwrap())
+            .create_async()
+            .await;
+        let entries = server
+            .mock("GET", "/api/v9/me/time_entries")
+            .match_query(mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let db = crate::test_support::in_memory_db();
+        let mut config = Config {
+            current_user_id: Some(1),
+            ..Config::default()
+        };
+        let err = sync_account(
+            &client,
+            &db,
+            &mut config,
+            None,
+            None,
+            Some(99),
+            true,
+            true,
+            false,
+            1,
+            30,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(err.to_string().contains("Workspace 99 not found"));
+        entries.assert_async().await;
     }
 }
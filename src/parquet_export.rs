@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use arrow_array::builder::{ListBuilder, StringBuilder};
+use arrow_array::{
+    ArrayRef, BooleanArray, Int64Array, RecordBatch, StringArray, TimestampMicrosecondArray,
+};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::toggl::models::TimeEntry;
+
+fn timestamp_type() -> DataType {
+    DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+}
+
+fn schema() -> Schema {
+    Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("start", timestamp_type(), false),
+        Field::new("stop", timestamp_type(), true),
+        Field::new("duration", DataType::Int64, false),
+        Field::new("description", DataType::Utf8, true),
+        Field::new("project_id", DataType::Int64, true),
+        Field::new("billable", DataType::Boolean, false),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ])
+}
+
+/// Writes entries as a single Parquet row group and returns the number of rows written.
+pub fn write_entries(path: &Path, entries: &[TimeEntry]) -> Result<usize> {
+    let schema = Arc::new(schema());
+
+    let mut tags = ListBuilder::new(StringBuilder::new());
+    for entry in entries {
+        match &entry.tags {
+            Some(entry_tags) => {
+                for tag in entry_tags {
+                    tags.values().append_value(tag);
+                }
+                tags.append(true);
+            }
+            None => tags.append(false),
+        }
+    }
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(entries.iter().map(|e| e.id))),
+        Arc::new(
+            entries
+                .iter()
+                .map(|e| Some(e.start.timestamp_micros()))
+                .collect::<TimestampMicrosecondArray>()
+                .with_timezone("UTC"),
+        ),
+        Arc::new(
+            entries
+                .iter()
+                .map(|e| e.stop.map(|s| s.timestamp_micros()))
+                .collect::<TimestampMicrosecondArray>()
+                .with_timezone("UTC"),
+        ),
+        Arc::new(Int64Array::from_iter_values(
+            entries.iter().map(|e| e.duration),
+        )),
+        Arc::new(StringArray::from_iter(
+            entries.iter().map(|e| e.description.as_deref()),
+        )),
+        Arc::new(Int64Array::from_iter(entries.iter().map(|e| e.project_id))),
+        Arc::new(BooleanArray::from_iter(
+            entries.iter().map(|e| Some(e.billable)),
+        )),
+        Arc::new(tags.finish()),
+    ];
+
+    let batch = RecordBatch::try_new(schema.clone(), columns)
+        .context("Failed to build Parquet record batch")?;
+
+    let file = File::create(path)
+        .with_context(|| format!("Failed to create output file: {}", path.display()))?;
+    let mut writer =
+        ArrowWriter::try_new(file, schema, None).context("Failed to create Parquet writer")?;
+    writer
+        .write(&batch)
+        .context("Failed to write Parquet data")?;
+    writer.close().context("Failed to finish Parquet file")?;
+
+    Ok(entries.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::timed_entry;
+    use chrono::{TimeZone, Utc};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn create_test_entry(id: i64, tags: Option<Vec<String>>) -> TimeEntry {
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap();
+        TimeEntry {
+            project_id: Some(10),
+            billable: true,
+            description: Some("Parquet".to_string()),
+            tags,
+            ..timed_entry(id, start, 3600)
+        }
+    }
+
+    #[test]
+    fn test_write_entries_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "toggl-timeguru-test-{}.parquet",
+            std::process::id()
+        ));
+        let entries = vec![
+            create_test_entry(1, Some(vec!["a".to_string(), "b".to_string()])),
+            create_test_entry(2, None),
+        ];
+
+        assert_eq!(write_entries(&path, &entries).unwrap(), 2);
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let batches: Vec<RecordBatch> = reader.map(|b| b.unwrap()).collect();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().field(1).data_type(), &timestamp_type());
+        let starts = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(starts.value(0), entries[0].start.timestamp_micros());
+        assert!(batch.column(7).is_null(1));
+    }
+}
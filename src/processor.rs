@@ -1,13 +1,31 @@
+use crate::config::DurationFormat;
 use crate::toggl::models::{GroupedTimeEntry, Project, TimeEntry};
 use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
 use std::collections::HashMap;
+use std::str::FromStr;
+
+/// The description part of a grouping key. With `case_insensitive`, descriptions that differ
+/// only in case or surrounding whitespace share a key.
+fn description_group_key(description: &Option<String>, case_insensitive: bool) -> Option<String> {
+    if case_insensitive {
+        description.as_ref().map(|d| d.trim().to_lowercase())
+    } else {
+        description.clone()
+    }
+}
 
-pub fn group_by_description(entries: Vec<TimeEntry>) -> Vec<GroupedTimeEntry> {
+/// Groups by description, project and workspace. A group shows the description of its first
+/// entry, which matters when `case_insensitive` merges differently cased variants.
+pub fn group_by_description(
+    entries: Vec<TimeEntry>,
+    case_insensitive: bool,
+) -> Vec<GroupedTimeEntry> {
     let mut groups: HashMap<(Option<String>, Option<i64>, i64), Vec<TimeEntry>> = HashMap::new();
 
     for entry in entries {
         let key = (
-            entry.description.clone(),
+            description_group_key(&entry.description, case_insensitive),
             entry.project_id,
             entry.workspace_id,
         );
@@ -16,11 +34,11 @@ pub fn group_by_description(entries: Vec<TimeEntry>) -> Vec<GroupedTimeEntry> {
 
     let mut grouped: Vec<GroupedTimeEntry> = groups
         .into_iter()
-        .map(|((description, project_id, _workspace_id), entries)| {
+        .map(|((_, project_id, _workspace_id), entries)| {
             let total_duration: i64 = entries.iter().map(|e| e.duration).sum();
 
             GroupedTimeEntry {
-                description,
+                description: entries[0].description.clone(),
                 project_id,
                 date: None,
                 entries,
@@ -36,7 +54,10 @@ pub fn group_by_description(entries: Vec<TimeEntry>) -> Vec<GroupedTimeEntry> {
 
 type GroupKey = (Option<String>, Option<i64>, i64, DateTime<Utc>);
 
-pub fn group_by_description_and_day(entries: Vec<TimeEntry>) -> Vec<GroupedTimeEntry> {
+pub fn group_by_description_and_day(
+    entries: Vec<TimeEntry>,
+    case_insensitive: bool,
+) -> Vec<GroupedTimeEntry> {
     let mut groups: HashMap<GroupKey, Vec<TimeEntry>> = HashMap::new();
     let mut order: Vec<GroupKey> = Vec::new();
 
@@ -44,7 +65,7 @@ pub fn group_by_description_and_day(entries: Vec<TimeEntry>) -> Vec<GroupedTimeE
         let date = entry.start.date_naive().and_hms_opt(0, 0, 0).unwrap();
         let date_utc = DateTime::<Utc>::from_naive_utc_and_offset(date, Utc);
         let key = (
-            entry.description.clone(),
+            description_group_key(&entry.description, case_insensitive),
             entry.project_id,
             entry.workspace_id,
             date_utc,
@@ -59,14 +80,13 @@ pub fn group_by_description_and_day(entries: Vec<TimeEntry>) -> Vec<GroupedTimeE
     order
         .into_iter()
         .map(|key| {
-            let (description, project_id, workspace_id, date) = key;
-            let entries = groups
-                .remove(&(description.clone(), project_id, workspace_id, date))
-                .unwrap();
+            let date = key.3;
+            let project_id = key.1;
+            let entries = groups.remove(&key).unwrap();
             let total_duration: i64 = entries.iter().map(|e| e.duration).sum();
 
             GroupedTimeEntry {
-                description,
+                description: entries[0].description.clone(),
                 project_id,
                 date: Some(date),
                 entries,
@@ -76,6 +96,175 @@ pub fn group_by_description_and_day(entries: Vec<TimeEntry>) -> Vec<GroupedTimeE
         .collect()
 }
 
+/// Where a finished entry ends on the clock: its stop time, or start plus duration when the stop
+/// time is missing or not after the start.
+fn span_end(entry: &TimeEntry) -> DateTime<Utc> {
+    entry
+        .stop
+        .filter(|stop| *stop > entry.start)
+        .unwrap_or(entry.start + chrono::Duration::seconds(entry.duration))
+}
+
+/// Whether a finished entry runs past the (UTC) midnight after its start.
+pub fn spans_midnight(entry: &TimeEntry) -> bool {
+    !is_running(entry) && span_end(entry).date_naive() > entry.start.date_naive()
+}
+
+/// Splits entries that cross midnight into one piece per day, each with its share of the
+/// duration, so day grouping credits every day it touches. Pieces keep the entry's id and other
+/// fields; running entries and entries within one day are returned unchanged.
+pub fn split_at_midnight(entries: &[TimeEntry]) -> Vec<TimeEntry> {
+    let mut pieces = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if !spans_midnight(entry) {
+            pieces.push(entry.clone());
+            continue;
+        }
+
+        let end = span_end(entry);
+        let span = (end - entry.start).num_seconds();
+        let mut start = entry.start;
+        let mut assigned = 0;
+        while start < end {
+            let next_midnight = DateTime::<Utc>::from_naive_utc_and_offset(
+                (start.date_naive() + chrono::Days::new(1))
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap(),
+                Utc,
+            );
+            let stop = next_midnight.min(end);
+            // The last piece takes the remainder so the pieces add up to the original duration.
+            let duration = if stop == end {
+                entry.duration - assigned
+            } else {
+                entry.duration * (stop - entry.start).num_seconds() / span - assigned
+            };
+            assigned += duration;
+            pieces.push(TimeEntry {
+                start,
+                stop: Some(stop),
+                duration,
+                ..entry.clone()
+            });
+            start = stop;
+        }
+    }
+    pieces
+}
+
+pub const NO_CLIENT_LABEL: &str = "(no client)";
+
+/// Rolls entries up per client of their project. Entries without a project, or whose project
+/// has no client (or is not cached), land in a "(no client)" group.
+pub fn group_by_client(entries: Vec<TimeEntry>, projects: &[Project]) -> Vec<GroupedTimeEntry> {
+    let client_of: HashMap<i64, Option<i64>> =
+        projects.iter().map(|p| (p.id, p.client_id)).collect();
+    let mut groups: HashMap<Option<i64>, Vec<TimeEntry>> = HashMap::new();
+
+    for entry in entries {
+        let client_id = entry
+            .project_id
+            .and_then(|pid| client_of.get(&pid).copied().flatten());
+        groups.entry(client_id).or_default().push(entry);
+    }
+
+    let mut grouped: Vec<GroupedTimeEntry> = groups
+        .into_iter()
+        .map(|(client_id, entries)| GroupedTimeEntry {
+            description: Some(match client_id {
+                Some(id) => format!("Client {}", id),
+                None => NO_CLIENT_LABEL.to_string(),
+            }),
+            project_id: None,
+            date: None,
+            total_duration: entries.iter().map(|e| e.duration).sum(),
+            entries,
+        })
+        .collect();
+
+    grouped.sort_by_key(|g| std::cmp::Reverse(g.total_duration));
+
+    grouped
+}
+
+pub const UNTAGGED_LABEL: &str = "(untagged)";
+
+/// Groups entries per tag. An entry with several tags is counted under each of them, so the
+/// group totals can add up to more than the tracked time; untagged entries form their own group.
+pub fn group_by_tag(entries: Vec<TimeEntry>) -> Vec<GroupedTimeEntry> {
+    let mut groups: HashMap<String, Vec<TimeEntry>> = HashMap::new();
+
+    for entry in entries {
+        let mut tags: Vec<String> = entry.tags.clone().unwrap_or_default();
+        tags.sort();
+        tags.dedup();
+        if tags.is_empty() {
+            tags.push(UNTAGGED_LABEL.to_string());
+        }
+        for tag in tags {
+            groups.entry(tag).or_default().push(entry.clone());
+        }
+    }
+
+    let mut grouped: Vec<GroupedTimeEntry> = groups
+        .into_iter()
+        .map(|(tag, entries)| GroupedTimeEntry {
+            description: Some(tag),
+            project_id: None,
+            date: None,
+            total_duration: entries.iter().map(|e| e.duration).sum(),
+            entries,
+        })
+        .collect();
+
+    grouped.sort_by_key(|g| std::cmp::Reverse(g.total_duration));
+
+    grouped
+}
+
+pub const OTHER_GROUP_LABEL: &str = "(other)";
+
+/// Collapses every group whose total is below `threshold_seconds` into a single
+/// trailing "(other)" bucket. Returns the groups and how many were merged; a
+/// lone small group is left as is since merging it would only hide its name.
+pub fn merge_small_groups(
+    grouped: Vec<GroupedTimeEntry>,
+    threshold_seconds: i64,
+) -> (Vec<GroupedTimeEntry>, usize) {
+    let (small, mut kept): (Vec<_>, Vec<_>) = grouped
+        .into_iter()
+        .partition(|g| g.total_duration < threshold_seconds);
+
+    if small.len() < 2 {
+        kept.extend(small);
+        return (kept, 0);
+    }
+
+    let merged_count = small.len();
+    let total_duration = small.iter().map(|g| g.total_duration).sum();
+    let entries = small.into_iter().flat_map(|g| g.entries).collect();
+
+    kept.push(GroupedTimeEntry {
+        description: Some(OTHER_GROUP_LABEL.to_string()),
+        project_id: None,
+        date: None,
+        entries,
+        total_duration,
+    });
+
+    (kept, merged_count)
+}
+
+/// Keeps the `n` longest groups in the order they came in, so a trailing "(other)"
+/// bucket stays last and a later sort only reorders the chosen ones.
+pub fn top_groups(groups: Vec<GroupedTimeEntry>, n: usize) -> Vec<GroupedTimeEntry> {
+    let mut ranked: Vec<_> = groups.into_iter().enumerate().collect();
+    ranked.sort_by_key(|(_, g)| Reverse(g.total_duration));
+    ranked.truncate(n);
+    ranked.sort_by_key(|(index, _)| *index);
+    ranked.into_iter().map(|(_, g)| g).collect()
+}
+
 pub fn filter_by_project(entries: Vec<TimeEntry>, project_id: i64) -> Vec<TimeEntry> {
     entries
         .into_iter()
@@ -83,6 +272,119 @@ pub fn filter_by_project(entries: Vec<TimeEntry>, project_id: i64) -> Vec<TimeEn
         .collect()
 }
 
+/// Keeps entries assigned to any of `project_ids`; an empty set keeps everything.
+pub fn filter_by_projects(
+    entries: Vec<TimeEntry>,
+    project_ids: &std::collections::HashSet<i64>,
+) -> Vec<TimeEntry> {
+    if project_ids.is_empty() {
+        return entries;
+    }
+    entries
+        .into_iter()
+        .filter(|e| e.project_id.is_some_and(|pid| project_ids.contains(&pid)))
+        .collect()
+}
+
+/// Keeps entries whose description matches `pattern`. Entries without a description are
+/// matched as the empty string, so `^$` finds them.
+pub fn filter_by_description_regex(
+    entries: Vec<TimeEntry>,
+    pattern: &regex::Regex,
+) -> Vec<TimeEntry> {
+    entries
+        .into_iter()
+        .filter(|e| pattern.is_match(e.description.as_deref().unwrap_or("")))
+        .collect()
+}
+
+pub fn filter_by_workspace(entries: Vec<TimeEntry>, workspace_id: i64) -> Vec<TimeEntry> {
+    entries
+        .into_iter()
+        .filter(|e| e.workspace_id == workspace_id)
+        .collect()
+}
+
+/// Trims a description and collapses each run of internal whitespace into a single space.
+pub fn normalize_description(description: &str) -> String {
+    description.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses a relative age such as `45m`, `12h`, `2d` or `3w`.
+pub fn parse_duration(s: &str) -> anyhow::Result<chrono::Duration> {
+    let s = s.trim();
+    crate::config::parse_span(s).ok_or_else(|| {
+        anyhow::anyhow!(
+            "invalid duration '{s}', expected a number followed by m, h, d or w (e.g. 2d)"
+        )
+    })
+}
+
+/// The instant `age` before `now`, or the earliest representable instant when that is out of
+/// range.
+fn age_cutoff(now: DateTime<Utc>, age: chrono::Duration) -> DateTime<Utc> {
+    now.checked_sub_signed(age)
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+/// Keeps entries that started less than `age` before `now`.
+pub fn filter_newer_than(
+    entries: Vec<TimeEntry>,
+    age: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Vec<TimeEntry> {
+    let cutoff = age_cutoff(now, age);
+    entries.into_iter().filter(|e| e.start >= cutoff).collect()
+}
+
+/// Keeps entries that started more than `age` before `now`.
+pub fn filter_older_than(
+    entries: Vec<TimeEntry>,
+    age: chrono::Duration,
+    now: DateTime<Utc>,
+) -> Vec<TimeEntry> {
+    let cutoff = age_cutoff(now, age);
+    entries.into_iter().filter(|e| e.start < cutoff).collect()
+}
+
+/// Keeps entries lasting between `min_secs` and `max_secs` seconds, both inclusive; either bound
+/// may be omitted. Running entries are checked against the minimum using their elapsed time and
+/// never fail the maximum, since they are still growing.
+pub fn filter_by_duration_range(
+    entries: Vec<TimeEntry>,
+    min_secs: Option<i64>,
+    max_secs: Option<i64>,
+) -> Vec<TimeEntry> {
+    let now = Utc::now();
+    entries
+        .into_iter()
+        .filter(|e| {
+            let running = is_running(e);
+            let seconds = elapsed_seconds(e, now);
+            min_secs.is_none_or(|min| seconds >= min)
+                && (running || max_secs.is_none_or(|max| seconds <= max))
+        })
+        .collect()
+}
+
+/// Whether the entry's timer is still running: a negative duration or no stop time.
+pub fn is_running(entry: &TimeEntry) -> bool {
+    entry.duration < 0 || entry.stop.is_none()
+}
+
+pub fn filter_running(entries: Vec<TimeEntry>) -> Vec<TimeEntry> {
+    entries.into_iter().filter(is_running).collect()
+}
+
+/// Seconds tracked as of `now`: the stored duration, or the time since start while running.
+pub fn elapsed_seconds(entry: &TimeEntry, now: DateTime<Utc>) -> i64 {
+    if is_running(entry) {
+        (now - entry.start).num_seconds().max(0)
+    } else {
+        entry.duration
+    }
+}
+
 pub fn filter_by_tag(entries: Vec<TimeEntry>, tag: &str) -> Vec<TimeEntry> {
     entries
         .into_iter()
@@ -96,6 +398,25 @@ pub fn filter_by_tag(entries: Vec<TimeEntry>, tag: &str) -> Vec<TimeEntry> {
         .collect()
 }
 
+/// Keeps entries carrying any of `tag_ids`. Ids survive tag renames, unlike names; an empty set
+/// keeps every entry.
+pub fn filter_by_tag_id(
+    entries: Vec<TimeEntry>,
+    tag_ids: &std::collections::HashSet<i64>,
+) -> Vec<TimeEntry> {
+    if tag_ids.is_empty() {
+        return entries;
+    }
+    entries
+        .into_iter()
+        .filter(|e| {
+            e.tag_ids
+                .as_ref()
+                .is_some_and(|ids| ids.iter().any(|id| tag_ids.contains(id)))
+        })
+        .collect()
+}
+
 #[allow(dead_code)]
 pub fn filter_by_client(
     entries: Vec<TimeEntry>,
@@ -124,7 +445,11 @@ pub fn filter_by_client(
 pub struct TimeEntryFilter {
     pub project_ids: std::collections::HashSet<i64>,
     pub tags: std::collections::HashSet<String>,
+    pub workspace_ids: std::collections::HashSet<i64>,
     pub billable_only: bool,
+    pub running_only: bool,
+    pub min_duration_secs: Option<i64>,
+    pub max_duration_secs: Option<i64>,
 }
 
 impl TimeEntryFilter {
@@ -151,7 +476,16 @@ impl TimeEntryFilter {
     }
 
     pub fn is_active(&self) -> bool {
-        !self.project_ids.is_empty() || !self.tags.is_empty() || self.billable_only
+        !self.project_ids.is_empty()
+            || !self.tags.is_empty()
+            || !self.workspace_ids.is_empty()
+            || self.billable_only
+            || self.running_only
+            || self.has_duration_range()
+    }
+
+    fn has_duration_range(&self) -> bool {
+        self.min_duration_secs.is_some() || self.max_duration_secs.is_some()
     }
 
     pub fn active_count(&self) -> usize {
@@ -162,19 +496,23 @@ impl TimeEntryFilter {
         if !self.tags.is_empty() {
             n += 1;
         }
+        if !self.workspace_ids.is_empty() {
+            n += 1;
+        }
         if self.billable_only {
             n += 1;
         }
+        if self.running_only {
+            n += 1;
+        }
+        if self.has_duration_range() {
+            n += 1;
+        }
         n
     }
 
-    pub fn apply(&self, mut entries: Vec<TimeEntry>, _projects: &[Project]) -> Vec<TimeEntry> {
-        if !self.project_ids.is_empty() {
-            entries.retain(|e| match e.project_id {
-                Some(pid) => self.project_ids.contains(&pid),
-                None => false,
-            });
-        }
+    pub fn apply(&self, entries: Vec<TimeEntry>, _projects: &[Project]) -> Vec<TimeEntry> {
+        let mut entries = filter_by_projects(entries, &self.project_ids);
 
         if !self.tags.is_empty() {
             entries.retain(|e| {
@@ -190,14 +528,130 @@ impl TimeEntryFilter {
             });
         }
 
+        if !self.workspace_ids.is_empty() {
+            entries.retain(|e| self.workspace_ids.contains(&e.workspace_id));
+        }
+
         if self.billable_only {
             entries.retain(|e| e.billable);
         }
 
+        if self.running_only {
+            entries = filter_running(entries);
+        }
+
+        if self.has_duration_range() {
+            entries =
+                filter_by_duration_range(entries, self.min_duration_secs, self.max_duration_secs);
+        }
+
         entries
     }
 }
 
+fn entry_end(entry: &TimeEntry) -> DateTime<Utc> {
+    entry
+        .stop
+        .unwrap_or_else(|| entry.start + chrono::Duration::seconds(entry.duration.max(0)))
+}
+
+/// The duration a stopped entry should have when its stored duration is zero or negative,
+/// computed from `stop - start`. Returns `None` for running entries, entries whose stored
+/// duration is fine, and entries whose stop is not after their start.
+pub fn corrected_duration(entry: &TimeEntry) -> Option<i64> {
+    if entry.duration > 0 {
+        return None;
+    }
+    let seconds = (entry.stop? - entry.start).num_seconds();
+    (seconds > 0).then_some(seconds)
+}
+
+/// Stopped entries whose stored duration is zero or negative and can be recomputed.
+pub fn find_invalid_durations(entries: &[TimeEntry]) -> Vec<&TimeEntry> {
+    entries
+        .iter()
+        .filter(|e| corrected_duration(e).is_some())
+        .collect()
+}
+
+/// Finds clusters of likely duplicate entries: entries sharing description, project and
+/// workspace whose time ranges overlap (or that start at the exact same instant).
+///
+/// Only clusters with two or more entries are returned, each sorted by start time.
+pub fn find_duplicates(entries: &[TimeEntry]) -> Vec<Vec<TimeEntry>> {
+    let mut groups: HashMap<(Option<String>, Option<i64>, i64), Vec<TimeEntry>> = HashMap::new();
+
+    for entry in entries {
+        let key = (
+            entry.description.clone(),
+            entry.project_id,
+            entry.workspace_id,
+        );
+        groups.entry(key).or_default().push(entry.clone());
+    }
+
+    let mut clusters: Vec<Vec<TimeEntry>> = Vec::new();
+
+    for (_, mut group) in groups {
+        if group.len() < 2 {
+            continue;
+        }
+        group.sort_by_key(|e| (e.start, e.id));
+
+        let mut current: Vec<TimeEntry> = Vec::new();
+        let mut current_end = DateTime::<Utc>::MIN_UTC;
+
+        for entry in group {
+            let overlaps = current
+                .last()
+                .is_some_and(|last| entry.start < current_end || entry.start == last.start);
+
+            if !overlaps && !current.is_empty() {
+                if current.len() > 1 {
+                    clusters.push(std::mem::take(&mut current));
+                } else {
+                    current.clear();
+                }
+            }
+
+            current_end = if current.is_empty() {
+                entry_end(&entry)
+            } else {
+                current_end.max(entry_end(&entry))
+            };
+            current.push(entry);
+        }
+
+        if current.len() > 1 {
+            clusters.push(current);
+        }
+    }
+
+    clusters.sort_by_key(|c| c[0].start);
+    clusters
+}
+
+/// Renders a duration for display: `1.50h` (decimal) or `1:30` (hours and minutes).
+pub fn format_hours(seconds: i64, format: DurationFormat) -> String {
+    match format {
+        DurationFormat::Decimal => format!("{:.2}h", seconds as f64 / 3600.0),
+        DurationFormat::HoursMinutes => {
+            let sign = if seconds < 0 { "-" } else { "" };
+            let minutes = seconds.unsigned_abs() / 60;
+            format!("{}{}:{:02}", sign, minutes / 60, minutes % 60)
+        }
+    }
+}
+
+/// A duration for a spreadsheet cell: a bare number for decimal so it can be summed, `H:MM`
+/// otherwise.
+pub fn format_hours_cell(seconds: i64, format: DurationFormat) -> String {
+    match format {
+        DurationFormat::Decimal => format!("{:.2}", seconds as f64 / 3600.0),
+        DurationFormat::HoursMinutes => format_hours(seconds, format),
+    }
+}
+
 #[allow(dead_code)]
 pub fn calculate_total_duration(entries: &[TimeEntry]) -> i64 {
     entries.iter().map(|e| e.duration).sum()
@@ -221,15 +675,69 @@ pub fn calculate_non_billable_duration(entries: &[TimeEntry]) -> i64 {
         .sum()
 }
 
-#[allow(dead_code)]
 pub fn sort_by_date(mut entries: Vec<TimeEntry>) -> Vec<TimeEntry> {
     entries.sort_by_key(|a| a.start);
     entries
 }
 
+/// Order applied to listed or exported entries and groups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    DateAsc,
+    DateDesc,
+    /// Longest first.
+    Duration,
+    /// Case-insensitive A to Z.
+    Description,
+}
+
+impl FromStr for SortOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "date-asc" | "date" => Ok(Self::DateAsc),
+            "date-desc" => Ok(Self::DateDesc),
+            "duration" => Ok(Self::Duration),
+            "description" => Ok(Self::Description),
+            other => Err(anyhow::anyhow!(
+                "invalid sort order '{other}', expected 'date-asc', 'date-desc', 'duration' or 'description'"
+            )),
+        }
+    }
+}
+
+fn description_key(description: &Option<String>) -> String {
+    description.as_deref().unwrap_or_default().to_lowercase()
+}
+
+pub fn sort_entries(mut entries: Vec<TimeEntry>, order: SortOrder) -> Vec<TimeEntry> {
+    match order {
+        SortOrder::DateAsc => return sort_by_date(entries),
+        SortOrder::DateDesc => entries.sort_by_key(|e| Reverse(e.start)),
+        SortOrder::Duration => entries.sort_by_key(|e| Reverse(e.duration)),
+        SortOrder::Description => entries.sort_by_cached_key(|e| description_key(&e.description)),
+    }
+    entries
+}
+
+/// Sorts groups; date orders use the group's day, or its earliest entry when it spans days.
+pub fn sort_groups(mut groups: Vec<GroupedTimeEntry>, order: SortOrder) -> Vec<GroupedTimeEntry> {
+    let first_start =
+        |g: &GroupedTimeEntry| g.date.or_else(|| g.entries.iter().map(|e| e.start).min());
+    match order {
+        SortOrder::DateAsc => groups.sort_by_key(first_start),
+        SortOrder::DateDesc => groups.sort_by_key(|g| Reverse(first_start(g))),
+        SortOrder::Duration => groups.sort_by_key(|g| Reverse(g.total_duration)),
+        SortOrder::Description => groups.sort_by_cached_key(|g| description_key(&g.description)),
+    }
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::create_test_project;
     use chrono::Utc;
 
     fn create_test_entry(
@@ -257,6 +765,7 @@ mod tests {
             uid: None,
             wid: None,
             pid: None,
+            synced_at: None,
         }
     }
 
@@ -286,6 +795,7 @@ mod tests {
             uid: None,
             wid: None,
             pid: None,
+            synced_at: None,
         }
     }
 
@@ -297,13 +807,71 @@ mod tests {
             create_test_entry(3, "Task B", 7200, Some(2)),
         ];
 
-        let grouped = group_by_description(entries);
+        let grouped = group_by_description(entries, false);
 
         assert_eq!(grouped.len(), 2);
         assert_eq!(grouped[0].total_duration, 7200);
         assert_eq!(grouped[1].total_duration, 5400);
     }
 
+    #[test]
+    fn test_group_by_description_case_insensitive() {
+        let entries = vec![
+            create_test_entry(1, "Meeting", 3600, Some(1)),
+            create_test_entry(2, " meeting ", 1800, Some(1)),
+            create_test_entry(3, "MEETING", 600, Some(2)),
+        ];
+
+        assert_eq!(group_by_description(entries.clone(), false).len(), 3);
+
+        let grouped = group_by_description(entries.clone(), true);
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped[0].description.as_deref(), Some("Meeting"));
+        assert_eq!(grouped[0].total_duration, 5400);
+
+        let by_day = group_by_description_and_day(entries, true);
+        assert_eq!(by_day.len(), 2);
+        assert_eq!(by_day[0].entries.len(), 2);
+        assert_eq!(by_day[0].description.as_deref(), Some("Meeting"));
+    }
+
+    #[test]
+    fn test_merge_small_groups() {
+        let entries = vec![
+            create_test_entry(1, "Big", 7200, Some(1)),
+            create_test_entry(2, "Medium", 1800, Some(1)),
+            create_test_entry(3, "Tiny A", 120, Some(1)),
+            create_test_entry(4, "Tiny B", 300, Some(2)),
+            create_test_entry(5, "Tiny C", 60, None),
+        ];
+
+        let (merged, count) = merge_small_groups(group_by_description(entries, false), 600);
+
+        assert_eq!(count, 3);
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].description.as_deref(), Some("Big"));
+        assert_eq!(merged[1].description.as_deref(), Some("Medium"));
+        let other = &merged[2];
+        assert_eq!(other.description.as_deref(), Some(OTHER_GROUP_LABEL));
+        assert_eq!(other.total_duration, 480);
+        assert_eq!(other.entries.len(), 3);
+        assert_eq!(other.project_id, None);
+    }
+
+    #[test]
+    fn test_merge_small_groups_leaves_single_small_group() {
+        let entries = vec![
+            create_test_entry(1, "Big", 7200, Some(1)),
+            create_test_entry(2, "Tiny", 120, Some(1)),
+        ];
+
+        let (merged, count) = merge_small_groups(group_by_description(entries, false), 600);
+
+        assert_eq!(count, 0);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[1].description.as_deref(), Some("Tiny"));
+    }
+
     #[test]
     fn test_filter_by_project() {
         let entries = vec![
@@ -318,6 +886,282 @@ mod tests {
         assert!(filtered.iter().all(|e| e.project_id == Some(1)));
     }
 
+    #[test]
+    fn test_filter_by_projects_keeps_any_listed_project() {
+        let entries = vec![
+            create_test_entry(1, "Task A", 3600, Some(1)),
+            create_test_entry(2, "Task B", 1800, Some(2)),
+            create_test_entry(3, "Task C", 7200, Some(3)),
+            create_test_entry(4, "Task D", 600, None),
+        ];
+
+        let filtered = filter_by_projects(entries, &[1, 3].into_iter().collect());
+
+        let ids: Vec<i64> = filtered.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_filter_by_projects_empty_set_is_no_filter() {
+        let entries = vec![
+            create_test_entry(1, "Task A", 3600, Some(1)),
+            create_test_entry(2, "Task B", 1800, None),
+        ];
+
+        let filtered = filter_by_projects(entries, &std::collections::HashSet::new());
+
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_tag_id_matches_ids() {
+        let mut tagged = create_test_entry(1, "Task A", 3600, None);
+        tagged.tag_ids = Some(vec![5]);
+        let untagged = create_test_entry(2, "Task B", 1800, None);
+        let entries = vec![tagged, untagged];
+
+        let matching = filter_by_tag_id(entries.clone(), &[5].into_iter().collect());
+        assert_eq!(matching.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+
+        let other = filter_by_tag_id(entries.clone(), &[6].into_iter().collect());
+        assert!(other.is_empty());
+
+        let all = filter_by_tag_id(entries, &std::collections::HashSet::new());
+        assert_eq!(all.len(), 2);
+    }
+
+    #[test]
+    fn test_filter_by_description_regex_anchored() {
+        let entries = vec![
+            create_test_entry(1, "Meeting with client", 3600, None),
+            create_test_entry(2, "Client meeting", 1800, None),
+            create_test_entry(3, "meeting notes", 600, None),
+        ];
+
+        let pattern = regex::Regex::new("^Meeting").unwrap();
+        let filtered = filter_by_description_regex(entries.clone(), &pattern);
+        assert_eq!(filtered.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+
+        let pattern = regex::RegexBuilder::new("^meeting")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        let filtered = filter_by_description_regex(entries, &pattern);
+        assert_eq!(
+            filtered.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn test_filter_by_description_regex_alternation() {
+        let mut untitled = create_test_entry(4, "", 300, None);
+        untitled.description = None;
+        let entries = vec![
+            create_test_entry(1, "Standup", 900, None),
+            create_test_entry(2, "Code review", 1800, None),
+            create_test_entry(3, "Retro", 3600, None),
+            untitled,
+        ];
+
+        let pattern = regex::Regex::new("^(Standup|Retro)$").unwrap();
+        let filtered = filter_by_description_regex(entries.clone(), &pattern);
+        assert_eq!(
+            filtered.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+
+        let pattern = regex::Regex::new("^$").unwrap();
+        let filtered = filter_by_description_regex(entries, &pattern);
+        assert_eq!(filtered.iter().map(|e| e.id).collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn test_corrected_duration_for_zero_duration_stopped_entry() {
+        let start = Utc::now() - chrono::Duration::hours(2);
+        let mut broken = create_test_entry(1, "Broken", 0, None);
+        broken.start = start;
+        broken.stop = Some(start + chrono::Duration::hours(1));
+        let mut running = create_test_entry(2, "Running", -1, None);
+        running.stop = None;
+        let mut fine = create_test_entry(3, "Fine", 3600, None);
+        fine.start = start;
+        fine.stop = Some(start + chrono::Duration::hours(1));
+
+        assert_eq!(corrected_duration(&broken), Some(3600));
+        assert_eq!(corrected_duration(&running), None);
+        assert_eq!(corrected_duration(&fine), None);
+
+        let entries = vec![broken, running, fine];
+        let invalid: Vec<i64> = find_invalid_durations(&entries)
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(invalid, vec![1]);
+    }
+
+    #[test]
+    fn test_filter_by_workspace() {
+        let mut other = create_test_entry(2, "Task B", 1800, Some(2));
+        other.workspace_id = 2;
+        let entries = vec![
+            create_test_entry(1, "Task A", 3600, Some(1)),
+            other,
+            create_test_entry(3, "Task C", 7200, Some(1)),
+        ];
+
+        let filtered = filter_by_workspace(entries, 1);
+
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|e| e.workspace_id == 1));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(
+            parse_duration("45m").unwrap(),
+            chrono::Duration::minutes(45)
+        );
+        assert_eq!(parse_duration("12h").unwrap(), chrono::Duration::hours(12));
+        assert_eq!(parse_duration(" 2D ").unwrap(), chrono::Duration::days(2));
+        assert_eq!(parse_duration("3w").unwrap(), chrono::Duration::weeks(3));
+        for bad in [
+            "",
+            "d",
+            "2",
+            "2y",
+            "-1d",
+            "1.5h",
+            "2 d",
+            "9999999999999999w",
+        ] {
+            assert!(parse_duration(bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_filter_by_entry_age() {
+        let now = Utc::now();
+        let entries = vec![
+            create_test_entry_with_date(1, "Today", 3600, None, now - chrono::Duration::hours(3)),
+            create_test_entry_with_date(
+                2,
+                "Last week",
+                3600,
+                None,
+                now - chrono::Duration::days(6),
+            ),
+            create_test_entry_with_date(3, "Old", 3600, None, now - chrono::Duration::days(40)),
+        ];
+        let ids = |entries: Vec<TimeEntry>| entries.iter().map(|e| e.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(filter_newer_than(
+                entries.clone(),
+                chrono::Duration::days(2),
+                now
+            )),
+            vec![1]
+        );
+        assert_eq!(
+            ids(filter_older_than(
+                entries.clone(),
+                chrono::Duration::days(30),
+                now
+            )),
+            vec![3]
+        );
+        let between = filter_older_than(
+            filter_newer_than(entries.clone(), chrono::Duration::days(30), now),
+            chrono::Duration::days(2),
+            now,
+        );
+        assert_eq!(ids(between), vec![2]);
+
+        let ages_ago = parse_duration("99999999999d").unwrap();
+        assert_eq!(
+            ids(filter_newer_than(entries.clone(), ages_ago, now)),
+            vec![1, 2, 3]
+        );
+        assert!(filter_older_than(entries, ages_ago, now).is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_duration_range_is_inclusive() {
+        let entries = vec![
+            create_test_entry(1, "Too short", 899, None),
+            create_test_entry(2, "At min", 900, None),
+            create_test_entry(3, "Between", 1800, None),
+            create_test_entry(4, "At max", 3600, None),
+            create_test_entry(5, "Too long", 3601, None),
+        ];
+        let ids = |entries: Vec<TimeEntry>| entries.iter().map(|e| e.id).collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(filter_by_duration_range(
+                entries.clone(),
+                Some(900),
+                Some(3600)
+            )),
+            vec![2, 3, 4]
+        );
+        assert_eq!(
+            ids(filter_by_duration_range(entries.clone(), None, Some(900))),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(filter_by_duration_range(entries, Some(3600), None)),
+            vec![4, 5]
+        );
+    }
+
+    #[test]
+    fn test_duration_range_skips_max_for_running_entries() {
+        let mut running = create_test_entry(1, "Running", 0, None);
+        running.start = Utc::now() - chrono::Duration::hours(3);
+        running.stop = None;
+        running.duration = -running.start.timestamp();
+        let mut just_started = running.clone();
+        just_started.id = 2;
+        just_started.start = Utc::now() - chrono::Duration::minutes(1);
+        just_started.duration = -just_started.start.timestamp();
+
+        let filter = TimeEntryFilter {
+            min_duration_secs: Some(600),
+            max_duration_secs: Some(3600),
+            ..TimeEntryFilter::default()
+        };
+        assert!(filter.is_active());
+        let kept = filter.apply(vec![running, just_started], &[]);
+        assert_eq!(kept.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+    }
+
+    #[test]
+    fn test_filter_running_and_elapsed_seconds() {
+        let now = Utc::now();
+        let mut negative = create_test_entry(2, "Running", -1, None);
+        negative.start = now - chrono::Duration::minutes(90);
+        let mut no_stop = create_test_entry(3, "No stop", 0, None);
+        no_stop.start = now - chrono::Duration::minutes(30);
+        no_stop.stop = None;
+        let entries = vec![create_test_entry(1, "Done", 3600, None), negative, no_stop];
+
+        assert_eq!(elapsed_seconds(&entries[0], now), 3600);
+        assert_eq!(elapsed_seconds(&entries[1], now), 5400);
+        assert_eq!(elapsed_seconds(&entries[2], now), 1800);
+
+        let running = filter_running(entries.clone());
+        let ids: Vec<i64> = running.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![2, 3]);
+
+        let filter = TimeEntryFilter {
+            running_only: true,
+            ..TimeEntryFilter::default()
+        };
+        assert!(filter.is_active());
+        assert_eq!(filter.apply(entries, &[]).len(), 2);
+    }
+
     #[test]
     fn test_calculate_total_duration() {
         let entries = vec![
@@ -330,32 +1174,19 @@ mod tests {
         assert_eq!(total, 5400);
     }
 
-    fn create_test_project(id: i64, client_id: Option<i64>) -> crate::toggl::models::Project {
-        crate::toggl::models::Project {
-            id,
-            workspace_id: 1,
+    fn client_project(id: i64, client_id: Option<i64>) -> Project {
+        Project {
             client_id,
-            name: format!("Project {}", id),
-            is_private: false,
-            active: true,
-            at: Utc::now(),
-            created_at: Utc::now(),
-            color: "#000000".to_string(),
-            billable: Some(false),
-            template: None,
-            auto_estimates: None,
-            estimated_hours: None,
-            rate: None,
-            currency: None,
+            ..create_test_project(id, &format!("Project {}", id))
         }
     }
 
     #[test]
     fn test_filter_by_client() {
         let projects = vec![
-            create_test_project(1, Some(100)),
-            create_test_project(2, Some(200)),
-            create_test_project(3, Some(100)),
+            client_project(1, Some(100)),
+            client_project(2, Some(200)),
+            client_project(3, Some(100)),
         ];
 
         let entries = vec![
@@ -375,12 +1206,58 @@ mod tests {
     }
 
     #[test]
-    fn test_combined_filters() {
+    fn test_group_by_client() {
         let projects = vec![
-            create_test_project(1, Some(100)),
-            create_test_project(2, Some(200)),
+            client_project(1, Some(100)),
+            client_project(2, Some(200)),
+            client_project(3, Some(100)),
+            client_project(4, None),
         ];
 
+        let entries = vec![
+            create_test_entry(1, "Task A", 3600, Some(1)),
+            create_test_entry(2, "Task B", 1800, Some(2)),
+            create_test_entry(3, "Task C", 7200, Some(3)),
+            create_test_entry(4, "Task D", 600, Some(4)),
+            create_test_entry(5, "Task E", 300, None),
+        ];
+
+        let grouped = group_by_client(entries, &projects);
+
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(grouped[0].description.as_deref(), Some("Client 100"));
+        assert_eq!(grouped[0].total_duration, 10800);
+        assert_eq!(grouped[0].entries.len(), 2);
+        assert_eq!(grouped[1].description.as_deref(), Some("Client 200"));
+        assert_eq!(grouped[1].total_duration, 1800);
+        assert_eq!(grouped[2].description.as_deref(), Some(NO_CLIENT_LABEL));
+        assert_eq!(grouped[2].total_duration, 900);
+    }
+
+    #[test]
+    fn test_group_by_tag_counts_multi_tagged_entries_under_each_tag() {
+        let mut design = create_test_entry(1, "Mockups", 3600, None);
+        design.tags = Some(vec!["design".to_string(), "client".to_string()]);
+        let mut meeting = create_test_entry(2, "Sync", 1800, None);
+        meeting.tags = Some(vec!["client".to_string()]);
+        let untagged = create_test_entry(3, "Misc", 600, None);
+
+        let grouped = group_by_tag(vec![design, meeting, untagged]);
+
+        assert_eq!(grouped.len(), 3);
+        assert_eq!(grouped[0].description.as_deref(), Some("client"));
+        assert_eq!(grouped[0].total_duration, 5400);
+        assert_eq!(grouped[0].entries.len(), 2);
+        assert_eq!(grouped[1].description.as_deref(), Some("design"));
+        assert_eq!(grouped[1].total_duration, 3600);
+        assert_eq!(grouped[2].description.as_deref(), Some(UNTAGGED_LABEL));
+        assert_eq!(grouped[2].total_duration, 600);
+    }
+
+    #[test]
+    fn test_combined_filters() {
+        let projects = vec![client_project(1, Some(100)), client_project(2, Some(200))];
+
         let mut entry1 = create_test_entry(1, "Task A", 3600, Some(1));
         entry1.tags = Some(vec!["urgent".to_string()]);
 
@@ -452,7 +1329,7 @@ mod tests {
             create_test_entry_with_date(5, "Coding", 7200, Some(1), day1),
         ];
 
-        let grouped = group_by_description_and_day(entries);
+        let grouped = group_by_description_and_day(entries, false);
 
         assert_eq!(grouped.len(), 4);
 
@@ -472,6 +1349,40 @@ mod tests {
         assert_eq!(day2_meeting.entries.len(), 2);
     }
 
+    #[test]
+    fn test_split_at_midnight_credits_each_day() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 20, 23, 0, 0).unwrap();
+        let mut overnight = create_test_entry_with_date(1, "Deploy", 7200, Some(1), start);
+        overnight.stop = Some(start + chrono::Duration::hours(2));
+        let same_day = create_test_entry_with_date(
+            2,
+            "Deploy",
+            1800,
+            Some(1),
+            Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap(),
+        );
+        let entries = vec![overnight, same_day];
+
+        assert!(spans_midnight(&entries[0]));
+        assert!(!spans_midnight(&entries[1]));
+
+        let grouped = group_by_description_and_day(split_at_midnight(&entries), false);
+        let day_total = |day: u32| {
+            grouped
+                .iter()
+                .find(|g| g.date == Some(Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()))
+                .map(|g| g.total_duration)
+        };
+        assert_eq!(day_total(20), Some(3600 + 1800));
+        assert_eq!(day_total(21), Some(3600));
+
+        // The source entries are left as they were.
+        assert_eq!(entries[0].duration, 7200);
+        assert_eq!(entries[0].start, start);
+    }
+
     #[test]
     fn test_group_by_description_and_day_with_sorting() {
         use chrono::TimeZone;
@@ -487,7 +1398,7 @@ mod tests {
         ];
 
         entries.sort_by_key(|a| a.start);
-        let grouped = group_by_description_and_day(entries);
+        let grouped = group_by_description_and_day(entries, false);
 
         assert_eq!(grouped.len(), 3);
 
@@ -517,7 +1428,7 @@ mod tests {
             create_test_entry_with_date(2, "Task", 1800, Some(2), day1_later),
         ];
 
-        let grouped = group_by_description_and_day(entries);
+        let grouped = group_by_description_and_day(entries, false);
 
         assert_eq!(grouped.len(), 2);
         assert_eq!(grouped[0].description, Some("Task".to_string()));
@@ -525,6 +1436,58 @@ mod tests {
         assert_ne!(grouped[0].project_id, grouped[1].project_id);
     }
 
+    #[test]
+    fn test_find_duplicates_exact() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap();
+        let mut a = create_test_entry_with_date(1, "Standup", 900, Some(1), start);
+        a.stop = Some(start + chrono::Duration::seconds(900));
+        let mut b = a.clone();
+        b.id = 2;
+
+        let duplicates = find_duplicates(&[a, b]);
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].len(), 2);
+    }
+
+    #[test]
+    fn test_find_duplicates_overlapping() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap();
+        let later = Utc.with_ymd_and_hms(2025, 1, 20, 10, 30, 0).unwrap();
+        let mut a = create_test_entry_with_date(1, "Coding", 3600, Some(1), start);
+        a.stop = Some(start + chrono::Duration::seconds(3600));
+        let mut b = create_test_entry_with_date(2, "Coding", 3600, Some(1), later);
+        b.stop = Some(later + chrono::Duration::seconds(3600));
+
+        let duplicates = find_duplicates(&[b, a]);
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0][0].id, 1);
+        assert_eq!(duplicates[0][1].id, 2);
+    }
+
+    #[test]
+    fn test_find_duplicates_ignores_adjacent_and_different_projects() {
+        use chrono::TimeZone;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap();
+        let next = Utc.with_ymd_and_hms(2025, 1, 20, 11, 0, 0).unwrap();
+        let mut a = create_test_entry_with_date(1, "Coding", 3600, Some(1), start);
+        a.stop = Some(next);
+        let mut b = create_test_entry_with_date(2, "Coding", 3600, Some(1), next);
+        b.stop = Some(next + chrono::Duration::seconds(3600));
+        let mut c = create_test_entry_with_date(3, "Coding", 3600, Some(2), start);
+        c.stop = Some(next);
+
+        let duplicates = find_duplicates(&[a, b, c]);
+
+        assert!(duplicates.is_empty());
+    }
+
     #[test]
     fn test_sort_by_date_ascending() {
         use chrono::TimeZone;
@@ -593,4 +1556,112 @@ mod tests {
         assert_eq!(sorted[1].id, 2);
         assert_eq!(sorted[2].id, 3);
     }
+
+    #[test]
+    fn test_sort_entries_by_each_order() {
+        use chrono::TimeZone;
+
+        let day1 = Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap();
+        let day2 = Utc.with_ymd_and_hms(2025, 1, 21, 10, 0, 0).unwrap();
+        let day3 = Utc.with_ymd_and_hms(2025, 1, 22, 10, 0, 0).unwrap();
+        let entries = vec![
+            create_test_entry_with_date(2, "beta", 600, Some(1), day2),
+            create_test_entry_with_date(3, "Alpha", 1800, Some(1), day3),
+            create_test_entry_with_date(1, "gamma", 3600, Some(1), day1),
+        ];
+        let ids = |order| {
+            sort_entries(entries.clone(), order)
+                .iter()
+                .map(|e| e.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(SortOrder::DateAsc), vec![1, 2, 3]);
+        assert_eq!(ids(SortOrder::DateDesc), vec![3, 2, 1]);
+        assert_eq!(ids(SortOrder::Duration), vec![1, 3, 2]);
+        assert_eq!(ids(SortOrder::Description), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_sort_groups_by_description_and_date() {
+        use chrono::TimeZone;
+
+        let day1 = Utc.with_ymd_and_hms(2025, 1, 20, 10, 0, 0).unwrap();
+        let day2 = Utc.with_ymd_and_hms(2025, 1, 21, 10, 0, 0).unwrap();
+        let entries = vec![
+            create_test_entry_with_date(1, "Zulu", 7200, Some(1), day1),
+            create_test_entry_with_date(2, "alpha", 600, Some(1), day2),
+        ];
+
+        let by_name = sort_groups(
+            group_by_description(entries.clone(), false),
+            SortOrder::Description,
+        );
+        assert_eq!(by_name[0].description.as_deref(), Some("alpha"));
+
+        let newest_first = sort_groups(group_by_description(entries, false), SortOrder::DateDesc);
+        assert_eq!(newest_first[0].description.as_deref(), Some("alpha"));
+        assert!(SortOrder::from_str("newest").is_err());
+    }
+
+    #[test]
+    fn test_top_groups_picks_longest_before_sorting() {
+        let entries = vec![
+            create_test_entry(1, "alpha", 600, None),
+            create_test_entry(2, "bravo", 7200, None),
+            create_test_entry(3, "charlie", 300, None),
+            create_test_entry(4, "delta", 3600, None),
+        ];
+
+        let top = top_groups(group_by_description(entries, false), 2);
+        let sorted = sort_groups(top, SortOrder::Description);
+        let names: Vec<_> = sorted
+            .iter()
+            .filter_map(|g| g.description.as_deref())
+            .collect();
+        assert_eq!(names, vec!["bravo", "delta"]);
+    }
+
+    #[test]
+    fn test_top_groups_keeps_other_bucket_last() {
+        let entries = vec![
+            create_test_entry(1, "alpha", 600, None),
+            create_test_entry(2, "bravo", 7200, None),
+            create_test_entry(3, "charlie", 300, None),
+            create_test_entry(4, "delta", 3600, None),
+        ];
+
+        let (merged, _) = merge_small_groups(group_by_description(entries, false), 3000);
+        let top = top_groups(merged.clone(), 3);
+        let names: Vec<_> = top
+            .iter()
+            .filter_map(|g| g.description.as_deref())
+            .collect();
+        assert_eq!(names, vec!["bravo", "delta", OTHER_GROUP_LABEL]);
+
+        assert_eq!(top_groups(merged, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_format_hours_decimal() {
+        assert_eq!(format_hours(5400, DurationFormat::Decimal), "1.50h");
+    }
+
+    #[test]
+    fn test_format_hours_hours_minutes() {
+        assert_eq!(format_hours(5400, DurationFormat::HoursMinutes), "1:30");
+        assert_eq!(format_hours(300, DurationFormat::HoursMinutes), "0:05");
+        assert_eq!(format_hours(-5400, DurationFormat::HoursMinutes), "-1:30");
+        assert_eq!(format_hours(-300, DurationFormat::HoursMinutes), "-0:05");
+        assert_eq!(format_hours(-5400, DurationFormat::Decimal), "-1.50h");
+    }
+
+    #[test]
+    fn test_format_hours_cell_has_no_unit() {
+        assert_eq!(format_hours_cell(5400, DurationFormat::Decimal), "1.50");
+        assert_eq!(
+            format_hours_cell(5400, DurationFormat::HoursMinutes),
+            "1:30"
+        );
+    }
 }
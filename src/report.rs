@@ -1,5 +1,5 @@
 use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 
 use crate::toggl::models::{Project, TimeEntry};
@@ -85,6 +85,18 @@ pub struct PeriodBucket {
     pub by_project: Vec<ProjectSummary>,
 }
 
+/// Per-day insight figures; days count as active when they have at least one entry.
+#[derive(Debug, Clone)]
+pub struct Highlights {
+    pub active_days: usize,
+    pub daily_average: i64,
+    pub busiest_day: NaiveDate,
+    pub busiest_day_duration: i64,
+    pub longest_entry: String,
+    pub longest_entry_start: DateTime<Utc>,
+    pub longest_entry_duration: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct Report {
     pub period: ReportPeriod,
@@ -96,6 +108,7 @@ pub struct Report {
     pub non_billable_duration: i64,
     pub by_project: Vec<ProjectSummary>,
     pub by_period: Vec<PeriodBucket>,
+    pub highlights: Option<Highlights>,
     pub round_minutes: Option<i64>,
     pub round_mode: RoundingMode,
 }
@@ -126,6 +139,16 @@ fn bucket_key(start: DateTime<Utc>, period: ReportPeriod) -> (String, NaiveDate)
     }
 }
 
+/// Buckets entries by the local calendar month they started in, keyed `YYYY-MM`.
+pub fn bucket_by_month(entries: Vec<TimeEntry>) -> BTreeMap<String, Vec<TimeEntry>> {
+    let mut months: BTreeMap<String, Vec<TimeEntry>> = BTreeMap::new();
+    for entry in entries {
+        let (label, _) = bucket_key(entry.start, ReportPeriod::Monthly);
+        months.entry(label).or_default().push(entry);
+    }
+    months
+}
+
 fn project_name(project_id: Option<i64>, projects: &HashMap<i64, Project>) -> String {
     match project_id {
         Some(id) => projects
@@ -172,6 +195,37 @@ fn aggregate_by_project(
     out
 }
 
+fn highlights(entries: &[(&TimeEntry, i64)]) -> Option<Highlights> {
+    let mut per_day: HashMap<NaiveDate, i64> = HashMap::new();
+    for (entry, dur) in entries {
+        *per_day
+            .entry(entry.start.with_timezone(&Local).date_naive())
+            .or_default() += *dur;
+    }
+
+    let (busiest_day, busiest_day_duration) = per_day
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(day, dur)| (*day, *dur))?;
+    let (longest, longest_entry_duration) = entries
+        .iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.start.cmp(&a.0.start)))?;
+    let total: i64 = per_day.values().sum();
+
+    Some(Highlights {
+        active_days: per_day.len(),
+        daily_average: total / per_day.len() as i64,
+        busiest_day,
+        busiest_day_duration,
+        longest_entry: longest
+            .description
+            .clone()
+            .unwrap_or_else(|| "(no description)".to_string()),
+        longest_entry_start: longest.start,
+        longest_entry_duration: *longest_entry_duration,
+    })
+}
+
 pub fn generate(
     entries: &[TimeEntry],
     projects: &[Project],
@@ -205,6 +259,7 @@ pub fn generate(
     let non_billable_duration = total_duration - billable_duration;
 
     let by_project = aggregate_by_project(&valid, &projects_map);
+    let highlights = highlights(&valid);
 
     type BucketEntries<'a> = (NaiveDate, Vec<(&'a TimeEntry, i64)>);
     let mut bucket_groups: HashMap<String, BucketEntries> = HashMap::new();
@@ -253,11 +308,74 @@ pub fn generate(
         non_billable_duration,
         by_project,
         by_period,
+        highlights,
         round_minutes,
         round_mode,
     }
 }
 
+/// Standup notes for one day's entries: a line per project with its total, then a bullet per
+/// distinct description. Each description is rounded on its own and the project total is the
+/// sum of the rounded bullets, so the numbers add up. Running entries count their elapsed time.
+pub fn standup_summary(
+    entries: &[TimeEntry],
+    projects: &HashMap<i64, Project>,
+    round_minutes: Option<i64>,
+    case_insensitive: bool,
+    now: DateTime<Utc>,
+) -> String {
+    let entries: Vec<TimeEntry> = entries
+        .iter()
+        .cloned()
+        .map(|mut e| {
+            e.duration = crate::processor::elapsed_seconds(&e, now);
+            e
+        })
+        .collect();
+
+    // (description, rounded seconds) bullets per project, in first-seen order.
+    type Bullets = Vec<(String, i64)>;
+    let mut by_project: Vec<(Option<i64>, Bullets)> = Vec::new();
+    for group in crate::processor::group_by_description(entries, case_insensitive) {
+        let seconds = round_seconds_up(group.total_duration, round_minutes);
+        let description = group
+            .description
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or_else(|| "(no description)".to_string());
+        match by_project
+            .iter_mut()
+            .find(|(pid, _)| *pid == group.project_id)
+        {
+            Some((_, items)) => items.push((description, seconds)),
+            None => by_project.push((group.project_id, vec![(description, seconds)])),
+        }
+    }
+
+    let total = |items: &[(String, i64)]| items.iter().map(|(_, s)| s).sum::<i64>();
+    by_project.sort_by(|(pa, a), (pb, b)| {
+        total(b)
+            .cmp(&total(a))
+            .then_with(|| project_name(*pa, projects).cmp(&project_name(*pb, projects)))
+    });
+
+    let mut out = String::new();
+    for (project_id, items) in &by_project {
+        out.push_str(&format!(
+            "{} ({})\n",
+            project_name(*project_id, projects),
+            format_hours(total(items), None)
+        ));
+        for (description, seconds) in items {
+            out.push_str(&format!(
+                "  - {} ({})\n",
+                description,
+                format_hours(*seconds, None)
+            ));
+        }
+    }
+    out
+}
+
 fn round_seconds_up(seconds: i64, round_minutes: Option<i64>) -> i64 {
     match round_minutes {
         Some(m) if m > 0 => {
@@ -283,6 +401,26 @@ fn pct(part: i64, total: i64) -> f64 {
     }
 }
 
+/// Billable time per project as it should appear on an invoice: projects without billable
+/// time are left out, each amount is rounded on its own and the grand total is the sum of the
+/// rounded lines so the numbers always add up.
+fn billable_lines(report: &Report, round_minutes: Option<i64>) -> (Vec<(&str, i64)>, i64) {
+    let mut lines: Vec<(&str, i64)> = report
+        .by_project
+        .iter()
+        .filter(|p| p.billable_duration > 0)
+        .map(|p| {
+            (
+                p.project_name.as_str(),
+                round_seconds_up(p.billable_duration, round_minutes),
+            )
+        })
+        .collect();
+    lines.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let total = lines.iter().map(|(_, seconds)| seconds).sum();
+    (lines, total)
+}
+
 pub fn print_text(report: &Report) {
     let start_local = report.start_date.with_timezone(&Local);
     let end_local = report.end_date.with_timezone(&Local);
@@ -320,6 +458,28 @@ pub fn print_text(report: &Report) {
         report.entry_count,
     );
 
+    if let Some(h) = &report.highlights {
+        println!("\nHighlights:");
+        println!(
+            "  Daily average:   {} over {} active day(s)",
+            format_hours(h.daily_average, None),
+            h.active_days
+        );
+        println!(
+            "  Most productive: {} — {}",
+            h.busiest_day.format("%Y-%m-%d (%a)"),
+            format_hours(h.busiest_day_duration, display_round)
+        );
+        println!(
+            "  Longest entry:   {} — {} ({})",
+            truncate(&h.longest_entry, 40),
+            format_hours(h.longest_entry_duration, display_round),
+            h.longest_entry_start
+                .with_timezone(&Local)
+                .format("%Y-%m-%d")
+        );
+    }
+
     println!("\nBy Project:");
     println!(
         "  {:<40} {:>10} {:>8} {:>10} {:>10}",
@@ -337,6 +497,26 @@ pub fn print_text(report: &Report) {
         );
     }
 
+    let (billable, billable_total) = billable_lines(report, display_round);
+    if !billable.is_empty() {
+        println!("\nBillable by Project:");
+        println!("  {:<40} {:>10}", "Project", "Billable");
+        println!("  {}", "-".repeat(51));
+        for (name, seconds) in &billable {
+            println!(
+                "  {:<40} {:>10}",
+                truncate(name, 40),
+                format_hours(*seconds, None)
+            );
+        }
+        println!("  {}", "-".repeat(51));
+        println!(
+            "  {:<40} {:>10}",
+            "Total billable",
+            format_hours(billable_total, None)
+        );
+    }
+
     println!("\n{} Breakdown:", report.period.label());
     println!(
         "  {:<22} {:>10} {:>10} {:>10}",
@@ -369,6 +549,111 @@ pub fn print_text(report: &Report) {
     println!();
 }
 
+/// Time tracked in a period measured against a configured target, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetProgress {
+    pub tracked: i64,
+    pub target: i64,
+}
+
+impl TargetProgress {
+    pub fn new(tracked: i64, target_hours: f64) -> Self {
+        Self {
+            tracked,
+            target: (target_hours * 3600.0).round() as i64,
+        }
+    }
+
+    pub fn is_met(&self) -> bool {
+        self.tracked >= self.target
+    }
+
+    /// "12.50h of 40.00h" followed by what is left, or by the overage once the target is met.
+    pub fn summary(&self) -> (String, String) {
+        let progress = format!(
+            "{} of {}",
+            format_hours(self.tracked, None),
+            format_hours(self.target, None)
+        );
+        let remaining = if self.is_met() {
+            format!("{} over", format_hours(self.tracked - self.target, None))
+        } else {
+            format!(
+                "{} remaining",
+                format_hours(self.target - self.tracked, None)
+            )
+        };
+        (progress, remaining)
+    }
+}
+
+/// The first instant of `date` in local time, falling back to UTC midnight when a DST gap
+/// skips it.
+pub fn local_midnight(date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Bounds of the local Monday-to-Sunday week containing `now`, the same weeks the weekly
+/// report buckets use.
+pub fn current_week_bounds(now: DateTime<Local>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let monday = now.date_naive() - Duration::days(now.weekday().num_days_from_monday() as i64);
+    (
+        local_midnight(monday),
+        local_midnight(monday + Duration::days(7)),
+    )
+}
+
+/// Seconds tracked by entries starting in `[start, end)`; a running entry counts up to `now`.
+pub fn tracked_seconds(
+    entries: &[TimeEntry],
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> i64 {
+    entries
+        .iter()
+        .filter(|e| e.start >= start && e.start < end)
+        .map(|e| {
+            if e.duration < 0 {
+                (now - e.start).num_seconds().max(0)
+            } else {
+                e.duration
+            }
+        })
+        .sum()
+}
+
+/// Sums the week containing `now` against `target_hours`.
+pub fn weekly_progress(
+    entries: &[TimeEntry],
+    target_hours: f64,
+    now: DateTime<Local>,
+) -> TargetProgress {
+    let (week_start, week_end) = current_week_bounds(now);
+    TargetProgress::new(
+        tracked_seconds(entries, week_start, week_end, now.with_timezone(&Utc)),
+        target_hours,
+    )
+}
+
+/// Prints a target line such as "This week: ...", coloring what is left green once met and
+/// yellow otherwise.
+pub fn print_target_progress(label: &str, progress: &TargetProgress, color: bool) {
+    let (tracked, remaining) = progress.summary();
+    let remaining = if color {
+        let code = if progress.is_met() { 32 } else { 33 };
+        format!("\x1b[{code}m{remaining}\x1b[0m")
+    } else {
+        remaining
+    };
+    println!("{label}: {tracked} target ({remaining})\n");
+}
+
 fn truncate(s: &str, max_chars: usize) -> String {
     let count = s.chars().count();
     if count <= max_chars {
@@ -383,6 +668,7 @@ fn truncate(s: &str, max_chars: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{create_test_project, timed_entry};
     use chrono::TimeZone;
 
     fn entry(
@@ -393,45 +679,30 @@ mod tests {
         billable: bool,
     ) -> TimeEntry {
         TimeEntry {
-            id,
-            workspace_id: 1,
             project_id: pid,
-            task_id: None,
             billable,
-            start,
-            stop: None,
-            duration,
-            description: None,
-            tags: None,
-            tag_ids: None,
-            duronly: false,
-            at: start,
-            server_deleted_at: None,
-            user_id: 1,
-            uid: None,
-            wid: None,
-            pid: None,
+            ..timed_entry(id, start, duration)
         }
     }
 
-    fn project(id: i64, name: &str) -> Project {
-        Project {
-            id,
-            workspace_id: 1,
-            client_id: None,
-            name: name.to_string(),
-            is_private: false,
-            active: true,
-            at: Utc::now(),
-            created_at: Utc::now(),
-            color: "#000000".to_string(),
-            billable: None,
-            template: None,
-            auto_estimates: None,
-            estimated_hours: None,
-            rate: None,
-            currency: None,
-        }
+    #[test]
+    fn bucket_by_month_groups_entries_per_calendar_month() {
+        let jan = Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap();
+        let feb = Utc.with_ymd_and_hms(2025, 2, 14, 12, 0, 0).unwrap();
+        let entries = vec![
+            entry(1, feb, 3600, None, false),
+            entry(2, jan, 1800, None, false),
+            entry(3, jan, 600, None, false),
+        ];
+
+        let months = bucket_by_month(entries);
+
+        assert_eq!(
+            months.keys().cloned().collect::<Vec<_>>(),
+            vec!["2025-01", "2025-02"]
+        );
+        assert_eq!(months["2025-01"].len(), 2);
+        assert_eq!(months["2025-02"][0].id, 1);
     }
 
     #[test]
@@ -443,7 +714,7 @@ mod tests {
             entry(2, d1, 1800, Some(1), false),
             entry(3, d2, 7200, Some(2), true),
         ];
-        let projects = vec![project(1, "A"), project(2, "B")];
+        let projects = vec![create_test_project(1, "A"), create_test_project(2, "B")];
         let report = generate(
             &entries,
             &projects,
@@ -462,6 +733,36 @@ mod tests {
         assert_eq!(report.by_period.len(), 2);
     }
 
+    #[test]
+    fn highlights_cover_active_days_busiest_day_and_longest_entry() {
+        let d1 = Utc.with_ymd_and_hms(2026, 4, 1, 12, 0, 0).unwrap();
+        let d2 = Utc.with_ymd_and_hms(2026, 4, 3, 12, 0, 0).unwrap();
+        let mut long = entry(3, d2, 5400, Some(1), false);
+        long.description = Some("Deep work".to_string());
+        let entries = vec![
+            entry(1, d1, 3600, Some(1), true),
+            entry(2, d1, 3600, Some(1), true),
+            long,
+        ];
+        let report = generate(
+            &entries,
+            &[create_test_project(1, "A")],
+            ReportPeriod::Daily,
+            d1,
+            d2,
+            None,
+            RoundingMode::Total,
+        );
+
+        let h = report.highlights.unwrap();
+        assert_eq!(h.active_days, 2);
+        assert_eq!(h.daily_average, (7200 + 5400) / 2);
+        assert_eq!(h.busiest_day, d1.with_timezone(&Local).date_naive());
+        assert_eq!(h.busiest_day_duration, 7200);
+        assert_eq!(h.longest_entry, "Deep work");
+        assert_eq!(h.longest_entry_duration, 5400);
+    }
+
     #[test]
     fn weekly_report_buckets_same_iso_week() {
         let wed = Utc.with_ymd_and_hms(2026, 4, 1, 9, 0, 0).unwrap();
@@ -470,7 +771,7 @@ mod tests {
             entry(1, wed, 3600, Some(1), true),
             entry(2, fri, 1800, Some(1), true),
         ];
-        let projects = vec![project(1, "A")];
+        let projects = vec![create_test_project(1, "A")];
         let report = generate(
             &entries,
             &projects,
@@ -491,7 +792,7 @@ mod tests {
             entry(1, d, 0, Some(1), true),
             entry(2, d, 3600, Some(1), true),
         ];
-        let projects = vec![project(1, "A")];
+        let projects = vec![create_test_project(1, "A")];
         let report = generate(
             &entries,
             &projects,
@@ -516,6 +817,36 @@ mod tests {
         assert_eq!(round_seconds_up(3601, Some(0)), 3601);
     }
 
+    #[test]
+    fn billable_lines_skip_non_billable_projects_and_sum_rounded_amounts() {
+        let d = Utc.with_ymd_and_hms(2026, 4, 1, 9, 0, 0).unwrap();
+        let entries = vec![
+            entry(1, d, 600, Some(1), true),
+            entry(2, d, 3000, Some(2), true),
+            entry(3, d, 1200, Some(2), false),
+            entry(4, d, 3600, Some(3), false),
+        ];
+        let projects = vec![
+            create_test_project(1, "A"),
+            create_test_project(2, "B"),
+            create_test_project(3, "C"),
+        ];
+        let report = generate(
+            &entries,
+            &projects,
+            ReportPeriod::Daily,
+            d,
+            d,
+            Some(15),
+            RoundingMode::Total,
+        );
+
+        let (lines, total) = billable_lines(&report, Some(15));
+
+        assert_eq!(lines, vec![("B", 3600), ("A", 900)]);
+        assert_eq!(total, 4500);
+    }
+
     #[test]
     fn period_parses_aliases() {
         assert_eq!(
@@ -551,7 +882,7 @@ mod tests {
             entry(1, d, 60, Some(1), true),
             entry(2, d, 60, Some(1), true),
         ];
-        let projects = vec![project(1, "A")];
+        let projects = vec![create_test_project(1, "A")];
 
         let totals_report = generate(
             &entries,
@@ -579,4 +910,68 @@ mod tests {
         assert_eq!(entry_report.by_project[0].duration, 1800);
         assert_eq!(entry_report.by_period[0].duration, 1800);
     }
+
+    #[test]
+    fn weekly_progress_counts_only_the_current_week() {
+        let now = Local.with_ymd_and_hms(2025, 3, 13, 12, 0, 0).unwrap();
+        let (week_start, week_end) = current_week_bounds(now);
+        assert_eq!(
+            week_start.with_timezone(&Local).date_naive(),
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+        );
+        assert_eq!(week_end - week_start, Duration::days(7));
+
+        let entries = vec![
+            entry(1, week_start + Duration::hours(9), 4 * 3600, None, false),
+            entry(2, week_start - Duration::hours(1), 8 * 3600, None, false),
+            entry(
+                3,
+                now.with_timezone(&Utc) - Duration::hours(2),
+                -1,
+                None,
+                false,
+            ),
+        ];
+
+        let progress = weekly_progress(&entries, 10.0, now);
+        assert_eq!(progress.tracked, 6 * 3600);
+        assert!(!progress.is_met());
+        assert_eq!(progress.summary().1, "4.00h remaining");
+
+        let met = weekly_progress(&entries, 5.5, now);
+        assert!(met.is_met());
+        assert_eq!(met.summary().1, "0.50h over");
+    }
+
+    #[test]
+    fn test_standup_summary_groups_descriptions_by_project() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 7, 17, 0, 0).unwrap();
+        let at = |hour| Utc.with_ymd_and_hms(2025, 3, 7, hour, 0, 0).unwrap();
+        let described = |id, hour, duration, pid, desc: &str| {
+            let mut e = entry(id, at(hour), duration, pid, false);
+            e.stop = Some(at(hour) + Duration::seconds(duration));
+            e.description = Some(desc.to_string());
+            e
+        };
+        let mut running = entry(5, at(16), -at(16).timestamp(), Some(1), false);
+        running.description = Some("Review".to_string());
+
+        let entries = vec![
+            described(1, 9, 1200, Some(1), "Review"),
+            described(2, 10, 600, Some(1), "review"),
+            described(3, 11, 3600, Some(2), "Planning"),
+            described(4, 12, 300, None, ""),
+            running,
+        ];
+        let projects = HashMap::from([
+            (1, create_test_project(1, "Web")),
+            (2, create_test_project(2, "Ops")),
+        ]);
+
+        let summary = standup_summary(&entries, &projects, Some(15), true, now);
+        assert_eq!(
+            summary,
+            "Web (1.50h)\n  - Review (1.50h)\nOps (1.00h)\n  - Planning (1.00h)\n(no project) (0.25h)\n  - (no description) (0.25h)\n"
+        );
+    }
 }
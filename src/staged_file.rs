@@ -0,0 +1,106 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// An output file written through a temp file next to it and renamed into place on `commit`,
+/// so the target is either the complete new file or left as it was. Dropping it uncommitted
+/// removes the temp file.
+pub struct StagedFile {
+    temp: PathBuf,
+    target: PathBuf,
+    committed: bool,
+}
+
+impl StagedFile {
+    /// Stages `target`; the temp file lives in the same directory so the rename stays on one
+    /// filesystem.
+    pub fn new(target: &Path) -> Self {
+        let name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "export".to_string());
+        let temp = target.with_file_name(format!(".{}.tmp-{}", name, std::process::id()));
+        Self {
+            temp,
+            target: target.to_path_buf(),
+            committed: false,
+        }
+    }
+
+    /// Path of the temp file, for writers that take a path rather than a `File`.
+    #[cfg_attr(not(any(feature = "parquet", test)), allow(dead_code))]
+    pub fn temp_path(&self) -> &Path {
+        &self.temp
+    }
+
+    pub fn create(&self) -> Result<File> {
+        File::create(&self.temp)
+            .with_context(|| format!("Failed to create output file: {}", self.target.display()))
+    }
+
+    /// Syncs the temp file to disk and renames it over the target.
+    pub fn commit(mut self) -> Result<()> {
+        File::open(&self.temp)
+            .and_then(|f| f.sync_all())
+            .with_context(|| format!("Failed to flush output file: {}", self.target.display()))?;
+        std::fs::rename(&self.temp, &self.target).with_context(|| {
+            format!(
+                "Failed to move {} into place at {}",
+                self.temp.display(),
+                self.target.display()
+            )
+        })?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl Drop for StagedFile {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = std::fs::remove_file(&self.temp);
+        }
+    }
+}
+
+/// Writes `contents` to `path` through a `StagedFile`.
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    use std::io::Write;
+
+    let staged = StagedFile::new(path);
+    staged
+        .create()?
+        .write_all(contents.as_ref())
+        .with_context(|| format!("Failed to write output file: {}", path.display()))?;
+    staged.commit()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_staged_file_replaces_target_only_on_commit() {
+        let dir =
+            std::env::temp_dir().join(format!("toggl-timeguru-test-{}-staged", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("report.csv");
+        std::fs::write(&target, b"old").unwrap();
+
+        let abandoned = StagedFile::new(&target);
+        abandoned.create().unwrap().write_all(b"partial").unwrap();
+        let temp = abandoned.temp_path().to_path_buf();
+        assert!(temp.exists());
+        drop(abandoned);
+        assert!(!temp.exists());
+        assert_eq!(std::fs::read(&target).unwrap(), b"old");
+
+        write(&target, b"new").unwrap();
+        let leftovers = std::fs::read_dir(&dir).unwrap().count();
+        let contents = std::fs::read(&target).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(contents, b"new");
+        assert_eq!(leftovers, 1);
+    }
+}
@@ -0,0 +1,468 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{BTreeMap, HashSet};
+use tracing::{Instrument, info_span};
+
+use crate::concurrency::run_bounded;
+use crate::db::Database;
+use crate::toggl::TogglApi;
+use crate::toggl::models::{TimeEntry, Workspace};
+
+/// Outcome of syncing one date window. `stale` counts cached entries missing from the server;
+/// they are only `deleted` when the sync reconciles.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WindowSync {
+    pub saved: usize,
+    pub stale: usize,
+    pub deleted: usize,
+    /// Entries fetched in the window, keyed by workspace id.
+    pub by_workspace: BTreeMap<i64, usize>,
+}
+
+/// Counts entries per workspace id.
+pub fn count_by_workspace(entries: &[TimeEntry]) -> BTreeMap<i64, usize> {
+    let mut counts = BTreeMap::new();
+    for entry in entries {
+        *counts.entry(entry.workspace_id).or_default() += 1;
+    }
+    counts
+}
+
+/// Splits `[start, end]` into consecutive windows of at most `chunk_days` days.
+pub fn sync_windows(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    chunk_days: i64,
+) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let chunk = Duration::days(chunk_days.max(1));
+    let mut windows = Vec::new();
+    let mut window_start = start;
+
+    while window_start < end {
+        let window_end = (window_start + chunk).min(end);
+        windows.push((window_start, window_end));
+        window_start = window_end;
+    }
+
+    if windows.is_empty() {
+        windows.push((start, end));
+    }
+
+    windows
+}
+
+/// Fetches one window from the API, saves its entries and records the sync in `sync_metadata`.
+/// Cached entries in the window that the server no longer has (or reports as deleted) are
+/// counted as stale and removed when `reconcile` is set.
+pub async fn sync_window(
+    api: &impl TogglApi,
+    db: &Database,
+    window_start: DateTime<Utc>,
+    window_end: DateTime<Utc>,
+    user_id: Option<i64>,
+    reconcile: bool,
+) -> Result<WindowSync> {
+    let local_ids = db.get_entry_ids_in_range(window_start, window_end, user_id)?;
+
+    let entries = api
+        .get_time_entries(window_start, window_end)
+        .instrument(info_span!("api_fetch"))
+        .await
+        .with_context(|| {
+            format!(
+                "Sync failed for window {} to {} (earlier windows were saved)",
+                window_start.format("%Y-%m-%d"),
+                window_end.format("%Y-%m-%d")
+            )
+        })?;
+    let entries = without_deleted(entries);
+
+    let api_ids: HashSet<i64> = entries.iter().map(|e| e.id).collect();
+    let stale_ids: Vec<i64> = local_ids
+        .into_iter()
+        .filter(|id| !api_ids.contains(id))
+        .collect();
+
+    let deleted = if reconcile {
+        db.delete_entries_by_ids(&stale_ids)?
+    } else {
+        0
+    };
+    let saved = info_span!("db_save", entries = entries.len())
+        .in_scope(|| db.save_time_entries(&entries))?;
+    db.update_sync_metadata("time_entries", entries.last().map(|e| e.id))?;
+
+    Ok(WindowSync {
+        saved,
+        stale: stale_ids.len(),
+        deleted,
+        by_workspace: count_by_workspace(&entries),
+    })
+}
+
+/// Drops entries the API reports as deleted on the server.
+fn without_deleted(entries: Vec<TimeEntry>) -> Vec<TimeEntry> {
+    entries
+        .into_iter()
+        .filter(|e| e.server_deleted_at.is_none())
+        .collect()
+}
+
+/// Fetches entries for a range and caches them, returning those not deleted on the server.
+pub async fn fetch_and_cache_entries(
+    api: &impl TogglApi,
+    db: &Database,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> Result<Vec<TimeEntry>> {
+    let entries = api
+        .get_time_entries(start_date, end_date)
+        .instrument(info_span!("api_fetch"))
+        .await?;
+    let entries = without_deleted(entries);
+    info_span!("db_save", entries = entries.len()).in_scope(|| db.save_time_entries(&entries))?;
+    db.update_sync_metadata("time_entries", entries.last().map(|e| e.id))?;
+    Ok(entries)
+}
+
+/// Returns the IDs of `local` entries whose server copy changed after they were last synced,
+/// i.e. the server `at` is newer than the cached `synced_at`. Entries never synced are skipped.
+pub fn entries_changed_on_server(local: &[TimeEntry], server: &[TimeEntry]) -> Vec<i64> {
+    local
+        .iter()
+        .filter_map(|entry| {
+            let synced_at = entry.synced_at?;
+            server
+                .iter()
+                .find(|s| s.id == entry.id)
+                .filter(|s| s.at > synced_at)
+                .map(|_| entry.id)
+        })
+        .collect()
+}
+
+/// Re-fetches the range spanned by `local` and returns the IDs of entries changed on the server
+/// since they were last synced.
+pub async fn find_server_conflicts(api: &impl TogglApi, local: &[TimeEntry]) -> Result<Vec<i64>> {
+    let (Some(start), Some(end)) = (
+        local.iter().map(|e| e.start).min(),
+        local.iter().map(|e| e.start).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let server = api
+        .get_time_entries(start, end + Duration::minutes(1))
+        .await
+        .context("Failed to re-fetch entries for the conflict check")?;
+    Ok(entries_changed_on_server(local, &server))
+}
+
+/// Fetches the projects of each workspace, at most `concurrency` at a time, and caches them,
+/// returning the number saved.
+pub async fn sync_projects(
+    api: &impl TogglApi,
+    db: &Database,
+    workspaces: &[Workspace],
+    user_id: Option<i64>,
+    concurrency: usize,
+) -> Result<usize> {
+    let results = run_bounded(
+        workspaces.iter().map(|w| api.get_projects(w.id)),
+        concurrency,
+    )
+    .instrument(info_span!(
+        "api_fetch_projects",
+        workspaces = workspaces.len()
+    ))
+    .await;
+
+    info_span!("db_save_projects").in_scope(|| {
+        let mut total = 0;
+        for projects in results {
+            total += db.save_projects(&projects?, user_id)?;
+        }
+        Ok(total)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{
+        create_test_entry, create_test_project, create_test_workspace, in_memory_db,
+    };
+    use crate::toggl::models::Project;
+
+    struct MockApi {
+        entries: Vec<TimeEntry>,
+        projects: Vec<Project>,
+    }
+
+    impl TogglApi for MockApi {
+        async fn get_current_user_id(&self) -> Result<i64> {
+            Ok(1)
+        }
+
+        async fn get_current_user_email(&self) -> Result<String> {
+            Ok("user@example.com".to_string())
+        }
+
+        async fn get_time_entries(
+            &self,
+            start_date: DateTime<Utc>,
+            end_date: DateTime<Utc>,
+        ) -> Result<Vec<TimeEntry>> {
+            Ok(self
+                .entries
+                .iter()
+                .filter(|e| e.start >= start_date && e.start <= end_date)
+                .cloned()
+                .collect())
+        }
+
+        async fn get_workspaces(&self) -> Result<Vec<Workspace>> {
+            Ok(Vec::new())
+        }
+
+        async fn get_projects(&self, workspace_id: i64) -> Result<Vec<Project>> {
+            Ok(self
+                .projects
+                .iter()
+                .filter(|p| p.workspace_id == workspace_id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_entries_changed_on_server() {
+        let synced = Utc::now() - Duration::hours(1);
+        let start = synced - Duration::days(1);
+
+        let mut unchanged = create_test_entry(1, start);
+        unchanged.synced_at = Some(synced);
+        let mut changed = create_test_entry(2, start);
+        changed.synced_at = Some(synced);
+        let never_synced = create_test_entry(3, start);
+
+        let mut server = vec![unchanged.clone(), changed.clone(), never_synced.clone()];
+        server[0].at = synced - Duration::minutes(5);
+        server[1].at = synced + Duration::minutes(5);
+        server[2].at = synced + Duration::minutes(5);
+
+        let local = [unchanged, changed, never_synced];
+        assert_eq!(entries_changed_on_server(&local, &server), vec![2]);
+        assert!(entries_changed_on_server(&local, &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_find_server_conflicts_refetches_entry_range() {
+        let synced = Utc::now() - Duration::hours(1);
+        let start = synced - Duration::days(2);
+
+        let mut local = create_test_entry(1, start);
+        local.synced_at = Some(synced);
+        let mut remote = local.clone();
+        remote.at = synced + Duration::minutes(1);
+
+        let api = MockApi {
+            entries: vec![remote],
+            projects: Vec::new(),
+        };
+
+        assert_eq!(
+            find_server_conflicts(&api, &[local]).await.unwrap(),
+            vec![1]
+        );
+        assert!(find_server_conflicts(&api, &[]).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_count_by_workspace() {
+        let now = Utc::now();
+        let mut other = create_test_entry(3, now);
+        other.workspace_id = 7;
+        let entries = vec![create_test_entry(1, now), create_test_entry(2, now), other];
+
+        assert_eq!(
+            count_by_workspace(&entries),
+            BTreeMap::from([(1, 2), (7, 1)])
+        );
+    }
+
+    #[test]
+    fn test_sync_windows_splits_range_into_chunks() {
+        let start = Utc::now() - Duration::days(10);
+        let end = start + Duration::days(10);
+
+        let windows = sync_windows(start, end, 4);
+
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[0], (start, start + Duration::days(4)));
+        assert_eq!(windows[2], (start + Duration::days(8), end));
+    }
+
+    #[test]
+    fn test_sync_windows_single_window_for_empty_range() {
+        let now = Utc::now();
+        assert_eq!(sync_windows(now, now, 90), vec![(now, now)]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_window_saves_new_and_removes_deleted_entries() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::days(5);
+        let end = Utc::now();
+
+        let stale = create_test_entry(1, start + Duration::days(1));
+        let kept = create_test_entry(2, start + Duration::days(2));
+        db.save_time_entries(&[stale, kept.clone()]).unwrap();
+
+        let api = MockApi {
+            entries: vec![kept, create_test_entry(3, start + Duration::days(3))],
+            projects: Vec::new(),
+        };
+
+        let result = sync_window(&api, &db, start, end, Some(1), true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            WindowSync {
+                saved: 2,
+                stale: 1,
+                deleted: 1,
+                by_workspace: BTreeMap::from([(1, 2)]),
+            }
+        );
+        let mut ids: Vec<i64> = db
+            .get_time_entries(start, end, Some(1))
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_window_without_reconcile_keeps_stale_entries() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::days(5);
+        let end = Utc::now();
+
+        let stale = create_test_entry(1, start + Duration::days(1));
+        db.save_time_entries(&[stale]).unwrap();
+
+        let mut deleted_remotely = create_test_entry(2, start + Duration::days(2));
+        deleted_remotely.server_deleted_at = Some(end);
+        let api = MockApi {
+            entries: vec![deleted_remotely],
+            projects: Vec::new(),
+        };
+
+        let result = sync_window(&api, &db, start, end, Some(1), false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result,
+            WindowSync {
+                saved: 0,
+                stale: 1,
+                deleted: 0,
+                by_workspace: BTreeMap::new(),
+            }
+        );
+        let ids: Vec<i64> = db
+            .get_time_entries(start, end, Some(1))
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_sync_projects_saves_every_workspace_with_bounded_concurrency() {
+        let db = in_memory_db();
+        let project_in = |id: i64, workspace_id: i64| Project {
+            workspace_id,
+            ..create_test_project(id, &format!("Project {}", id))
+        };
+        let api = MockApi {
+            entries: Vec::new(),
+            projects: vec![
+                project_in(10, 1),
+                project_in(20, 2),
+                project_in(21, 2),
+                project_in(30, 3),
+            ],
+        };
+        let workspaces: Vec<Workspace> = (1..=3)
+            .map(|id| create_test_workspace(id, &format!("Workspace {}", id)))
+            .collect();
+
+        let saved = sync_projects(&api, &db, &workspaces, Some(1), 2)
+            .await
+            .unwrap();
+
+        assert_eq!(saved, 4);
+        assert_eq!(db.get_projects(Some(1)).unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_sync_window_leaves_entries_outside_window_alone() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::days(5);
+        let end = Utc::now();
+
+        let outside = create_test_entry(1, start - Duration::days(10));
+        db.save_time_entries(&[outside]).unwrap();
+
+        let api = MockApi {
+            entries: Vec::new(),
+            projects: Vec::new(),
+        };
+
+        let result = sync_window(&api, &db, start, end, Some(1), true)
+            .await
+            .unwrap();
+
+        assert_eq!(result, WindowSync::default());
+        let all = db
+            .get_time_entries(start - Duration::days(30), end, Some(1))
+            .unwrap();
+        assert_eq!(all.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_cache_entries_skips_server_deleted_entries() {
+        let db = in_memory_db();
+        let start = Utc::now() - Duration::days(5);
+        let end = Utc::now();
+
+        let kept = create_test_entry(1, start + Duration::days(1));
+        let mut deleted_remotely = create_test_entry(2, start + Duration::days(2));
+        deleted_remotely.server_deleted_at = Some(end);
+        let api = MockApi {
+            entries: vec![kept, deleted_remotely],
+            projects: Vec::new(),
+        };
+
+        let fetched = fetch_and_cache_entries(&api, &db, start, end)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+        let cached: Vec<i64> = db
+            .get_time_entries(start, end, Some(1))
+            .unwrap()
+            .iter()
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(cached, vec![1]);
+    }
+}
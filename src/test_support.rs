@@ -0,0 +1,81 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::db::Database;
+use crate::toggl::models::{Project, TimeEntry, Workspace};
+
+pub(crate) fn in_memory_db() -> Database {
+    Database::new_in_memory().unwrap()
+}
+
+/// A time entry of user 1 in workspace 1 without project or description. A negative
+/// `duration` makes it a running entry with no stop time.
+pub(crate) fn timed_entry(id: i64, start: DateTime<Utc>, duration: i64) -> TimeEntry {
+    TimeEntry {
+        id,
+        workspace_id: 1,
+        project_id: None,
+        task_id: None,
+        billable: false,
+        start,
+        stop: (duration >= 0).then(|| start + Duration::seconds(duration)),
+        duration,
+        description: None,
+        tags: None,
+        tag_ids: None,
+        duronly: false,
+        at: start,
+        server_deleted_at: None,
+        user_id: 1,
+        uid: None,
+        wid: None,
+        pid: None,
+        synced_at: None,
+    }
+}
+
+/// A finished one-hour entry described as "Entry {id}".
+pub(crate) fn create_test_entry(id: i64, start: DateTime<Utc>) -> TimeEntry {
+    TimeEntry {
+        description: Some(format!("Entry {}", id)),
+        ..timed_entry(id, start, 3600)
+    }
+}
+
+pub(crate) fn create_test_project(id: i64, name: &str) -> Project {
+    Project {
+        id,
+        workspace_id: 1,
+        client_id: None,
+        name: name.to_string(),
+        is_private: false,
+        active: true,
+        at: Utc::now(),
+        created_at: Utc::now(),
+        color: "#000000".to_string(),
+        billable: None,
+        template: None,
+        auto_estimates: None,
+        estimated_hours: None,
+        rate: None,
+        currency: None,
+    }
+}
+
+pub(crate) fn create_test_workspace(id: i64, name: &str) -> Workspace {
+    Workspace {
+        id,
+        name: name.to_string(),
+        premium: false,
+        admin: true,
+        default_hourly_rate: None,
+        default_currency: "USD".to_string(),
+        only_admins_may_create_projects: false,
+        only_admins_see_billable_rates: false,
+        rounding: 0,
+        rounding_minutes: 0,
+        at: Utc::now(),
+        logo_url: None,
+    }
+}
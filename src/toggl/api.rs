@@ -0,0 +1,51 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use super::TogglClient;
+use super::models::{Project, TimeEntry, Workspace};
+
+/// The subset of the Toggl API the command handlers rely on.
+///
+/// Handlers take `&impl TogglApi` so their logic can be exercised against an in-memory fake
+/// instead of the network.
+pub trait TogglApi {
+    async fn get_current_user_id(&self) -> Result<i64>;
+
+    async fn get_current_user_email(&self) -> Result<String>;
+
+    async fn get_time_entries(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<TimeEntry>>;
+
+    async fn get_workspaces(&self) -> Result<Vec<Workspace>>;
+
+    async fn get_projects(&self, workspace_id: i64) -> Result<Vec<Project>>;
+}
+
+impl TogglApi for TogglClient {
+    async fn get_current_user_id(&self) -> Result<i64> {
+        TogglClient::get_current_user_id(self).await
+    }
+
+    async fn get_current_user_email(&self) -> Result<String> {
+        TogglClient::get_current_user_email(self).await
+    }
+
+    async fn get_time_entries(
+        &self,
+        start_date: DateTime<Utc>,
+        end_date: DateTime<Utc>,
+    ) -> Result<Vec<TimeEntry>> {
+        TogglClient::get_time_entries(self, start_date, end_date).await
+    }
+
+    async fn get_workspaces(&self) -> Result<Vec<Workspace>> {
+        TogglClient::get_workspaces(self).await
+    }
+
+    async fn get_projects(&self, workspace_id: i64) -> Result<Vec<Project>> {
+        TogglClient::get_projects(self, workspace_id).await
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use base64::{Engine as _, engine::general_purpose};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use reqwest::{Client, StatusCode, header};
+use std::io::Write;
 use std::sync::{Arc, Mutex};
 use tracing::{debug, error, info, warn};
 
@@ -14,6 +15,23 @@ pub struct BulkUpdateOperation {
     pub value: serde_json::Value,
 }
 
+/// Changes to a time entry sent together in one PUT; fields left `None` are not sent.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct TimeEntryUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i64>,
+}
+
 #[derive(Debug, serde::Deserialize)]
 pub struct BulkUpdateResponse {
     pub success: Vec<i64>,
@@ -43,10 +61,23 @@ impl Default for RateLimitInfo {
     }
 }
 
+pub const DEFAULT_CREATED_WITH: &str = "toggl-timeguru";
+
+/// Number of projects requested per page when listing a workspace's projects.
+const PROJECTS_PAGE_SIZE: usize = 200;
+/// Upper bound on project pages per workspace, in case the server never sends a short page.
+const MAX_PROJECT_PAGES: usize = 500;
+
+/// Base of every API endpoint, including the version segment.
+pub const DEFAULT_API_BASE_URL: &str = "https://api.track.toggl.com/api/v9";
+
 pub struct TogglClient {
     client: Client,
     api_token: String,
     base_url: String,
+    created_with: String,
+    /// Where failed requests are described when verbose errors are on.
+    error_log: Option<Arc<Mutex<dyn Write + Send>>>,
     rate_limit_info: Arc<Mutex<RateLimitInfo>>,
 }
 
@@ -60,17 +91,64 @@ impl TogglClient {
 
         let client = Client::builder()
             .default_headers(headers)
+            .user_agent(format!(
+                "{}/{}",
+                env!("CARGO_PKG_NAME"),
+                env!("CARGO_PKG_VERSION")
+            ))
             .build()
             .context("Failed to create HTTP client")?;
 
         Ok(Self {
             client,
             api_token,
-            base_url: "https://api.track.toggl.com/api/v9".to_string(),
+            base_url: DEFAULT_API_BASE_URL.to_string(),
+            created_with: DEFAULT_CREATED_WITH.to_string(),
+            error_log: None,
             rate_limit_info: Arc::new(Mutex::new(RateLimitInfo::default())),
         })
     }
 
+    /// Overrides the `created_with` value sent when creating time entries.
+    pub fn with_created_with(mut self, created_with: String) -> Self {
+        self.created_with = created_with;
+        self
+    }
+
+    /// Points the client at another API version or a compatible gateway, e.g.
+    /// `https://api.track.toggl.com/api/v10`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Prints the request URL, status and raw response body of failed API calls to stderr.
+    pub fn with_verbose_errors(self, verbose_errors: bool) -> Self {
+        let stderr: Arc<Mutex<dyn Write + Send>> = Arc::new(Mutex::new(std::io::stderr()));
+        self.with_error_log(verbose_errors.then_some(stderr))
+    }
+
+    /// Sends the verbose failure details to `log` instead of stderr; `None` turns them off.
+    pub fn with_error_log(mut self, log: Option<Arc<Mutex<dyn Write + Send>>>) -> Self {
+        self.error_log = log;
+        self
+    }
+
+    /// Joins an endpoint path such as `/me` onto the configured base URL.
+    fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    fn report_failure(&self, url: &str, status: StatusCode, body: &str) {
+        if let Some(log) = &self.error_log
+            && let Ok(mut log) = log.lock()
+        {
+            let _ = writeln!(log, "API request failed: {}", url);
+            let _ = writeln!(log, "  Status: {}", status);
+            let _ = writeln!(log, "  Response body: {}", body);
+        }
+    }
+
     fn auth_header(&self) -> String {
         let credentials = format!("{}:api_token", self.api_token);
         let encoded = general_purpose::STANDARD.encode(credentials.as_bytes());
@@ -142,7 +220,7 @@ impl TogglClient {
     }
 
     pub async fn get_current_user(&self) -> Result<serde_json::Value> {
-        let url = format!("{}/me", self.base_url);
+        let url = self.url("/me");
 
         info!("Fetching current user information from Toggl API");
         debug!("API URL: {}", url);
@@ -179,6 +257,8 @@ impl TogglClient {
             }
             status => {
                 error!("Unexpected response status when fetching user: {}", status);
+                let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 anyhow::bail!("Unexpected response status: {}", status)
             }
         }
@@ -218,20 +298,41 @@ impl TogglClient {
         end_date: DateTime<Utc>,
         max_retries: u32,
     ) -> Result<Vec<TimeEntry>> {
-        let url = format!(
-            "{}/me/time_entries?start_date={}&end_date={}",
-            self.base_url,
-            start_date.format("%Y-%m-%d"),
-            end_date.format("%Y-%m-%d")
-        );
+        // Full timestamps keep the time of day; a date alone would cut the range at midnight.
+        let url = self.url(&format!(
+            "/me/time_entries?start_date={}&end_date={}",
+            start_date.to_rfc3339_opts(SecondsFormat::Secs, true),
+            end_date.to_rfc3339_opts(SecondsFormat::Secs, true)
+        ));
 
         debug!("Fetching time entries from Toggl API: {}", url);
         info!(
             "Requesting time entries from {} to {}",
-            start_date.format("%Y-%m-%d"),
-            end_date.format("%Y-%m-%d")
+            start_date.format("%Y-%m-%d %H:%M:%S"),
+            end_date.format("%Y-%m-%d %H:%M:%S")
         );
 
+        let response = self
+            .get_with_retry(&url, "time entries", max_retries)
+            .await?;
+        let values = response
+            .json::<Vec<serde_json::Value>>()
+            .await
+            .context("Failed to parse time entries")?;
+        let entries = parse_time_entries(values);
+        info!("Successfully fetched {} time entries", entries.len());
+        debug!("Time entries: {:?}", entries);
+        Ok(entries)
+    }
+
+    /// Sends a GET request, retrying on network errors, rate limits and server
+    /// errors with exponential backoff. Returns the response once it is `200 OK`.
+    async fn get_with_retry(
+        &self,
+        url: &str,
+        what: &str,
+        max_retries: u32,
+    ) -> Result<reqwest::Response> {
         let mut last_error = None;
 
         for attempt in 1..=max_retries {
@@ -246,7 +347,7 @@ impl TogglClient {
 
             let response = match self
                 .client
-                .get(&url)
+                .get(url)
                 .header(header::AUTHORIZATION, self.auth_header())
                 .send()
                 .await
@@ -265,15 +366,7 @@ impl TogglClient {
             self.extract_rate_limit_headers(&response);
 
             match status {
-                StatusCode::OK => {
-                    let entries = response
-                        .json::<Vec<TimeEntry>>()
-                        .await
-                        .context("Failed to parse time entries")?;
-                    info!("Successfully fetched {} time entries", entries.len());
-                    debug!("Time entries: {:?}", entries);
-                    return Ok(entries);
-                }
+                StatusCode::OK => return Ok(response),
                 StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
                     error!("Authentication failed with status: {}", status);
                     return Err(anyhow::anyhow!(
@@ -309,12 +402,14 @@ impl TogglClient {
                 }
                 _ => {
                     let error_text = response.text().await.unwrap_or_default();
+                    self.report_failure(url, status, &error_text);
                     error!(
                         "API request failed - Status: {}, Error: {}",
                         status, error_text
                     );
                     return Err(anyhow::anyhow!(
-                        "Failed to fetch time entries. Status: {}, Error: {}",
+                        "Failed to fetch {}. Status: {}, Error: {}",
+                        what,
                         status,
                         error_text
                     ));
@@ -327,7 +422,7 @@ impl TogglClient {
 
     #[allow(dead_code)]
     pub async fn get_workspaces(&self) -> Result<Vec<Workspace>> {
-        let url = format!("{}/workspaces", self.base_url);
+        let url = self.url("/workspaces");
 
         let response = self
             .client
@@ -348,6 +443,8 @@ impl TogglClient {
                 Ok(workspaces)
             }
             status => {
+                let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 anyhow::bail!("Failed to fetch workspaces. Status: {}", status)
             }
         }
@@ -355,30 +452,68 @@ impl TogglClient {
 
     #[allow(dead_code)]
     pub async fn get_projects(&self, workspace_id: i64) -> Result<Vec<Project>> {
-        let url = format!("{}/workspaces/{}/projects", self.base_url, workspace_id);
-
-        let response = self
-            .client
-            .get(&url)
-            .header(header::AUTHORIZATION, self.auth_header())
-            .send()
+        self.get_projects_paginated(workspace_id, PROJECTS_PAGE_SIZE, 3)
             .await
-            .context("Failed to fetch projects")?;
+    }
 
-        self.extract_rate_limit_headers(&response);
+    async fn get_projects_paginated(
+        &self,
+        workspace_id: i64,
+        per_page: usize,
+        max_retries: u32,
+    ) -> Result<Vec<Project>> {
+        let mut projects = Vec::new();
+        let mut previous_ids = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = self.url(&format!(
+                "/workspaces/{}/projects?page={}&per_page={}",
+                workspace_id, page, per_page
+            ));
+            debug!("Fetching projects page {}: {}", page, url);
+
+            let response = self.get_with_retry(&url, "projects", max_retries).await?;
+            let batch = response
+                .json::<Vec<Project>>()
+                .await
+                .context("Failed to parse projects")?;
 
-        match response.status() {
-            StatusCode::OK => {
-                let projects = response
-                    .json::<Vec<Project>>()
-                    .await
-                    .context("Failed to parse projects")?;
-                Ok(projects)
+            // A server that ignores `page` sends the same full page forever.
+            let ids: Vec<i64> = batch.iter().map(|p| p.id).collect();
+            if !ids.is_empty() && ids == previous_ids {
+                warn!(
+                    "Projects page {} repeats page {} for workspace {}; stopping pagination",
+                    page,
+                    page - 1,
+                    workspace_id
+                );
+                break;
             }
-            status => {
-                anyhow::bail!("Failed to fetch projects. Status: {}", status)
+
+            let count = batch.len();
+            projects.extend(batch);
+
+            if count < per_page {
+                break;
+            }
+            if page >= MAX_PROJECT_PAGES {
+                warn!(
+                    "Stopped after {} pages of projects for workspace {}",
+                    page, workspace_id
+                );
+                break;
             }
+            previous_ids = ids;
+            page += 1;
         }
+
+        info!(
+            "Fetched {} projects for workspace {}",
+            projects.len(),
+            workspace_id
+        );
+        Ok(projects)
     }
 
     pub async fn update_time_entry_project(
@@ -394,10 +529,10 @@ impl TogglClient {
             workspace_id, entry_id, project_id
         );
 
-        let url = format!(
-            "{}/workspaces/{}/time_entries/{}",
-            self.base_url, workspace_id, entry_id
-        );
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}",
+            workspace_id, entry_id
+        ));
 
         debug!("API URL: {}", url);
 
@@ -455,6 +590,7 @@ impl TogglClient {
             }
             status => {
                 let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 error!(
                     "Failed to update time entry - Status: {}, Error: {}",
                     status, error_text
@@ -482,10 +618,10 @@ impl TogglClient {
             workspace_id, entry_id, description
         );
 
-        let url = format!(
-            "{}/workspaces/{}/time_entries/{}",
-            self.base_url, workspace_id, entry_id
-        );
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}",
+            workspace_id, entry_id
+        ));
 
         debug!("API URL: {}", url);
 
@@ -539,6 +675,82 @@ impl TogglClient {
             }
             status => {
                 let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
+                error!(
+                    "Failed to update time entry - Status: {}, Error: {}",
+                    status, error_text
+                );
+                Err(anyhow::anyhow!(
+                    "Failed to update time entry. Status: {}, Error: {}",
+                    status,
+                    error_text
+                ))
+            }
+        }
+    }
+
+    /// Applies every change in `update` to one time entry with a single request, so either all
+    /// of them are saved or none is.
+    pub async fn update_time_entry(
+        &self,
+        workspace_id: i64,
+        entry_id: i64,
+        update: &TimeEntryUpdate,
+    ) -> Result<TimeEntry> {
+        self.check_rate_limit_before_request().await?;
+
+        info!(
+            "update_time_entry called: workspace={}, entry={}, update={:?}",
+            workspace_id, entry_id, update
+        );
+
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}",
+            workspace_id, entry_id
+        ));
+
+        debug!("API URL: {}", url);
+
+        info!("Sending PUT request to Toggl API...");
+
+        let response = match self
+            .client
+            .put(&url)
+            .header(header::AUTHORIZATION, self.auth_header())
+            .json(update)
+            .send()
+            .await
+        {
+            Ok(resp) => {
+                debug!("Received response from API");
+                resp
+            }
+            Err(e) => {
+                error!("Network error sending PUT request: {}", e);
+                return Err(anyhow::anyhow!("Network error: {}", e));
+            }
+        };
+
+        self.extract_rate_limit_headers(&response);
+
+        match response.status() {
+            StatusCode::OK => {
+                let updated_entry = response
+                    .json::<TimeEntry>()
+                    .await
+                    .context("Failed to parse updated time entry")?;
+                info!("Successfully updated time entry {}", entry_id);
+                Ok(updated_entry)
+            }
+            StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
+                error!("Authentication failed while updating time entry");
+                Err(anyhow::anyhow!(
+                    "Authentication failed. Please check your API token."
+                ))
+            }
+            status => {
+                let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 error!(
                     "Failed to update time entry - Status: {}, Error: {}",
                     status, error_text
@@ -556,15 +768,16 @@ impl TogglClient {
         &self,
         workspace_id: i64,
         description: Option<String>,
+        project_id: Option<i64>,
     ) -> Result<TimeEntry> {
         self.check_rate_limit_before_request().await?;
 
         info!(
-            "start_time_entry called: workspace={}, description={:?}",
-            workspace_id, description
+            "start_time_entry called: workspace={}, description={:?}, project={:?}",
+            workspace_id, description, project_id
         );
 
-        let url = format!("{}/workspaces/{}/time_entries", self.base_url, workspace_id);
+        let url = self.url(&format!("/workspaces/{}/time_entries", workspace_id));
 
         debug!("API URL: {}", url);
 
@@ -584,13 +797,20 @@ impl TogglClient {
         );
         body.insert(
             "created_with".to_string(),
-            serde_json::Value::String("toggl-timeguru".to_string()),
+            serde_json::Value::String(self.created_with.clone()),
         );
 
         if let Some(desc) = description {
             body.insert("description".to_string(), serde_json::Value::String(desc));
         }
 
+        if let Some(pid) = project_id {
+            body.insert(
+                "project_id".to_string(),
+                serde_json::Value::Number(pid.into()),
+            );
+        }
+
         debug!("Request body: {:?}", body);
 
         info!("Sending POST request to Toggl API...");
@@ -632,6 +852,7 @@ impl TogglClient {
             }
             status => {
                 let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 error!(
                     "Failed to start time entry - Status: {}, Error: {}",
                     status, error_text
@@ -645,6 +866,20 @@ impl TogglClient {
         }
     }
 
+    /// Stops whatever entry is running on the account, in the workspace it was started in.
+    ///
+    /// The current-entry endpoint is account-wide, so the entry's own workspace is used for the
+    /// stop call rather than the configured default. Returns `None` when nothing is running.
+    pub async fn stop_current_time_entry(&self) -> Result<Option<TimeEntry>> {
+        match self.get_current_time_entry().await? {
+            Some(current) => self
+                .stop_time_entry(current.workspace_id, current.id)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
     pub async fn stop_time_entry(&self, workspace_id: i64, entry_id: i64) -> Result<TimeEntry> {
         self.check_rate_limit_before_request().await?;
 
@@ -653,10 +888,10 @@ impl TogglClient {
             workspace_id, entry_id
         );
 
-        let url = format!(
-            "{}/workspaces/{}/time_entries/{}/stop",
-            self.base_url, workspace_id, entry_id
-        );
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}/stop",
+            workspace_id, entry_id
+        ));
 
         debug!("API URL: {}", url);
 
@@ -705,6 +940,7 @@ impl TogglClient {
             }
             status => {
                 let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 error!(
                     "Failed to stop time entry - Status: {}, Error: {}",
                     status, error_text
@@ -718,10 +954,80 @@ impl TogglClient {
         }
     }
 
+    pub async fn delete_time_entry(&self, workspace_id: i64, entry_id: i64) -> Result<()> {
+        self.check_rate_limit_before_request().await?;
+
+        info!(
+            "delete_time_entry called: workspace={}, entry_id={}",
+            workspace_id, entry_id
+        );
+
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}",
+            workspace_id, entry_id
+        ));
+
+        debug!("API URL: {}", url);
+
+        info!("Sending DELETE request to Toggl API...");
+
+        let response = match self
+            .client
+            .delete(&url)
+            .header(header::AUTHORIZATION, self.auth_header())
+            .send()
+            .await
+        {
+            Ok(resp) => {
+                debug!("Received response from API");
+                resp
+            }
+            Err(e) => {
+                error!("Network error sending DELETE request: {}", e);
+                return Err(anyhow::anyhow!("Network error: {}", e));
+            }
+        };
+
+        self.extract_rate_limit_headers(&response);
+
+        match response.status() {
+            StatusCode::OK | StatusCode::NO_CONTENT => {
+                info!("Successfully deleted time entry with id {}", entry_id);
+                Ok(())
+            }
+            StatusCode::FORBIDDEN | StatusCode::UNAUTHORIZED => {
+                error!("Authentication failed while deleting time entry");
+                Err(anyhow::anyhow!(
+                    "Authentication failed. Please check your API token."
+                ))
+            }
+            StatusCode::NOT_FOUND => {
+                error!("Time entry {} not found", entry_id);
+                Err(anyhow::anyhow!(
+                    "Time entry {} not found. It may have already been deleted.",
+                    entry_id
+                ))
+            }
+            status => {
+                let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
+                error!(
+                    "Failed to delete time entry - Status: {}, Error: {}",
+                    status, error_text
+                );
+                Err(anyhow::anyhow!(
+                    "Failed to delete time entry. Status: {}, Error: {}",
+                    status,
+                    error_text
+                ))
+            }
+        }
+    }
+
     pub async fn get_current_time_entry(&self) -> Result<Option<TimeEntry>> {
         info!("get_current_time_entry called");
 
-        let url = format!("{}/me/time_entries/current", self.base_url);
+        let url = self.url("/me/time_entries/current");
 
         debug!("API URL: {}", url);
 
@@ -737,10 +1043,11 @@ impl TogglClient {
 
         match response.status() {
             StatusCode::OK => {
-                let time_entry = response
-                    .json::<Option<TimeEntry>>()
+                let body = response
+                    .text()
                     .await
-                    .context("Failed to parse time entry response")?;
+                    .context("Failed to read time entry response")?;
+                let time_entry = parse_current_time_entry(&body);
 
                 if let Some(ref entry) = time_entry {
                     info!("Found running time entry with id {}", entry.id);
@@ -758,6 +1065,7 @@ impl TogglClient {
             }
             status => {
                 let error_text = response.text().await.unwrap_or_default();
+                self.report_failure(&url, status, &error_text);
                 error!(
                     "Failed to get current time entry - Status: {}, Error: {}",
                     status, error_text
@@ -794,10 +1102,10 @@ impl TogglClient {
             .collect::<Vec<_>>()
             .join(",");
 
-        let url = format!(
-            "{}/workspaces/{}/time_entries/{}",
-            self.base_url, workspace_id, ids_str
-        );
+        let url = self.url(&format!(
+            "/workspaces/{}/time_entries/{}",
+            workspace_id, ids_str
+        ));
 
         info!(
             "bulk_update_time_entries called: workspace={}, entry_count={}",
@@ -903,6 +1211,7 @@ impl TogglClient {
                 }
                 _ => {
                     let error_text = response.text().await.unwrap_or_default();
+                    self.report_failure(&url, status, &error_text);
                     error!(
                         "Bulk update failed - Status: {}, Error: {}",
                         status, error_text
@@ -970,6 +1279,60 @@ impl TogglClient {
     }
 }
 
+/// Parses each record on its own so one malformed entry is logged and skipped
+/// instead of failing the whole response.
+fn parse_time_entries(values: Vec<serde_json::Value>) -> Vec<TimeEntry> {
+    values
+        .into_iter()
+        .filter_map(|value| {
+            let id = value.get("id").cloned();
+            match serde_json::from_value::<TimeEntry>(value)
+                .map_err(anyhow::Error::from)
+                .and_then(TimeEntry::normalize)
+            {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    warn!("Skipping unparseable time entry {:?}: {}", id, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Reads the `/me/time_entries/current` body, which is `null` when nothing is running but has
+/// also been seen as an empty body or `{}`; anything that isn't a usable entry means "not running".
+fn parse_current_time_entry(body: &str) -> Option<TimeEntry> {
+    if body.trim().is_empty() {
+        debug!("Empty current time entry response, treating as no running entry");
+        return None;
+    }
+
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Null) => None,
+        Ok(value) => match serde_json::from_value::<TimeEntry>(value)
+            .map_err(anyhow::Error::from)
+            .and_then(TimeEntry::normalize)
+        {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                debug!(
+                    "Unusable current time entry response, treating as no running entry: {}",
+                    e
+                );
+                None
+            }
+        },
+        Err(e) => {
+            debug!(
+                "Invalid current time entry JSON, treating as no running entry: {}",
+                e
+            );
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -977,9 +1340,9 @@ mod tests {
     use mockito::{Matcher, Server};
 
     fn mock_client(server: &Server) -> TogglClient {
-        let mut client = TogglClient::new("test_token".to_string()).unwrap();
-        client.base_url = format!("{}/api/v9", server.url());
-        client
+        TogglClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/api/v9", server.url()))
     }
 
     #[test]
@@ -1080,6 +1443,334 @@ mod tests {
         assert_eq!(info.resets_in, Some(42));
     }
 
+    #[tokio::test]
+    async fn test_verbose_errors_keep_response_body_in_error() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server).with_verbose_errors(true);
+        let _mock = server
+            .mock("GET", "/api/v9/workspaces/1/projects")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .with_body("invalid workspace")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let err = client.get_projects(1).await.unwrap_err().to_string();
+        assert!(err.contains("400"));
+        assert!(err.contains("invalid workspace"));
+    }
+
+    #[tokio::test]
+    async fn test_verbose_errors_describe_failure_only_when_enabled() {
+        let mut server = Server::new_async().await;
+        let _mock = server
+            .mock("GET", "/api/v9/workspaces/1/projects")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .with_body("invalid workspace")
+            .expect(2)
+            .create_async()
+            .await;
+
+        for verbose in [true, false] {
+            let log = Arc::new(Mutex::new(Vec::new()));
+            let client = mock_client(&server).with_error_log(verbose.then(|| log.clone() as _));
+            client.get_projects(1).await.unwrap_err();
+
+            let output = String::from_utf8(log.lock().unwrap().clone()).unwrap();
+            if verbose {
+                assert!(output.contains("/api/v9/workspaces/1/projects"));
+                assert!(output.contains("400"));
+                assert!(output.contains("Response body: invalid workspace"));
+            } else {
+                assert!(output.is_empty());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_time_entries_skips_malformed_records() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let _mock = server
+            .mock("GET", "/api/v9/me/time_entries")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                {"id":1,"workspace_id":1,"project_id":null,"task_id":null,"billable":false,
+                "start":"2025-01-01T09:00:00Z","stop":"2025-01-01T10:00:00Z","duration":3600,
+                "description":"Complete","tags":null,"tag_ids":null,"duronly":true,
+                "at":"2025-01-01T10:00:00Z","server_deleted_at":null,"user_id":1},
+                {"id":2,"wid":3,"uid":1,"start":"2025-01-02T09:00:00Z",
+                "stop":"2025-01-02T09:30:00Z","duration":null,"at":"2025-01-02T09:30:00Z"},
+                {"id":3,"workspace_id":1,"start":"not a date","at":"2025-01-03T00:00:00Z"},
+                {"id":4,"start":"2025-01-04T09:00:00Z","duration":60,"at":"2025-01-04T09:01:00Z"}
+                ]"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 5, 0, 0, 0).unwrap();
+        let entries = client.get_time_entries(start, end).await.unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].id, 1);
+        assert_eq!(entries[1].id, 2);
+        assert_eq!(entries[1].workspace_id, 3);
+        assert_eq!(entries[1].duration, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fetched_zero_duration_is_reported_as_invalid() {
+        use crate::processor::{corrected_duration, find_invalid_durations};
+        use crate::toggl::TogglApi;
+
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let _mock = server
+            .mock("GET", "/api/v9/me/time_entries")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                {"id":1,"workspace_id":1,"start":"2025-01-01T09:00:00Z",
+                "stop":"2025-01-01T09:30:00Z","duration":0,"at":"2025-01-01T09:30:00Z"},
+                {"id":2,"workspace_id":1,"start":"2025-01-02T09:00:00Z",
+                "stop":"2025-01-02T10:00:00Z","duration":3600,"at":"2025-01-02T10:00:00Z"}
+                ]"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 1, 3, 0, 0, 0).unwrap();
+        let entries = TogglApi::get_time_entries(&client, start, end)
+            .await
+            .unwrap();
+
+        let invalid = find_invalid_durations(&entries);
+        assert_eq!(invalid.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1]);
+        assert_eq!(corrected_duration(invalid[0]), Some(1800));
+    }
+
+    #[tokio::test]
+    async fn test_get_current_time_entry_treats_null_and_empty_object_as_none() {
+        for body in ["null", "{}", ""] {
+            let mut server = Server::new_async().await;
+            let client = mock_client(&server);
+            let _mock = server
+                .mock("GET", "/api/v9/me/time_entries/current")
+                .with_status(200)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1)
+                .create_async()
+                .await;
+
+            let current = client.get_current_time_entry().await.unwrap();
+
+            assert!(current.is_none(), "body {:?} should mean no entry", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stop_current_time_entry_uses_the_entry_workspace() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let entry = r#"{"id":7,"workspace_id":42,"start":"2025-01-01T09:00:00Z","stop":null,
+            "duration":-1735722000,"at":"2025-01-01T09:00:00Z","user_id":1}"#;
+        let stopped = r#"{"id":7,"workspace_id":42,"start":"2025-01-01T09:00:00Z",
+            "stop":"2025-01-01T10:00:00Z","duration":3600,"at":"2025-01-01T10:00:00Z","user_id":1}"#;
+        let _current = server
+            .mock("GET", "/api/v9/me/time_entries/current")
+            .with_status(200)
+            .with_body(entry)
+            .create_async()
+            .await;
+        let stop = server
+            .mock("PATCH", "/api/v9/workspaces/42/time_entries/7/stop")
+            .with_status(200)
+            .with_body(stopped)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let result = client.stop_current_time_entry().await.unwrap().unwrap();
+
+        stop.assert_async().await;
+        assert_eq!(result.workspace_id, 42);
+        assert_eq!(result.duration, 3600);
+    }
+
+    #[tokio::test]
+    async fn test_with_base_url_routes_requests_to_custom_path() {
+        let mut server = Server::new_async().await;
+        let client = TogglClient::new("test_token".to_string())
+            .unwrap()
+            .with_base_url(&format!("{}/gateway/api/v10/", server.url()));
+        let mock = server
+            .mock("GET", "/gateway/api/v10/me")
+            .with_status(200)
+            .with_body(r#"{"id":5,"email":"user@example.com"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        assert_eq!(client.get_current_user_id().await.unwrap(), 5);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_update_time_entry_sends_only_changed_fields_in_one_request() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let mock = server
+            .mock("PUT", "/api/v9/workspaces/3/time_entries/7")
+            .match_body(Matcher::Json(serde_json::json!({
+                "description": "Review",
+                "project_id": 10,
+                "billable": true
+            })))
+            .with_status(200)
+            .with_body(
+                r#"{"id":7,"workspace_id":3,"project_id":10,"billable":true,
+                "description":"Review","start":"2025-01-01T09:00:00Z",
+                "stop":"2025-01-01T10:00:00Z","duration":3600,"at":"2025-01-01T10:00:00Z",
+                "user_id":1}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let update = TimeEntryUpdate {
+            description: Some("Review".to_string()),
+            project_id: Some(10),
+            billable: Some(true),
+            ..Default::default()
+        };
+        let entry = client.update_time_entry(3, 7, &update).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(entry.project_id, Some(10));
+        assert!(entry.billable);
+    }
+
+    #[tokio::test]
+    async fn test_update_time_entry_sends_fixed_times_with_duration() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let mock = server
+            .mock("PUT", "/api/v9/workspaces/3/time_entries/7")
+            .match_body(Matcher::PartialJson(serde_json::json!({"duration": 3600})))
+            .with_status(200)
+            .with_body(
+                r#"{"id":7,"workspace_id":3,"billable":false,"start":"2025-01-01T09:00:00Z",
+                "stop":"2025-01-01T10:00:00Z","duration":3600,"at":"2025-01-01T10:00:00Z",
+                "user_id":1}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let start = Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap();
+        let stop = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        let update = TimeEntryUpdate {
+            start: Some(start),
+            stop: Some(stop),
+            duration: Some((stop - start).num_seconds()),
+            ..Default::default()
+        };
+        let entry = client.update_time_entry(3, 7, &update).await.unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(entry.duration, 3600);
+    }
+
+    #[test]
+    fn test_parse_current_time_entry_reads_running_entry() {
+        let entry = parse_current_time_entry(
+            r#"{"id":7,"workspace_id":1,"start":"2025-01-01T09:00:00Z","stop":null,
+            "duration":-1735722000,"at":"2025-01-01T09:00:00Z","user_id":1}"#,
+        )
+        .unwrap();
+
+        assert_eq!(entry.id, 7);
+        assert!(entry.stop.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_start_time_entry_sends_user_agent_and_created_with() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server).with_created_with("my-script".to_string());
+        let _mock = server
+            .mock("POST", "/api/v9/workspaces/1/time_entries")
+            .match_header(
+                "user-agent",
+                Matcher::Regex(format!("^toggl-timeguru/{}$", env!("CARGO_PKG_VERSION"))),
+            )
+            .match_body(Matcher::PartialJson(
+                serde_json::json!({"created_with": "my-script"}),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{"id":1,"workspace_id":1,"project_id":null,"task_id":null,"billable":false,
+                "start":"2025-01-01T00:00:00Z","stop":null,"duration":-1,"description":null,
+                "tags":null,"tag_ids":null,"duronly":true,"at":"2025-01-01T00:00:00Z",
+                "server_deleted_at":null,"user_id":1,"uid":1,"wid":1,"pid":null}"#,
+            )
+            .expect(1)
+            .create_async()
+            .await;
+
+        let entry = client.start_time_entry(1, None, None).await.unwrap();
+        assert_eq!(entry.id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_get_time_entries_sends_full_timestamps() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let today = Utc::now().date_naive();
+        let start = Utc.from_utc_datetime(&today.and_hms_opt(0, 0, 0).unwrap());
+        let late = Utc.from_utc_datetime(&today.and_hms_opt(23, 0, 0).unwrap());
+        let end = Utc.from_utc_datetime(&today.and_hms_opt(23, 30, 0).unwrap());
+        let _mock = server
+            .mock("GET", "/api/v9/me/time_entries")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded(
+                    "start_date".into(),
+                    start.to_rfc3339_opts(SecondsFormat::Secs, true),
+                ),
+                Matcher::UrlEncoded(
+                    "end_date".into(),
+                    end.to_rfc3339_opts(SecondsFormat::Secs, true),
+                ),
+            ]))
+            .with_status(200)
+            .with_body(format!(
+                r#"[{{"id":9,"workspace_id":1,"start":"{start}","stop":"{stop}","duration":1200,
+                "at":"{stop}","user_id":1}}]"#,
+                start = late.to_rfc3339(),
+                stop = (late + chrono::Duration::minutes(20)).to_rfc3339(),
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let entries = client.get_time_entries(start, end).await.unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].start, late);
+    }
+
     #[tokio::test]
     async fn test_mocked_rate_limit_response_returns_error() {
         let mut server = Server::new_async().await;
@@ -1087,8 +1778,8 @@ mod tests {
         let _mock = server
             .mock("GET", "/api/v9/me/time_entries")
             .match_query(Matcher::AllOf(vec![
-                Matcher::UrlEncoded("start_date".into(), "2025-01-01".into()),
-                Matcher::UrlEncoded("end_date".into(), "2025-01-02".into()),
+                Matcher::UrlEncoded("start_date".into(), "2025-01-01T00:00:00Z".into()),
+                Matcher::UrlEncoded("end_date".into(), "2025-01-02T00:00:00Z".into()),
             ]))
             .with_status(429)
             .with_header("X-Toggl-Quota-Remaining", "0")
@@ -1114,4 +1805,63 @@ mod tests {
         assert_eq!(info.remaining, Some(0));
         assert_eq!(info.resets_in, Some(30));
     }
+
+    #[tokio::test]
+    async fn test_mocked_get_projects_collects_all_pages() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let project = |id: i64| {
+            format!(
+                r##"{{"id":{id},"workspace_id":1,"client_id":null,"name":"P{id}","is_private":false,"active":true,"at":"2025-01-01T00:00:00Z","created_at":"2025-01-01T00:00:00Z","color":"#000000"}}"##
+            )
+        };
+        let _first = server
+            .mock("GET", "/api/v9/workspaces/1/projects")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page".into(), "1".into()),
+                Matcher::UrlEncoded("per_page".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_body(format!("[{},{}]", project(1), project(2)))
+            .expect(1)
+            .create_async()
+            .await;
+        let _second = server
+            .mock("GET", "/api/v9/workspaces/1/projects")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("page".into(), "2".into()),
+                Matcher::UrlEncoded("per_page".into(), "2".into()),
+            ]))
+            .with_status(200)
+            .with_body(format!("[{}]", project(3)))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let projects = client.get_projects_paginated(1, 2, 1).await.unwrap();
+
+        let ids: Vec<i64> = projects.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_get_projects_stops_when_the_server_ignores_paging() {
+        let mut server = Server::new_async().await;
+        let client = mock_client(&server);
+        let body = r##"[{"id":1,"workspace_id":1,"client_id":null,"name":"P1","is_private":false,"active":true,"at":"2025-01-01T00:00:00Z","created_at":"2025-01-01T00:00:00Z","color":"#000000"},{"id":2,"workspace_id":1,"client_id":null,"name":"P2","is_private":false,"active":true,"at":"2025-01-01T00:00:00Z","created_at":"2025-01-01T00:00:00Z","color":"#000000"}]"##;
+        let mock = server
+            .mock("GET", "/api/v9/workspaces/1/projects")
+            .match_query(Matcher::Any)
+            .with_status(200)
+            .with_body(body)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let projects = client.get_projects_paginated(1, 2, 1).await.unwrap();
+
+        mock.assert_async().await;
+        let ids: Vec<i64> = projects.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
 }
@@ -1,4 +1,6 @@
+pub mod api;
 pub mod client;
 pub mod models;
 
+pub use api::TogglApi;
 pub use client::TogglClient;
@@ -1,26 +1,92 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Treats both a missing field and an explicit `null` as the type's default.
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Color given to projects the API returns without one.
+pub const DEFAULT_PROJECT_COLOR: &str = "#9e9e9e";
+
+fn default_project_color() -> String {
+    DEFAULT_PROJECT_COLOR.to_string()
+}
+
+/// Treats a missing, `null` or blank project color as `DEFAULT_PROJECT_COLOR`.
+fn color_or_default<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?
+        .filter(|color| !color.trim().is_empty())
+        .unwrap_or_else(default_project_color))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TimeEntry {
     pub id: i64,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub workspace_id: i64,
     pub project_id: Option<i64>,
     pub task_id: Option<i64>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub billable: bool,
     pub start: DateTime<Utc>,
     pub stop: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub duration: i64,
     pub description: Option<String>,
     pub tags: Option<Vec<String>>,
     pub tag_ids: Option<Vec<i64>>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub duronly: bool,
     pub at: DateTime<Utc>,
     pub server_deleted_at: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "null_as_default")]
     pub user_id: i64,
     pub uid: Option<i64>,
     pub wid: Option<i64>,
     pub pid: Option<i64>,
+    /// When the entry was last written to the local cache; never sent by the API.
+    #[serde(default, skip_serializing)]
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+impl TimeEntry {
+    /// Fills fields that some API payloads only send under their legacy names
+    /// (`wid`, `uid`, `pid`). The duration is kept as the server sent it, so a broken zero
+    /// duration stays visible to `doctor` and `edit --fix-duration`.
+    /// Fails if the entry still has no workspace, since it could not be updated later.
+    pub fn normalize(mut self) -> anyhow::Result<Self> {
+        if self.workspace_id == 0 {
+            self.workspace_id = self
+                .wid
+                .ok_or_else(|| anyhow::anyhow!("time entry {} has no workspace", self.id))?;
+        }
+        if self.user_id == 0
+            && let Some(uid) = self.uid
+        {
+            self.user_id = uid;
+        }
+        if self.project_id.is_none() {
+            self.project_id = self.pid;
+        }
+        Ok(self)
+    }
+
+    /// Rounds up to the next multiple of `round_to_minutes`; non-positive periods leave it as is.
+    pub fn rounded_duration(&self, round_to_minutes: i64) -> i64 {
+        if round_to_minutes <= 0 {
+            return self.duration;
+        }
+        let seconds_per_round = round_to_minutes * 60;
+        ((self.duration as f64 / seconds_per_round as f64).ceil() as i64) * seconds_per_round
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +99,10 @@ pub struct Project {
     pub active: bool,
     pub at: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(
+        default = "default_project_color",
+        deserialize_with = "color_or_default"
+    )]
     pub color: String,
     pub billable: Option<bool>,
     pub template: Option<bool>,
@@ -70,15 +140,21 @@ pub struct GroupedTimeEntry {
 }
 
 impl GroupedTimeEntry {
+    #[cfg(test)]
     pub fn total_hours(&self) -> f64 {
         self.total_duration as f64 / 3600.0
     }
 
+    /// Rounds up to the next multiple of `round_to_minutes`; non-positive periods leave it as is.
     pub fn rounded_duration(&self, round_to_minutes: i64) -> i64 {
+        if round_to_minutes <= 0 {
+            return self.total_duration;
+        }
         let seconds_per_round = round_to_minutes * 60;
         ((self.total_duration as f64 / seconds_per_round as f64).ceil() as i64) * seconds_per_round
     }
 
+    #[cfg(test)]
     pub fn rounded_hours(&self, round_to_minutes: i64) -> f64 {
         self.rounded_duration(round_to_minutes) as f64 / 3600.0
     }
@@ -87,6 +163,78 @@ impl GroupedTimeEntry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::timed_entry;
+
+    #[test]
+    fn test_time_entry_lenient_deserialization() {
+        let entry: TimeEntry = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "wid": 42,
+            "uid": 5,
+            "pid": 9,
+            "billable": null,
+            "start": "2025-01-01T09:00:00Z",
+            "stop": "2025-01-01T10:30:00Z",
+            "duration": null,
+            "description": "Partial",
+            "at": "2025-01-01T10:30:00Z"
+        }))
+        .unwrap();
+        let entry = entry.normalize().unwrap();
+
+        assert_eq!(entry.workspace_id, 42);
+        assert_eq!(entry.user_id, 5);
+        assert_eq!(entry.project_id, Some(9));
+        assert_eq!(entry.duration, 0);
+        assert!(!entry.billable);
+    }
+
+    #[test]
+    fn test_time_entry_normalize_requires_workspace() {
+        let entry: TimeEntry = serde_json::from_value(serde_json::json!({
+            "id": 8,
+            "start": "2025-01-01T09:00:00Z",
+            "duration": 60,
+            "at": "2025-01-01T09:01:00Z"
+        }))
+        .unwrap();
+
+        assert!(entry.normalize().is_err());
+    }
+
+    #[test]
+    fn test_project_without_color_gets_default() {
+        let project = |color: Option<serde_json::Value>| {
+            let mut value = serde_json::json!({
+                "id": 3,
+                "workspace_id": 42,
+                "client_id": null,
+                "name": "Internal",
+                "is_private": false,
+                "active": true,
+                "at": "2025-01-01T09:00:00Z",
+                "created_at": "2025-01-01T09:00:00Z",
+                "billable": null,
+                "template": null,
+                "auto_estimates": null,
+                "estimated_hours": null,
+                "rate": null,
+                "currency": null
+            });
+            if let Some(color) = color {
+                value["color"] = color;
+            }
+            serde_json::from_value::<Project>(value).unwrap().color
+        };
+
+        assert_eq!(project(None), DEFAULT_PROJECT_COLOR);
+        assert_eq!(
+            project(Some(serde_json::Value::Null)),
+            DEFAULT_PROJECT_COLOR
+        );
+        assert_eq!(project(Some(serde_json::json!(""))), DEFAULT_PROJECT_COLOR);
+        assert_eq!(project(Some(serde_json::json!("#06aaf5"))), "#06aaf5");
+    }
 
     fn create_grouped_entry(duration_seconds: i64) -> GroupedTimeEntry {
         GroupedTimeEntry {
@@ -98,6 +246,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rounding_with_non_positive_period_returns_raw_duration() {
+        let entry = create_grouped_entry(1000);
+        assert_eq!(entry.rounded_duration(0), 1000);
+        assert_eq!(entry.rounded_duration(-15), 1000);
+    }
+
     #[test]
     fn test_rounding_quarter_hours_exact() {
         let entry = create_grouped_entry(900);
@@ -181,4 +336,13 @@ mod tests {
         let entry = create_grouped_entry(4176);
         assert_eq!(entry.total_hours(), 1.16);
     }
+
+    #[test]
+    fn test_time_entry_rounded_duration() {
+        let entry = timed_entry(1, Utc::now(), 20 * 60);
+
+        assert_eq!(entry.rounded_duration(15), 1800);
+        assert_eq!(entry.rounded_duration(60), 3600);
+        assert_eq!(entry.rounded_duration(0), 1200);
+    }
 }
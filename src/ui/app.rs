@@ -1,6 +1,6 @@
 use anyhow::Result;
 use arboard::Clipboard;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Days, Duration, Local, Utc};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use ratatui::{
     Frame, Terminal,
@@ -11,8 +11,11 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
 };
 
-use crate::config::{PersistedFilter, ProjectSortMethod};
-use crate::processor::TimeEntryFilter;
+use crate::config::{DurationFormat, Favorite, PersistedFilter, ProjectSortMethod, TuiGrouping};
+use crate::processor::{
+    TimeEntryFilter, elapsed_seconds, format_hours, format_hours_cell, is_running,
+};
+use crate::report::{TargetProgress, current_week_bounds, local_midnight};
 use crate::toggl::TogglClient;
 use crate::toggl::models::{GroupedTimeEntry, Project, TimeEntry};
 use std::collections::{HashMap, HashSet};
@@ -28,6 +31,7 @@ pub enum FilterSection {
     Billable,
     Projects,
     Tags,
+    Workspaces,
 }
 
 impl FilterSection {
@@ -35,15 +39,17 @@ impl FilterSection {
         match self {
             Self::Billable => Self::Projects,
             Self::Projects => Self::Tags,
-            Self::Tags => Self::Billable,
+            Self::Tags => Self::Workspaces,
+            Self::Workspaces => Self::Billable,
         }
     }
 
     fn prev(self) -> Self {
         match self {
-            Self::Billable => Self::Tags,
+            Self::Billable => Self::Workspaces,
             Self::Projects => Self::Billable,
             Self::Tags => Self::Projects,
+            Self::Workspaces => Self::Tags,
         }
     }
 
@@ -52,10 +58,107 @@ impl FilterSection {
             Self::Billable => "Billable",
             Self::Projects => "Projects",
             Self::Tags => "Tags",
+            Self::Workspaces => "Workspaces",
+        }
+    }
+}
+
+/// Date ranges offered by the presets overlay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePreset {
+    Today,
+    Yesterday,
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+    LastDays(i64),
+}
+
+impl DatePreset {
+    const ALL: [Self; 8] = [
+        Self::Today,
+        Self::Yesterday,
+        Self::ThisWeek,
+        Self::LastWeek,
+        Self::ThisMonth,
+        Self::LastDays(7),
+        Self::LastDays(30),
+        Self::LastDays(90),
+    ];
+
+    fn label(self) -> String {
+        match self {
+            Self::Today => "Today".to_string(),
+            Self::Yesterday => "Yesterday".to_string(),
+            Self::ThisWeek => "This Week".to_string(),
+            Self::LastWeek => "Last Week".to_string(),
+            Self::ThisMonth => "This Month".to_string(),
+            Self::LastDays(days) => format!("Last {} days", days),
+        }
+    }
+
+    /// Resolves the preset in local time; weeks start on Monday like the weekly report.
+    fn range(self, now: DateTime<Local>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let today = now.date_naive();
+        match self {
+            Self::Today => (
+                local_midnight(today),
+                local_midnight(today + Duration::days(1)),
+            ),
+            Self::Yesterday => (
+                local_midnight(today - Duration::days(1)),
+                local_midnight(today),
+            ),
+            Self::ThisWeek => current_week_bounds(now),
+            Self::LastWeek => {
+                // Step back in calendar days; subtracting 7 * 24h would be an hour off across
+                // a DST change.
+                let monday = today - Days::new(u64::from(now.weekday().num_days_from_monday()));
+                (
+                    local_midnight(monday - Days::new(7)),
+                    local_midnight(monday),
+                )
+            }
+            Self::ThisMonth => {
+                let first = today.with_day(1).unwrap();
+                let next = first
+                    .checked_add_months(chrono::Months::new(1))
+                    .unwrap_or(first);
+                (local_midnight(first), local_midnight(next))
+            }
+            Self::LastDays(days) => {
+                let end = now.with_timezone(&Utc);
+                (end - Duration::days(days), end)
+            }
         }
     }
 }
 
+/// Distinct lowercase tags across `entries`, sorted.
+fn entry_tags(entries: &[TimeEntry]) -> Vec<String> {
+    let tags: HashSet<String> = entries
+        .iter()
+        .filter_map(|e| e.tags.as_ref())
+        .flatten()
+        .map(|t| t.to_lowercase())
+        .collect();
+    let mut tags: Vec<String> = tags.into_iter().collect();
+    tags.sort();
+    tags
+}
+
+/// Distinct workspace IDs across `entries`, sorted.
+fn entry_workspaces(entries: &[TimeEntry]) -> Vec<i64> {
+    let mut workspaces: Vec<i64> = entries
+        .iter()
+        .map(|e| e.workspace_id)
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    workspaces.sort();
+    workspaces
+}
+
 fn sort_projects(projects: &mut [Project], method: ProjectSortMethod, usage: &HashMap<i64, usize>) {
     match method {
         ProjectSortMethod::Name => {
@@ -70,7 +173,143 @@ fn sort_projects(projects: &mut [Project], method: ProjectSortMethod, usage: &Ha
     }
 }
 
-fn format_rate_limit_reset_duration(seconds: u32) -> String {
+/// How many recently assigned projects are pinned at the top of the project selector.
+const MAX_RECENT_PROJECTS: usize = 5;
+
+/// Moves `id` to the front of the most-recently-used list, keeping at most
+/// `MAX_RECENT_PROJECTS` entries.
+fn remember_recent_project(recent: &mut Vec<i64>, id: i64) {
+    recent.retain(|&r| r != id);
+    recent.insert(0, id);
+    recent.truncate(MAX_RECENT_PROJECTS);
+}
+
+/// Moves the items listed in `recent` to the front, in that order; the rest keep their order.
+fn pin_recent<T>(items: Vec<T>, recent: &[i64], id_of: impl Fn(&T) -> i64) -> Vec<T> {
+    let mut rest: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    let mut pinned = Vec::new();
+    for &id in recent {
+        if let Some(slot) = rest
+            .iter_mut()
+            .find(|slot| slot.as_ref().is_some_and(|item| id_of(item) == id))
+        {
+            pinned.extend(slot.take());
+        }
+    }
+    pinned.extend(rest.into_iter().flatten());
+    pinned
+}
+
+/// True over SSH, or on Linux/BSD without an X11 or Wayland display, where `arboard` cannot
+/// reach a clipboard the user sees.
+fn is_headless_session() -> bool {
+    let set = |name: &str| std::env::var_os(name).is_some_and(|v| !v.is_empty());
+    if set("SSH_CONNECTION") || set("SSH_TTY") {
+        return true;
+    }
+    cfg!(all(unix, not(target_os = "macos"))) && !set("DISPLAY") && !set("WAYLAND_DISPLAY")
+}
+
+/// OSC 52 escape that asks the terminal to put `text` on the clipboard, wrapped in tmux's
+/// passthrough when running inside tmux.
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    use base64::{Engine as _, engine::general_purpose};
+
+    let sequence = format!(
+        "\x1b]52;c;{}\x07",
+        general_purpose::STANDARD.encode(text.as_bytes())
+    );
+    if in_tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", sequence)
+    } else {
+        sequence
+    }
+}
+
+fn copy_via_osc52(text: &str) -> std::io::Result<()> {
+    use std::io::{IsTerminal, Write};
+
+    let in_tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    // Piped output would carry the escape sequence into files instead of reaching a terminal.
+    if !stdout.is_terminal() {
+        return Err(std::io::Error::other("stdout is not a terminal"));
+    }
+    stdout.write_all(osc52_sequence(text, in_tmux).as_bytes())?;
+    stdout.flush()
+}
+
+/// Where `copy_text` managed to put the text.
+pub enum CopyOutcome {
+    Clipboard,
+    Terminal,
+    File(std::path::PathBuf),
+    Failed,
+}
+
+/// Copies `text` to the system clipboard, falling back to OSC 52 through the terminal and then
+/// to a file in the temp directory.
+pub fn copy_text(text: &str) -> CopyOutcome {
+    copy_text_with(text, false)
+}
+
+/// `copy_text` for a command that exits right after copying. On X11 and Wayland the clipboard
+/// is served by the process that set it, so this blocks until another program takes the text
+/// over; a clipboard manager usually does so at once.
+pub fn copy_text_before_exit(text: &str) -> CopyOutcome {
+    copy_text_with(text, true)
+}
+
+fn copy_text_with(
+    text: &str,
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] wait: bool,
+) -> CopyOutcome {
+    if !is_headless_session() {
+        let set = |clipboard: &mut Clipboard| {
+            #[cfg(target_os = "linux")]
+            if wait {
+                use arboard::SetExtLinux;
+                eprintln!(
+                    "Keeping the text on the clipboard until it is replaced (Ctrl+C to stop)"
+                );
+                return clipboard.set().wait().text(text);
+            }
+            clipboard.set_text(text)
+        };
+        match Clipboard::new().and_then(|mut clipboard| set(&mut clipboard)) {
+            Ok(()) => return CopyOutcome::Clipboard,
+            Err(e) => {
+                tracing::warn!("System clipboard failed, falling back to OSC 52: {}", e);
+            }
+        }
+    }
+
+    if copy_via_osc52(text).is_ok() {
+        return CopyOutcome::Terminal;
+    }
+
+    let path = std::env::temp_dir().join("toggl-timeguru-clipboard.txt");
+    match std::fs::write(&path, text) {
+        Ok(()) => CopyOutcome::File(path),
+        Err(_) => CopyOutcome::Failed,
+    }
+}
+
+/// Formats rows as a GitHub-flavored Markdown table. Pipes in cells are escaped and line breaks
+/// flattened so a cell can never break the table.
+fn markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let cell = |text: &str| text.replace('|', "\\|").replace(['\r', '\n'], " ");
+    let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+    let mut table = line(headers.iter().map(|h| cell(h)).collect());
+    table.push_str(&line(headers.iter().map(|_| "---".to_string()).collect()));
+    for row in rows {
+        table.push_str(&line(row.iter().map(|c| cell(c)).collect()));
+    }
+    table
+}
+
+fn format_compact_duration(seconds: u32) -> String {
     let hours = seconds / 3600;
     let minutes = (seconds % 3600) / 60;
     let seconds = seconds % 60;
@@ -91,6 +330,99 @@ fn format_rate_limit_reset_duration(seconds: u32) -> String {
     parts.join(" ")
 }
 
+/// Project filter after a quick-filter press: narrows to `project_id`, or clears the filter when
+/// it already selects exactly that project.
+fn quick_project_filter(current: &HashSet<i64>, project_id: i64) -> HashSet<i64> {
+    if current.len() == 1 && current.contains(&project_id) {
+        HashSet::new()
+    } else {
+        HashSet::from([project_id])
+    }
+}
+
+/// Seconds shown on an entry row: live elapsed time while running, else the (rounded) duration.
+fn entry_display_seconds(entry: &TimeEntry, round_minutes: Option<i64>, now: DateTime<Utc>) -> i64 {
+    if is_running(entry) {
+        elapsed_seconds(entry, now)
+    } else if let Some(minutes) = round_minutes {
+        entry.rounded_duration(minutes)
+    } else {
+        entry.duration
+    }
+}
+
+/// Seconds shown on a group row. A group holding a running entry sums its members' elapsed
+/// time unrounded, since its stored total includes the running entry's negative duration.
+fn group_display_seconds(
+    group: &GroupedTimeEntry,
+    round_minutes: Option<i64>,
+    now: DateTime<Utc>,
+) -> i64 {
+    if group.entries.iter().any(is_running) {
+        group.entries.iter().map(|e| elapsed_seconds(e, now)).sum()
+    } else if let Some(minutes) = round_minutes {
+        group.rounded_duration(minutes)
+    } else {
+        group.total_duration
+    }
+}
+
+/// Rounding intervals, in minutes, that the interval key cycles through after "off".
+const ROUNDING_STEPS: [i64; 5] = [5, 10, 15, 30, 60];
+
+/// The interval after `current` in off → 5 → 10 → 15 → 30 → 60 → off. An interval between steps
+/// (say 20 from config) moves on to the next larger step.
+fn next_rounding_step(current: Option<i64>) -> Option<i64> {
+    match current {
+        None => Some(ROUNDING_STEPS[0]),
+        Some(minutes) => ROUNDING_STEPS.iter().copied().find(|step| *step > minutes),
+    }
+}
+
+/// Footer and status text for a rounding interval.
+fn rounding_label(minutes: Option<i64>) -> String {
+    match minutes {
+        Some(minutes) => format!("{minutes}m"),
+        None => "OFF".to_string(),
+    }
+}
+
+/// Confirmation shown on quit when leaving now would interrupt work or hide a partial failure.
+fn quit_prompt(in_flight: usize, partial_failure: bool) -> Option<&'static str> {
+    if in_flight > 0 {
+        Some("Operations in progress, quit anyway? (y/N)")
+    } else if partial_failure {
+        Some("The last operation partially failed, quit anyway? (y/N)")
+    } else {
+        None
+    }
+}
+
+/// Index of the first row of the next (or previous) run of rows sharing the same day.
+fn day_jump_target<T: PartialEq>(days: &[T], current: usize, forward: bool) -> Option<usize> {
+    if current >= days.len() {
+        return None;
+    }
+
+    let block_start = |mut i: usize| {
+        while i > 0 && days[i - 1] == days[i] {
+            i -= 1;
+        }
+        i
+    };
+
+    if forward {
+        (current + 1..days.len()).find(|&i| days[i] != days[current])
+    } else {
+        let start = block_start(current);
+        if start == 0 {
+            None
+        } else {
+            Some(block_start(start - 1))
+        }
+    }
+}
+
 pub struct App {
     pub time_entries: Vec<TimeEntry>,
     pub grouped_entries: Vec<GroupedTimeEntry>,
@@ -101,21 +433,27 @@ pub struct App {
     pub end_date: DateTime<Utc>,
     pub show_grouped: bool,
     pub group_by_day: bool,
+    pub group_by_client: bool,
     pub sort_by_date: bool,
     pub show_rounded: bool,
     pub round_minutes: Option<i64>,
+    /// Write the rounding shown at exit back to config; off unless the user asks for it.
+    pub save_rounding: bool,
     pub projects: HashMap<i64, Project>,
     pub show_filter_panel: bool,
     pub filter_section: FilterSection,
     pub filter_projects_state: ListState,
     pub filter_tags_state: ListState,
     pub available_tags: Vec<String>,
+    pub filter_workspaces_state: ListState,
+    pub available_workspaces: Vec<i64>,
     pub active_filter: TimeEntryFilter,
     pub clipboard_message: Option<String>,
     pub show_project_selector: bool,
     pub project_selector_state: ListState,
     pub project_search_query: String,
     pub filtered_projects: Vec<Project>,
+    pub recent_project_ids: Vec<i64>,
     pub status_message: Option<String>,
     pub error_message: Option<String>,
     pub show_edit_modal: bool,
@@ -130,6 +468,37 @@ pub struct App {
     pub project_usage_total: usize,
     pub project_usage_window_start: DateTime<Utc>,
     pub project_sort_method: ProjectSortMethod,
+    pub running_entry: Option<TimeEntry>,
+    pub default_workspace_id: Option<i64>,
+    pub show_start_timer_modal: bool,
+    pub start_timer_project_id: Option<i64>,
+    pub duration_format: DurationFormat,
+    pub check_conflicts: bool,
+    conflict_acknowledged: bool,
+    pub case_insensitive_grouping: bool,
+    pub favorites: Vec<Favorite>,
+    pub show_favorites_picker: bool,
+    pub favorites_state: ListState,
+    pub weekly_progress: Option<TargetProgress>,
+    pub current_user_id: Option<i64>,
+    pub show_date_presets: bool,
+    pub date_presets_state: ListState,
+    show_archived_projects: bool,
+    archived_projects: Vec<Project>,
+    in_flight: usize,
+    partial_failure: bool,
+    show_quit_confirm: bool,
+    running_entry_updates: Option<std::sync::mpsc::Receiver<Option<TimeEntry>>>,
+    /// The background timer poll, aborted when the app is dropped.
+    timer_refresh_task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl Drop for App {
+    fn drop(&mut self) {
+        if let Some(task) = self.timer_refresh_task.take() {
+            task.abort();
+        }
+    }
 }
 
 impl App {
@@ -162,14 +531,9 @@ impl App {
             project_selector_state.select(Some(0));
         }
 
-        let available_tags_set: HashSet<String> = all_entries
-            .iter()
-            .filter_map(|e| e.tags.as_ref())
-            .flatten()
-            .map(|t| t.to_lowercase())
-            .collect();
-        let mut available_tags: Vec<String> = available_tags_set.iter().cloned().collect();
-        available_tags.sort();
+        let available_tags = entry_tags(&all_entries);
+        let available_tags_set: HashSet<String> = available_tags.iter().cloned().collect();
+        let available_workspaces = entry_workspaces(&all_entries);
 
         let mut active_filter = TimeEntryFilter::new();
         for pid in saved_filter.project_ids {
@@ -201,6 +565,10 @@ impl App {
         if !available_tags.is_empty() {
             filter_tags_state.select(Some(0));
         }
+        let mut filter_workspaces_state = ListState::default();
+        if !available_workspaces.is_empty() {
+            filter_workspaces_state.select(Some(0));
+        }
 
         Self {
             time_entries: filtered_entries,
@@ -212,14 +580,18 @@ impl App {
             end_date,
             show_grouped: false,
             group_by_day: false,
+            group_by_client: false,
             sort_by_date: false,
             show_rounded: true,
             round_minutes,
+            save_rounding: false,
             projects: projects_map,
             show_filter_panel: false,
             filter_section: FilterSection::Billable,
             filter_projects_state,
             filter_tags_state,
+            filter_workspaces_state,
+            available_workspaces,
             available_tags,
             active_filter,
             clipboard_message: None,
@@ -227,6 +599,7 @@ impl App {
             project_selector_state,
             project_search_query: String::new(),
             filtered_projects,
+            recent_project_ids: Vec::new(),
             status_message: None,
             error_message: None,
             show_edit_modal: false,
@@ -241,14 +614,99 @@ impl App {
             project_usage_total,
             project_usage_window_start,
             project_sort_method,
+            running_entry: None,
+            default_workspace_id: None,
+            show_start_timer_modal: false,
+            start_timer_project_id: None,
+            duration_format: DurationFormat::default(),
+            check_conflicts: false,
+            conflict_acknowledged: false,
+            case_insensitive_grouping: false,
+            favorites: Vec::new(),
+            show_favorites_picker: false,
+            favorites_state: ListState::default(),
+            weekly_progress: None,
+            current_user_id: None,
+            show_date_presets: false,
+            date_presets_state: ListState::default(),
+            show_archived_projects: false,
+            archived_projects: Vec::new(),
+            in_flight: 0,
+            partial_failure: false,
+            show_quit_confirm: false,
+            running_entry_updates: None,
+            timer_refresh_task: None,
+        }
+    }
+
+    /// Quits, or asks first while API operations are pending or the last one partially failed.
+    fn request_quit(&mut self) {
+        if quit_prompt(self.in_flight, self.partial_failure).is_some() {
+            self.show_quit_confirm = true;
+        } else {
+            self.should_quit = true;
+        }
+    }
+
+    /// Runs an API operation while counting it as in flight, so quitting mid-way asks first.
+    fn track_in_flight(&mut self, op: impl FnOnce(&mut Self)) {
+        self.in_flight += 1;
+        op(self);
+        self.in_flight -= 1;
+    }
+
+    /// Sets the most-recently-used project list and pins it in the project selector.
+    pub fn set_recent_projects(&mut self, ids: Vec<i64>) {
+        self.recent_project_ids = ids;
+        self.reset_filtered_projects();
+    }
+
+    /// Polls the running timer in the background every `interval`, so the header stays current
+    /// without pressing anything. Does nothing when offline or when `interval` is zero.
+    pub fn start_timer_refresh(&mut self, interval: std::time::Duration) {
+        let (Some(client), Some(handle)) = (self.client.clone(), self.runtime_handle.clone())
+        else {
+            return;
+        };
+        if interval.is_zero() {
+            return;
+        }
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let task = handle.spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                match client.get_current_time_entry().await {
+                    Ok(entry) => {
+                        if tx.send(entry).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => tracing::warn!("Background timer refresh failed: {}", e),
+                }
+            }
+        });
+        self.running_entry_updates = Some(rx);
+        if let Some(previous) = self.timer_refresh_task.replace(task) {
+            previous.abort();
+        }
+    }
+
+    fn apply_running_entry_updates(&mut self) {
+        if let Some(rx) = &self.running_entry_updates
+            && let Some(latest) = rx.try_iter().last()
+        {
+            self.running_entry = latest;
         }
     }
 
     pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> Result<()> {
         loop {
+            self.apply_running_entry_updates();
             terminal.draw(|f| self.ui(f))?;
 
-            if let Event::Key(key) = event::read()?
+            if event::poll(std::time::Duration::from_secs(1))?
+                && let Event::Key(key) = event::read()?
                 && key.kind == KeyEventKind::Press
             {
                 self.handle_key_event(key);
@@ -273,13 +731,113 @@ impl App {
             return;
         }
 
+        if self.show_quit_confirm {
+            self.show_quit_confirm = false;
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.should_quit = true;
+            } else {
+                // The user has now seen the failure, so a second 'q' quits straight away.
+                self.partial_failure = false;
+            }
+            return;
+        }
+
+        if self.show_favorites_picker {
+            match key.code {
+                KeyCode::Enter => {
+                    self.start_favorite_timer();
+                }
+                KeyCode::Esc | KeyCode::Char('F') => {
+                    self.show_favorites_picker = false;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.next_favorite();
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.previous_favorite();
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.show_date_presets {
+            match key.code {
+                KeyCode::Enter => {
+                    self.apply_selected_date_preset();
+                }
+                KeyCode::Esc | KeyCode::Char('R') => {
+                    self.show_date_presets = false;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    let i = self
+                        .date_presets_state
+                        .selected()
+                        .map(|i| (i + 1) % DatePreset::ALL.len())
+                        .unwrap_or(0);
+                    self.date_presets_state.select(Some(i));
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    let i = self
+                        .date_presets_state
+                        .selected()
+                        .map(|i| i.checked_sub(1).unwrap_or(DatePreset::ALL.len() - 1))
+                        .unwrap_or(0);
+                    self.date_presets_state.select(Some(i));
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.show_start_timer_modal {
+            match key.code {
+                KeyCode::Enter => {
+                    self.start_timer();
+                }
+                KeyCode::Esc => {
+                    self.close_start_timer_modal();
+                }
+                KeyCode::Tab => {
+                    self.toggle_start_timer_project();
+                }
+                KeyCode::Char(c) => {
+                    self.edit_insert_char(c);
+                }
+                KeyCode::Backspace => {
+                    self.edit_backspace();
+                }
+                KeyCode::Delete => {
+                    self.edit_delete();
+                }
+                KeyCode::Left => {
+                    self.edit_cursor = self.edit_cursor.saturating_sub(1);
+                }
+                KeyCode::Right => {
+                    let char_count = self.edit_input.chars().count();
+                    if self.edit_cursor < char_count {
+                        self.edit_cursor += 1;
+                    }
+                }
+                KeyCode::Home => {
+                    self.edit_cursor = 0;
+                }
+                KeyCode::End => {
+                    self.edit_cursor = self.edit_input.chars().count();
+                }
+                _ => {}
+            }
+            return;
+        }
+
         if self.show_edit_modal {
             match key.code {
                 KeyCode::Enter => {
-                    self.save_edited_description();
+                    self.track_in_flight(Self::save_edited_description);
                 }
                 KeyCode::Esc => {
                     self.show_edit_modal = false;
+                    self.conflict_acknowledged = false;
                     self.edit_input.clear();
                     self.edit_cursor = 0;
                     self.edit_entry_ids.clear();
@@ -317,11 +875,12 @@ impl App {
             match key.code {
                 KeyCode::Esc | KeyCode::Char('p') => {
                     self.show_project_selector = false;
+                    self.conflict_acknowledged = false;
                     self.project_search_query.clear();
                     self.reset_filtered_projects();
                 }
                 KeyCode::Enter => {
-                    self.assign_project_to_entry();
+                    self.track_in_flight(Self::assign_project_to_entry);
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
                     self.next_project();
@@ -352,6 +911,9 @@ impl App {
                     self.project_search_query.pop();
                     self.filter_projects();
                 }
+                KeyCode::Char('a') => {
+                    self.toggle_archived_projects();
+                }
                 KeyCode::Char(c) if c.is_alphanumeric() => {
                     self.jump_to_project_by_char(c);
                 }
@@ -388,7 +950,7 @@ impl App {
         } else {
             match key.code {
                 KeyCode::Char('q') | KeyCode::Esc => {
-                    self.should_quit = true;
+                    self.request_quit();
                 }
                 KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.should_quit = true;
@@ -411,18 +973,39 @@ impl App {
                 KeyCode::End => {
                     self.goto_last();
                 }
+                KeyCode::Char('n') => {
+                    self.jump_day(true);
+                }
+                KeyCode::Char('N') => {
+                    self.jump_day(false);
+                }
                 KeyCode::Char('g') => {
                     self.toggle_grouping();
                 }
                 KeyCode::Char('d') => {
                     self.toggle_day_grouping();
                 }
+                KeyCode::Char('C') => {
+                    self.toggle_client_grouping();
+                }
+                KeyCode::Char('P') => {
+                    self.toggle_selected_project_filter();
+                }
+                KeyCode::Char('o') => {
+                    self.toggle_running_filter();
+                }
                 KeyCode::Char('s') => {
                     self.toggle_sort_by_date();
                 }
                 KeyCode::Char('r') => {
                     self.toggle_rounding();
                 }
+                KeyCode::Char('i') => {
+                    self.cycle_rounding_interval();
+                }
+                KeyCode::Char('I') => {
+                    self.toggle_save_rounding();
+                }
                 KeyCode::Char('f') => {
                     self.toggle_filter_panel();
                 }
@@ -433,12 +1016,45 @@ impl App {
                 KeyCode::Char('y') => {
                     self.copy_to_clipboard();
                 }
+                KeyCode::Char('Y') => {
+                    self.copy_formatted_to_clipboard();
+                }
+                KeyCode::Char('M') => {
+                    self.copy_visible_as_markdown();
+                }
+                KeyCode::Char('p') if self.is_client_grouped() => {
+                    self.status_message = Some(
+                        "Project assignment is not available when grouped by client".to_string(),
+                    );
+                }
                 KeyCode::Char('p') => {
                     self.toggle_project_selector();
                 }
+                KeyCode::Char('e') if self.is_client_grouped() => {
+                    self.status_message =
+                        Some("Editing is not available when grouped by client".to_string());
+                }
                 KeyCode::Char('e') => {
                     self.open_edit_modal();
                 }
+                KeyCode::Char('t') => {
+                    self.open_start_timer_modal();
+                }
+                KeyCode::Char('T') => {
+                    self.stop_timer();
+                }
+                KeyCode::Char('u') => {
+                    self.refresh_entries();
+                }
+                KeyCode::Char('F') => {
+                    self.open_favorites_picker();
+                }
+                KeyCode::Char('R') => {
+                    if self.date_presets_state.selected().is_none() {
+                        self.date_presets_state.select(Some(0));
+                    }
+                    self.show_date_presets = true;
+                }
                 _ => {}
             }
         }
@@ -492,6 +1108,16 @@ impl App {
         self.list_state.select(Some(i));
     }
 
+    /// Applies the grouping the TUI should start with and computes the grouped view for it.
+    pub fn set_grouping(&mut self, grouping: TuiGrouping) {
+        self.show_grouped = grouping != TuiGrouping::None;
+        self.group_by_day = grouping == TuiGrouping::Day;
+        self.recompute_grouped_entries();
+        if !self.time_entries.is_empty() {
+            self.list_state.select(Some(0));
+        }
+    }
+
     fn toggle_grouping(&mut self) {
         self.show_grouped = !self.show_grouped;
         self.list_state.select(Some(0));
@@ -503,13 +1129,31 @@ impl App {
         self.list_state.select(Some(0));
     }
 
+    fn toggle_client_grouping(&mut self) {
+        self.group_by_client = !self.group_by_client;
+        if self.group_by_client {
+            self.show_grouped = true;
+        }
+        self.recompute_grouped_entries();
+        self.list_state.select(Some(0));
+    }
+
+    fn is_client_grouped(&self) -> bool {
+        self.show_grouped && self.group_by_client
+    }
+
     fn recompute_grouped_entries(&mut self) {
-        use crate::processor::{group_by_description, group_by_description_and_day};
+        use crate::processor::{
+            group_by_client, group_by_description, group_by_description_and_day,
+        };
 
-        self.grouped_entries = if self.group_by_day {
-            group_by_description_and_day(self.time_entries.clone())
+        self.grouped_entries = if self.group_by_client {
+            let projects: Vec<_> = self.projects.values().cloned().collect();
+            group_by_client(self.time_entries.clone(), &projects)
+        } else if self.group_by_day {
+            group_by_description_and_day(self.time_entries.clone(), self.case_insensitive_grouping)
         } else {
-            group_by_description(self.time_entries.clone())
+            group_by_description(self.time_entries.clone(), self.case_insensitive_grouping)
         };
     }
 
@@ -523,6 +1167,35 @@ impl App {
         self.show_rounded = !self.show_rounded;
     }
 
+    fn cycle_rounding_interval(&mut self) {
+        match next_rounding_step(self.display_rounding()) {
+            Some(minutes) => {
+                self.round_minutes = Some(minutes);
+                self.show_rounded = true;
+            }
+            None => self.show_rounded = false,
+        }
+        let hint = if self.save_rounding {
+            "saved to config on exit"
+        } else {
+            "I to keep it"
+        };
+        self.status_message = Some(format!(
+            "Rounding: {} ({})",
+            rounding_label(self.display_rounding()),
+            hint
+        ));
+    }
+
+    fn toggle_save_rounding(&mut self) {
+        self.save_rounding = !self.save_rounding;
+        self.status_message = Some(if self.save_rounding {
+            "Rounding shown at exit will be saved to config".to_string()
+        } else {
+            "Rounding will not be saved to config".to_string()
+        });
+    }
+
     fn toggle_sort_by_date(&mut self) {
         self.sort_by_date = !self.sort_by_date;
         if self.sort_by_date {
@@ -625,6 +1298,11 @@ impl App {
             }
         }
 
+        let ids: Vec<i64> = entries_to_update.iter().map(|(_, id)| *id).collect();
+        if !self.confirm_no_conflicts(&ids) {
+            return;
+        }
+
         self.show_edit_modal = false;
         self.edit_input.clear();
         self.edit_cursor = 0;
@@ -683,6 +1361,7 @@ impl App {
                             self.all_entries.iter_mut().find(|e| e.id == *entry_id)
                         {
                             all_entry.description = Some(new_description.clone());
+                            all_entry.synced_at = Some(Utc::now());
                         }
 
                         if let Err(e) =
@@ -753,6 +1432,7 @@ impl App {
         }
 
         if !error_occurred {
+            self.partial_failure = fail_count > 0;
             if fail_count == 0 {
                 self.status_message = Some(format!(
                     "Successfully updated description for {} entr{}",
@@ -776,22 +1456,384 @@ impl App {
         }
     }
 
-    fn toggle_billable_filter(&mut self) {
-        self.active_filter.billable_only = !self.active_filter.billable_only;
-        self.apply_filters();
+    fn selected_entry_template(&self) -> Option<(Option<String>, Option<i64>)> {
+        let selected = self.list_state.selected()?;
+        if self.show_grouped {
+            self.grouped_entries
+                .get(selected)
+                .map(|g| (g.description.clone(), g.project_id))
+        } else {
+            self.time_entries
+                .get(selected)
+                .map(|e| (e.description.clone(), e.project_id))
+        }
     }
 
-    fn clear_filters(&mut self) {
-        self.active_filter = TimeEntryFilter::new();
-        self.apply_filters();
+    fn open_start_timer_modal(&mut self) {
+        let (description, project_id) = self.selected_entry_template().unwrap_or((None, None));
+        self.edit_input = description.unwrap_or_default();
+        self.edit_cursor = self.edit_input.chars().count();
+        self.start_timer_project_id = project_id;
+        self.show_start_timer_modal = true;
     }
 
-    fn filter_section_len(&self) -> usize {
-        match self.filter_section {
-            FilterSection::Billable => 0,
-            FilterSection::Projects => self.filtered_projects.len(),
-            FilterSection::Tags => self.available_tags.len(),
-        }
+    fn close_start_timer_modal(&mut self) {
+        self.show_start_timer_modal = false;
+        self.edit_input.clear();
+        self.edit_cursor = 0;
+        self.start_timer_project_id = None;
+    }
+
+    fn toggle_start_timer_project(&mut self) {
+        if self.start_timer_project_id.is_some() {
+            self.start_timer_project_id = None;
+        } else if let Some((_, project_id)) = self.selected_entry_template() {
+            self.start_timer_project_id = project_id;
+        }
+    }
+
+    fn start_timer(&mut self) {
+        let description = match self.edit_input.trim() {
+            "" => None,
+            desc => Some(desc.to_string()),
+        };
+        let project_id = self.start_timer_project_id;
+        self.close_start_timer_modal();
+        self.start_timer_with(description, project_id);
+    }
+
+    fn apply_selected_date_preset(&mut self) {
+        self.show_date_presets = false;
+        let Some(preset) = self
+            .date_presets_state
+            .selected()
+            .and_then(|i| DatePreset::ALL.get(i))
+            .copied()
+        else {
+            return;
+        };
+
+        let (start_date, end_date) = preset.range(Local::now());
+        match self
+            .db
+            .get_time_entries(start_date, end_date, self.current_user_id)
+        {
+            Ok(entries) => {
+                self.start_date = start_date;
+                self.end_date = end_date;
+                self.replace_entries(entries);
+                self.status_message = Some(format!(
+                    "{}: {} entries loaded",
+                    preset.label(),
+                    self.all_entries.len()
+                ));
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load entries: {}", e));
+            }
+        }
+    }
+
+    /// Swaps in a freshly loaded set of entries and rebuilds the filter choices and views.
+    fn replace_entries(&mut self, entries: Vec<TimeEntry>) {
+        self.available_tags = entry_tags(&entries);
+        self.available_workspaces = entry_workspaces(&entries);
+        self.filter_tags_state
+            .select((!self.available_tags.is_empty()).then_some(0));
+        self.filter_workspaces_state
+            .select((!self.available_workspaces.is_empty()).then_some(0));
+        self.all_entries = entries;
+        self.apply_filters();
+    }
+
+    /// Re-fetches the current date range from the API into the local cache, then reloads the
+    /// entries from the cache. Without an API client, or if the fetch fails, only the cache is
+    /// reloaded.
+    fn refresh_entries(&mut self) {
+        let (start_date, end_date) = (self.start_date, self.end_date);
+
+        let fetched = match (self.client.clone(), self.runtime_handle.clone()) {
+            (Some(client), Some(handle)) => {
+                let (tx, rx) = std::sync::mpsc::channel();
+                let db = self.db.clone();
+                handle.spawn(async move {
+                    let result = crate::sync::fetch_and_cache_entries(
+                        client.as_ref(),
+                        &db,
+                        start_date,
+                        end_date,
+                    )
+                    .await;
+                    let _ = tx.send(result);
+                });
+
+                match rx.recv_timeout(std::time::Duration::from_secs(30)) {
+                    Ok(Ok(entries)) => Some(entries.len()),
+                    Ok(Err(e)) => {
+                        tracing::warn!("Refresh from the API failed, reloading the cache: {}", e);
+                        None
+                    }
+                    Err(_) => {
+                        tracing::warn!("Refresh from the API timed out, reloading the cache");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        match self
+            .db
+            .get_time_entries(start_date, end_date, self.current_user_id)
+        {
+            Ok(entries) => {
+                self.replace_entries(entries);
+                self.status_message = Some(match fetched {
+                    Some(count) => format!("Refreshed {} entries", count),
+                    None => format!(
+                        "Offline: reloaded {} entries from the local cache",
+                        self.all_entries.len()
+                    ),
+                });
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load entries: {}", e));
+            }
+        }
+    }
+
+    fn open_favorites_picker(&mut self) {
+        if self.favorites.is_empty() {
+            self.status_message = Some(
+                "No favorites yet. Add one with: toggl-timeguru config --add-favorite \"...\""
+                    .to_string(),
+            );
+            return;
+        }
+        if self.favorites_state.selected().is_none() {
+            self.favorites_state.select(Some(0));
+        }
+        self.show_favorites_picker = true;
+    }
+
+    fn next_favorite(&mut self) {
+        let len = self.favorites.len();
+        if len == 0 {
+            return;
+        }
+        let i = self
+            .favorites_state
+            .selected()
+            .map(|i| (i + 1) % len)
+            .unwrap_or(0);
+        self.favorites_state.select(Some(i));
+    }
+
+    fn previous_favorite(&mut self) {
+        let len = self.favorites.len();
+        if len == 0 {
+            return;
+        }
+        let i = self
+            .favorites_state
+            .selected()
+            .map(|i| if i == 0 { len - 1 } else { i - 1 })
+            .unwrap_or(0);
+        self.favorites_state.select(Some(i));
+    }
+
+    fn start_favorite_timer(&mut self) {
+        self.show_favorites_picker = false;
+        if let Some(favorite) = self
+            .favorites_state
+            .selected()
+            .and_then(|i| self.favorites.get(i))
+            .cloned()
+        {
+            self.start_timer_with(Some(favorite.description), favorite.project_id);
+        }
+    }
+
+    fn start_timer_with(&mut self, description: Option<String>, project_id: Option<i64>) {
+        let client = match &self.client {
+            Some(c) => c.clone(),
+            None => {
+                self.error_message = Some("API client not available".to_string());
+                return;
+            }
+        };
+
+        let handle = match &self.runtime_handle {
+            Some(h) => h.clone(),
+            None => {
+                self.error_message = Some("Runtime handle not available".to_string());
+                return;
+            }
+        };
+
+        let workspace_id = match project_id
+            .and_then(|pid| self.projects.get(&pid))
+            .map(|p| p.workspace_id)
+            .or(self.default_workspace_id)
+            .or_else(|| self.all_entries.first().map(|e| e.workspace_id))
+        {
+            Some(id) => id,
+            None => {
+                self.error_message = Some("No workspace available to start a timer".to_string());
+                return;
+            }
+        };
+
+        tracing::info!(
+            "Starting timer from TUI: workspace={}, project={:?}",
+            workspace_id,
+            project_id
+        );
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        handle.spawn(async move {
+            let result = client
+                .start_time_entry(workspace_id, description, project_id)
+                .await;
+            let _ = tx.send(result);
+        });
+
+        match rx.recv_timeout(std::time::Duration::from_secs(10)) {
+            Ok(Ok(entry)) => {
+                self.status_message = Some(format!(
+                    "Timer started: {}",
+                    entry
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| "(No description)".to_string())
+                ));
+                self.running_entry = Some(entry);
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to start timer: {}", e);
+                self.error_message = Some(format!("Failed to start timer: {}", e));
+            }
+            Err(e) => {
+                tracing::error!("Error waiting for start timer result: {}", e);
+                self.error_message = Some(format!("Error communicating with API task: {}", e));
+            }
+        }
+    }
+
+    fn stop_timer(&mut self) {
+        let client = match &self.client {
+            Some(c) => c.clone(),
+            None => {
+                self.error_message = Some("API client not available".to_string());
+                return;
+            }
+        };
+
+        let handle = match &self.runtime_handle {
+            Some(h) => h.clone(),
+            None => {
+                self.error_message = Some("Runtime handle not available".to_string());
+                return;
+            }
+        };
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        handle.spawn(async move {
+            let _ = tx.send(client.stop_current_time_entry().await);
+        });
+
+        match rx.recv_timeout(std::time::Duration::from_secs(10)) {
+            Ok(Ok(Some(stopped))) => {
+                self.running_entry = None;
+                if let Err(e) = self.db.save_time_entries(std::slice::from_ref(&stopped)) {
+                    tracing::error!("Failed to save stopped entry {}: {}", stopped.id, e);
+                }
+                self.status_message = Some(format!(
+                    "Timer stopped: {}",
+                    format_hours(stopped.duration, self.duration_format)
+                ));
+                if stopped.start >= self.start_date && stopped.start <= self.end_date {
+                    self.all_entries.retain(|e| e.id != stopped.id);
+                    self.all_entries.insert(0, stopped);
+                    self.apply_filters();
+                }
+            }
+            Ok(Ok(None)) => {
+                self.running_entry = None;
+                self.status_message = Some("No time entry is currently running".to_string());
+            }
+            Ok(Err(e)) => {
+                tracing::error!("Failed to stop timer: {}", e);
+                self.error_message = Some(format!("Failed to stop timer: {}", e));
+            }
+            Err(e) => {
+                tracing::error!("Error waiting for stop timer result: {}", e);
+                self.error_message = Some(format!("Error communicating with API task: {}", e));
+            }
+        }
+    }
+
+    fn toggle_billable_filter(&mut self) {
+        self.active_filter.billable_only = !self.active_filter.billable_only;
+        self.apply_filters();
+    }
+
+    fn toggle_running_filter(&mut self) {
+        self.active_filter.running_only = !self.active_filter.running_only;
+        self.apply_filters();
+        if self.active_filter.running_only {
+            self.status_message = Some(match self.time_entries.len() {
+                0 => "No running entries in this range".to_string(),
+                1 => "Showing the running entry".to_string(),
+                n => format!("{} timers are running at once", n),
+            });
+        }
+    }
+
+    fn toggle_selected_project_filter(&mut self) {
+        let Some(selected) = self.list_state.selected() else {
+            return;
+        };
+        let project_id = if self.show_grouped {
+            self.grouped_entries.get(selected).map(|g| g.project_id)
+        } else {
+            self.time_entries.get(selected).map(|e| e.project_id)
+        };
+        let Some(project_id) = project_id else {
+            return;
+        };
+        let Some(project_id) = project_id else {
+            self.status_message = Some("Entry has no project".to_string());
+            return;
+        };
+
+        self.active_filter.project_ids =
+            quick_project_filter(&self.active_filter.project_ids, project_id);
+        self.status_message = Some(if self.active_filter.project_ids.is_empty() {
+            "Project filter cleared".to_string()
+        } else {
+            let name = self
+                .projects
+                .get(&project_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| format!("Project {}", project_id));
+            format!("Filtered to {} (press P again to clear)", name)
+        });
+        self.apply_filters();
+    }
+
+    fn clear_filters(&mut self) {
+        self.active_filter = TimeEntryFilter::new();
+        self.apply_filters();
+    }
+
+    fn filter_section_len(&self) -> usize {
+        match self.filter_section {
+            FilterSection::Billable => 0,
+            FilterSection::Projects => self.filtered_projects.len(),
+            FilterSection::Tags => self.available_tags.len(),
+            FilterSection::Workspaces => self.available_workspaces.len(),
+        }
     }
 
     fn filter_section_state(&mut self) -> Option<&mut ListState> {
@@ -799,6 +1841,7 @@ impl App {
             FilterSection::Billable => None,
             FilterSection::Projects => Some(&mut self.filter_projects_state),
             FilterSection::Tags => Some(&mut self.filter_tags_state),
+            FilterSection::Workspaces => Some(&mut self.filter_workspaces_state),
         }
     }
 
@@ -857,6 +1900,18 @@ impl App {
                     self.apply_filters();
                 }
             }
+            FilterSection::Workspaces => {
+                if let Some(idx) = self.filter_workspaces_state.selected()
+                    && let Some(&wid) = self.available_workspaces.get(idx)
+                {
+                    if self.active_filter.workspace_ids.contains(&wid) {
+                        self.active_filter.workspace_ids.remove(&wid);
+                    } else {
+                        self.active_filter.workspace_ids.insert(wid);
+                    }
+                    self.apply_filters();
+                }
+            }
         }
     }
 
@@ -927,6 +1982,19 @@ impl App {
         self.list_state.select(Some(i));
     }
 
+    fn jump_day(&mut self, forward: bool) {
+        if !(self.show_grouped && self.group_by_day) {
+            self.status_message = Some("Day jumping requires day grouping (press d)".to_string());
+            return;
+        }
+
+        let days: Vec<_> = self.grouped_entries.iter().map(|g| g.date).collect();
+        let current = self.list_state.selected().unwrap_or(0);
+        if let Some(target) = day_jump_target(&days, current, forward) {
+            self.list_state.select(Some(target));
+        }
+    }
+
     fn goto_first(&mut self) {
         let len = if self.show_grouped {
             self.grouped_entries.len()
@@ -967,23 +2035,122 @@ impl App {
         };
 
         if let Some(desc) = description {
-            match Clipboard::new() {
-                Ok(mut clipboard) => {
-                    if clipboard.set_text(&desc).is_ok() {
-                        self.clipboard_message = Some(format!("Copied: {}", desc));
-                    } else {
-                        self.clipboard_message = Some("Failed to copy to clipboard".to_string());
-                    }
-                }
-                Err(_) => {
-                    self.clipboard_message = Some("Clipboard unavailable".to_string());
-                }
-            }
+            let message = format!("Copied: {}", desc);
+            self.set_clipboard_text(&desc, message);
         } else {
             self.clipboard_message = Some("No description to copy".to_string());
         }
     }
 
+    /// Copies through the system clipboard, or through the terminal (OSC 52) in SSH and
+    /// display-less sessions or when the system clipboard fails. If even that cannot be written,
+    /// the text is saved to a temp file instead.
+    fn set_clipboard_text(&mut self, text: &str, success_message: String) {
+        self.clipboard_message = Some(match copy_text(text) {
+            CopyOutcome::Clipboard => success_message,
+            CopyOutcome::Terminal => format!("{} (via terminal)", success_message),
+            CopyOutcome::File(path) => {
+                format!("Clipboard unavailable; saved to {}", path.display())
+            }
+            CopyOutcome::Failed => "Clipboard unavailable".to_string(),
+        });
+    }
+
+    fn project_name_for(&self, project_id: Option<i64>) -> String {
+        project_id
+            .and_then(|pid| self.projects.get(&pid))
+            .map(|p| p.name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Fields for the entry or group at `index` in the visible list: date (day-grouped only) or
+    /// start, hours, project, description and, for groups, the entry count. Durations are the
+    /// ones the row shows: rounded per the toggle, and elapsed time up to `now` when running.
+    fn row_fields(&self, index: usize, now: DateTime<Utc>) -> Option<Vec<String>> {
+        let round_to = self.display_rounding();
+
+        if self.show_grouped {
+            let entry = self.grouped_entries.get(index)?;
+            let seconds = group_display_seconds(entry, round_to, now);
+            let mut fields = Vec::new();
+            if self.group_by_day
+                && let Some(date) = entry.date
+            {
+                fields.push(date.format("%Y-%m-%d").to_string());
+            }
+            fields.push(format_hours_cell(seconds, self.duration_format));
+            fields.push(self.project_name_for(entry.project_id));
+            fields.push(entry.description.clone().unwrap_or_default());
+            fields.push(entry.entries.len().to_string());
+            Some(fields)
+        } else {
+            let entry = self.time_entries.get(index)?;
+            let seconds = entry_display_seconds(entry, round_to, now);
+            Some(vec![
+                entry.start.format("%Y-%m-%d %H:%M").to_string(),
+                format_hours_cell(seconds, self.duration_format),
+                self.project_name_for(entry.project_id),
+                entry.description.clone().unwrap_or_default(),
+            ])
+        }
+    }
+
+    /// Builds a tab-separated row for the selected entry or group so it pastes cleanly into a
+    /// spreadsheet.
+    fn selected_entry_as_row(&self) -> Option<String> {
+        let selected = self.list_state.selected()?;
+        self.row_fields(selected, Utc::now())
+            .map(|fields| fields.join("\t"))
+    }
+
+    /// Renders every visible entry or group as a Markdown table, with the same columns as the
+    /// tab-separated row copy.
+    fn visible_as_markdown(&self) -> (String, usize) {
+        let (mut headers, len) = if self.show_grouped {
+            (
+                vec!["Hours", "Project", "Description", "Entries"],
+                self.grouped_entries.len(),
+            )
+        } else {
+            (
+                vec!["Start", "Hours", "Project", "Description"],
+                self.time_entries.len(),
+            )
+        };
+        if self.show_grouped && self.group_by_day {
+            headers.insert(0, "Date");
+        }
+        let now = Utc::now();
+        let rows: Vec<Vec<String>> = (0..len).filter_map(|i| self.row_fields(i, now)).collect();
+        (markdown_table(&headers, &rows), rows.len())
+    }
+
+    fn copy_visible_as_markdown(&mut self) {
+        let (table, rows) = self.visible_as_markdown();
+        if rows == 0 {
+            self.clipboard_message = Some("Nothing to copy".to_string());
+            return;
+        }
+        let message = format!(
+            "Copied {} row{} as a Markdown table",
+            rows,
+            if rows == 1 { "" } else { "s" }
+        );
+        self.set_clipboard_text(&table, message);
+    }
+
+    fn copy_formatted_to_clipboard(&mut self) {
+        match self.selected_entry_as_row() {
+            Some(row) => {
+                let message = format!("Copied row: {}", row.replace('\t', " | "));
+                self.set_clipboard_text(&row, message);
+            }
+            None => {
+                self.clipboard_message = Some("No entry selected to copy".to_string());
+            }
+        }
+    }
+
     fn next_project(&mut self) {
         let len = self.filtered_projects.len();
         if len == 0 {
@@ -1105,8 +2272,8 @@ impl App {
             return;
         }
 
-        let all_projects: Vec<_> = self.projects.values().cloned().collect();
-        self.filtered_projects = all_projects
+        self.filtered_projects = self
+            .selector_projects()
             .into_iter()
             .filter(|p| p.name.to_lowercase().contains(&query))
             .collect();
@@ -1124,13 +2291,55 @@ impl App {
         }
     }
 
+    /// Projects offered by the selector: active ones, plus archived ones when toggled on.
+    fn selector_projects(&self) -> Vec<Project> {
+        let mut projects: Vec<Project> = self
+            .projects
+            .values()
+            .filter(|p| p.active || self.show_archived_projects)
+            .cloned()
+            .collect();
+        if self.show_archived_projects {
+            projects.extend(
+                self.archived_projects
+                    .iter()
+                    .filter(|p| !self.projects.contains_key(&p.id))
+                    .cloned(),
+            );
+        }
+        projects
+    }
+
+    fn toggle_archived_projects(&mut self) {
+        if !self.show_archived_projects {
+            match self.db.get_all_projects(self.current_user_id) {
+                Ok(all) => {
+                    self.archived_projects = all.into_iter().filter(|p| !p.active).collect();
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load archived projects: {}", e);
+                    self.status_message = Some(format!("Failed to load archived projects: {}", e));
+                    return;
+                }
+            }
+        }
+
+        self.show_archived_projects = !self.show_archived_projects;
+        self.status_message = Some(if self.show_archived_projects {
+            format!(
+                "Showing {} archived project(s)",
+                self.archived_projects.len()
+            )
+        } else {
+            "Hiding archived projects".to_string()
+        });
+        self.filter_projects();
+    }
+
     fn reset_filtered_projects(&mut self) {
-        self.filtered_projects = self.projects.values().cloned().collect();
-        sort_projects(
-            &mut self.filtered_projects,
-            self.project_sort_method,
-            &self.project_usage,
-        );
+        let mut projects = self.selector_projects();
+        sort_projects(&mut projects, self.project_sort_method, &self.project_usage);
+        self.filtered_projects = pin_recent(projects, &self.recent_project_ids, |p| p.id);
 
         if !self.filtered_projects.is_empty() {
             self.project_selector_state.select(Some(0));
@@ -1164,13 +2373,65 @@ impl App {
         }
     }
 
-    fn assign_project_to_entry(&mut self) {
-        tracing::info!("assign_project_to_entry called");
+    /// With `check_conflicts` on, re-fetches the entries about to be overwritten. If the server
+    /// changed any of them since the last sync, warns and returns false; pressing Enter again
+    /// overwrites anyway. A failed check is logged and does not block the edit.
+    fn confirm_no_conflicts(&mut self, entry_ids: &[i64]) -> bool {
+        if !self.check_conflicts || std::mem::take(&mut self.conflict_acknowledged) {
+            return true;
+        }
 
-        let selected_project_idx = match self.project_selector_state.selected() {
-            Some(idx) => {
-                tracing::debug!("Selected project index: {}", idx);
-                idx
+        let (Some(client), Some(handle)) = (self.client.clone(), self.runtime_handle.clone())
+        else {
+            return true;
+        };
+
+        let local: Vec<TimeEntry> = self
+            .all_entries
+            .iter()
+            .filter(|e| entry_ids.contains(&e.id))
+            .cloned()
+            .collect();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        handle.spawn(async move {
+            let result = crate::sync::find_server_conflicts(client.as_ref(), &local).await;
+            let _ = tx.send(result);
+        });
+
+        match rx.recv_timeout(std::time::Duration::from_secs(10)) {
+            Ok(Ok(conflicts)) if conflicts.is_empty() => true,
+            Ok(Ok(conflicts)) => {
+                tracing::warn!(
+                    "Entries changed on the server since last sync: {:?}",
+                    conflicts
+                );
+                self.conflict_acknowledged = true;
+                self.status_message = Some(format!(
+                    "{} of {} entries changed on the server since the last sync. Press Enter again to overwrite, Esc to cancel",
+                    conflicts.len(),
+                    entry_ids.len()
+                ));
+                false
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Conflict check failed, continuing with the edit: {}", e);
+                true
+            }
+            Err(_) => {
+                tracing::warn!("Conflict check timed out, continuing with the edit");
+                true
+            }
+        }
+    }
+
+    fn assign_project_to_entry(&mut self) {
+        tracing::info!("assign_project_to_entry called");
+
+        let selected_project_idx = match self.project_selector_state.selected() {
+            Some(idx) => {
+                tracing::debug!("Selected project index: {}", idx);
+                idx
             }
             None => {
                 tracing::warn!("No project selected");
@@ -1193,6 +2454,8 @@ impl App {
 
         let project_id = selected_project.id;
         let project_name = selected_project.name.clone();
+        // Archived projects aren't in the loaded project map; add on success so rows show the name.
+        let archived_project = (!selected_project.active).then(|| selected_project.clone());
 
         let selected_entry_idx = match self.list_state.selected() {
             Some(idx) => {
@@ -1271,6 +2534,10 @@ impl App {
             let entry_ids: Vec<i64> = grouped_entry.entries.iter().map(|e| e.id).collect();
             let workspace_id = grouped_entry.entries[0].workspace_id;
 
+            if !self.confirm_no_conflicts(&entry_ids) {
+                return;
+            }
+
             tracing::info!(
                 "Using bulk API to assign project {} to {} entries in workspace {}",
                 project_id,
@@ -1324,6 +2591,7 @@ impl App {
                                 self.all_entries.iter_mut().find(|e| e.id == *entry_id)
                             {
                                 all_entry.project_id = Some(project_id);
+                                all_entry.synced_at = Some(Utc::now());
                             }
 
                             if let Some((start, old_pid)) = prior {
@@ -1404,6 +2672,7 @@ impl App {
                 total_entries
             );
 
+            self.partial_failure = fail_count > 0;
             if fail_count == 0 {
                 self.status_message = Some(format!(
                     "Assigned {} to {} entries",
@@ -1416,6 +2685,12 @@ impl App {
                 ));
             }
 
+            if success_count > 0 {
+                remember_recent_project(&mut self.recent_project_ids, project_id);
+                if let Some(project) = archived_project.clone() {
+                    self.projects.entry(project.id).or_insert(project);
+                }
+            }
             self.recompute_grouped_entries();
             self.show_project_selector = false;
             self.project_search_query.clear();
@@ -1442,6 +2717,10 @@ impl App {
             let entry_id = entry.id;
             let workspace_id = entry.workspace_id;
 
+            if !self.confirm_no_conflicts(&[entry_id]) {
+                return;
+            }
+
             tracing::debug!("Spawning async task for single entry {}", entry_id);
 
             let (tx, rx) = std::sync::mpsc::channel();
@@ -1471,11 +2750,16 @@ impl App {
                     if let Some(all_entry) = self.all_entries.iter_mut().find(|e| e.id == entry_id)
                     {
                         all_entry.project_id = Some(project_id);
+                        all_entry.synced_at = Some(Utc::now());
                     }
 
                     if let Some((start, old_pid)) = prior {
                         self.adjust_usage_for_reassign(start, old_pid, Some(project_id));
                     }
+                    remember_recent_project(&mut self.recent_project_ids, project_id);
+                    if let Some(project) = archived_project.clone() {
+                        self.projects.entry(project.id).or_insert(project);
+                    }
 
                     if let Err(e) = self
                         .db
@@ -1486,6 +2770,7 @@ impl App {
                             entry_id,
                             e
                         );
+                        self.partial_failure = true;
                         self.status_message = Some(format!(
                             "Assigned project: {}, but failed to save to database: {}",
                             project_name, e
@@ -1495,6 +2780,7 @@ impl App {
                             "Successfully updated project in database for entry {}",
                             entry_id
                         );
+                        self.partial_failure = false;
                         self.status_message = Some(format!("Assigned project: {}", project_name));
                     }
 
@@ -1567,6 +2853,22 @@ impl App {
         if self.show_edit_modal {
             self.render_edit_modal(f);
         }
+
+        if self.show_start_timer_modal {
+            self.render_start_timer_modal(f);
+        }
+
+        if self.show_favorites_picker {
+            self.render_favorites_picker(f);
+        }
+
+        if self.show_date_presets {
+            self.render_date_presets(f);
+        }
+
+        if self.show_quit_confirm {
+            self.render_quit_confirm(f);
+        }
     }
 
     fn render_header(&self, f: &mut Frame, area: Rect) {
@@ -1585,13 +2887,31 @@ impl App {
             )
         };
 
-        let header = Paragraph::new(title)
-            .style(Style::default().fg(Color::Cyan))
-            .block(Block::default().borders(Borders::ALL));
+        let mut spans = vec![Span::styled(title, Style::default().fg(Color::Cyan))];
+        if let Some(ref running) = self.running_entry {
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!(
+                    "● Running: {} (since {}, {})",
+                    running.description.as_deref().unwrap_or("(No description)"),
+                    running.start.with_timezone(&chrono::Local).format("%H:%M"),
+                    format_compact_duration(
+                        (Utc::now() - running.start).num_seconds().max(0) as u32
+                    )
+                ),
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        let header =
+            Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
 
         f.render_widget(header, area);
     }
 
+    /// Parses a `#rrggbb` project color; empty or malformed colors fall back to gray.
     fn parse_color(hex: &str) -> Color {
         let hex = hex.trim_start_matches('#');
         if hex.len() == 6
@@ -1603,10 +2923,32 @@ impl App {
         {
             return Color::Rgb(r, g, b);
         }
-        Color::White
+        Color::Gray
+    }
+
+    /// Rounding period applied to displayed durations, if rounding is toggled on.
+    pub fn display_rounding(&self) -> Option<i64> {
+        self.round_minutes.filter(|_| self.show_rounded)
+    }
+
+    /// Sum of the durations shown on the visible rows, so the footer matches the list.
+    fn visible_total_seconds(&self, now: DateTime<Utc>) -> i64 {
+        let round = self.display_rounding();
+        if self.show_grouped {
+            self.grouped_entries
+                .iter()
+                .map(|g| group_display_seconds(g, round, now))
+                .sum()
+        } else {
+            self.time_entries
+                .iter()
+                .map(|e| entry_display_seconds(e, round, now))
+                .sum()
+        }
     }
 
     fn render_list(&mut self, f: &mut Frame, area: Rect) {
+        let now = Utc::now();
         let items: Vec<ListItem> = if self.show_grouped {
             self.grouped_entries
                 .iter()
@@ -1615,13 +2957,7 @@ impl App {
                         .description
                         .clone()
                         .unwrap_or_else(|| "(No description)".to_string());
-                    let hours = if let Some(round_to_minutes) = self.round_minutes
-                        && self.show_rounded
-                    {
-                        entry.rounded_hours(round_to_minutes)
-                    } else {
-                        entry.total_hours()
-                    };
+                    let seconds = group_display_seconds(entry, self.display_rounding(), now);
 
                     let mut spans = vec![];
 
@@ -1636,7 +2972,7 @@ impl App {
                     }
 
                     spans.push(Span::styled(
-                        format!("{:.2}h", hours),
+                        format_hours(seconds, self.duration_format),
                         Style::default()
                             .fg(Color::Green)
                             .add_modifier(Modifier::BOLD),
@@ -1672,17 +3008,8 @@ impl App {
                         .clone()
                         .unwrap_or_else(|| "(No description)".to_string());
 
-                    let duration_hours = if let Some(round_to_minutes) = self.round_minutes
-                        && self.show_rounded
-                    {
-                        let seconds_per_round = round_to_minutes * 60;
-                        let rounded_duration = ((entry.duration as f64 / seconds_per_round as f64)
-                            .ceil() as i64)
-                            * seconds_per_round;
-                        rounded_duration as f64 / 3600.0
-                    } else {
-                        entry.duration as f64 / 3600.0
-                    };
+                    let running = is_running(entry);
+                    let seconds = entry_display_seconds(entry, self.display_rounding(), now);
 
                     let mut spans = vec![
                         Span::styled(
@@ -1691,7 +3018,7 @@ impl App {
                         ),
                         Span::raw(" - "),
                         Span::styled(
-                            format!("{:.2}h", duration_hours),
+                            format_hours(seconds, self.duration_format),
                             Style::default().fg(Color::Green),
                         ),
                         Span::raw(" - "),
@@ -1707,6 +3034,13 @@ impl App {
                         ));
                     }
 
+                    if running {
+                        spans.push(Span::styled(
+                            "▶ running ",
+                            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                        ));
+                    }
+
                     spans.push(Span::raw(desc));
 
                     let content = Line::from(spans);
@@ -1715,7 +3049,9 @@ impl App {
                 .collect()
         };
 
-        let title = if self.show_grouped {
+        let title = if self.is_client_grouped() {
+            "Time Entries (Grouped by Client)"
+        } else if self.show_grouped {
             "Time Entries (Grouped)"
         } else {
             "Time Entries"
@@ -1752,6 +3088,7 @@ impl App {
             FilterSection::Billable,
             FilterSection::Projects,
             FilterSection::Tags,
+            FilterSection::Workspaces,
         ]
         .iter()
         .enumerate()
@@ -1779,6 +3116,13 @@ impl App {
                         " ●"
                     }
                 }
+                FilterSection::Workspaces => {
+                    if self.active_filter.workspace_ids.is_empty() {
+                        ""
+                    } else {
+                        " ●"
+                    }
+                }
             };
             let label = format!("[{}{}]", section.label(), count_hint);
             let style = if active {
@@ -1790,7 +3134,7 @@ impl App {
                 Style::default().fg(Color::Gray)
             };
             let mut v = vec![Span::styled(label, style)];
-            if i < 2 {
+            if i < 3 {
                 v.push(Span::raw(" "));
             }
             v
@@ -1889,6 +3233,32 @@ impl App {
                     f.render_stateful_widget(list, rows[1], &mut self.filter_tags_state);
                 }
             }
+            FilterSection::Workspaces => {
+                let items: Vec<ListItem> = self
+                    .available_workspaces
+                    .iter()
+                    .map(|wid| {
+                        let selected = self.active_filter.workspace_ids.contains(wid);
+                        let mark = if selected { "[x]" } else { "[ ]" };
+                        ListItem::new(Line::from(vec![
+                            Span::raw(mark),
+                            Span::raw(" "),
+                            Span::styled(
+                                format!("Workspace {}", wid),
+                                Style::default().fg(Color::Cyan),
+                            ),
+                        ]))
+                    })
+                    .collect();
+                let list = List::new(items)
+                    .highlight_style(
+                        Style::default()
+                            .bg(Color::DarkGray)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                    .highlight_symbol("> ");
+                f.render_stateful_widget(list, rows[1], &mut self.filter_workspaces_state);
+            }
         }
     }
 
@@ -1903,7 +3273,13 @@ impl App {
             .iter()
             .map(|project| {
                 let color = Self::parse_color(&project.color);
-                let mut spans = vec![
+                let mut spans = Vec::new();
+                if self.project_search_query.is_empty()
+                    && self.recent_project_ids.contains(&project.id)
+                {
+                    spans.push(Span::styled("★ ", Style::default().fg(Color::Yellow)));
+                }
+                spans.extend([
                     Span::styled(
                         format!("[{}]", project.name),
                         Style::default().fg(color).add_modifier(Modifier::BOLD),
@@ -1917,7 +3293,7 @@ impl App {
                             Style::default().fg(Color::DarkGray)
                         },
                     ),
-                ];
+                ]);
 
                 let count = self.project_usage.get(&project.id).copied().unwrap_or(0);
                 if count > 0 {
@@ -1959,7 +3335,7 @@ impl App {
         let mut help_spans = vec![
             Span::styled("Controls: ", Style::default().fg(Color::Yellow)),
             Span::raw(
-                "↑↓/jk: Navigate  │  0-9/a-z: Jump  │  /: Search  │  Enter: Select  │  p/Esc: Cancel",
+                "↑↓/jk: Navigate  │  0-9/A-Z: Jump  │  /: Search  │  a: Archived  │  Enter: Select  │  p/Esc: Cancel",
             ),
         ];
 
@@ -1989,7 +3365,7 @@ impl App {
         match info.resets_in {
             Some(resets_in) => Some(format!(
                 "{remaining} req left, resets in {}",
-                format_rate_limit_reset_duration(resets_in)
+                format_compact_duration(resets_in)
             )),
             None => Some(format!("{remaining} req left")),
         }
@@ -1998,15 +3374,29 @@ impl App {
     fn render_footer(&self, f: &mut Frame, area: Rect) {
         let grouping_status = if self.show_grouped { "ON" } else { "OFF" };
         let day_grouping_status = if self.group_by_day { "ON" } else { "OFF" };
+        let client_grouping_status = if self.group_by_client { "ON" } else { "OFF" };
         let sort_status = if self.sort_by_date { "ON" } else { "OFF" };
-        let rounding_status = if self.show_rounded { "ON" } else { "OFF" };
+        let rounding_status = rounding_label(self.display_rounding());
         let rate_limit_indicator = self.rate_limit_footer_text();
         let filter_indicator = if self.active_filter.is_active() {
             let mut parts: Vec<String> = Vec::new();
             if self.active_filter.billable_only {
                 parts.push("billable".to_string());
             }
-            if !self.active_filter.project_ids.is_empty() {
+            if self.active_filter.running_only {
+                parts.push("running".to_string());
+            }
+            if self.active_filter.project_ids.len() == 1 {
+                let name = self
+                    .active_filter
+                    .project_ids
+                    .iter()
+                    .next()
+                    .and_then(|pid| self.projects.get(pid))
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| "1 project".to_string());
+                parts.push(name);
+            } else if !self.active_filter.project_ids.is_empty() {
                 parts.push(format!(
                     "{} project(s)",
                     self.active_filter.project_ids.len()
@@ -2040,22 +3430,38 @@ impl App {
                 Span::styled("Toggles: ", Style::default().fg(Color::Yellow)),
                 Span::raw(format!("g:Group({}) ", grouping_status)),
                 Span::raw(format!("d:Day({}) ", day_grouping_status)),
+                Span::raw(format!("C:Client({}) ", client_grouping_status)),
                 Span::raw(format!("s:Sort({}) ", sort_status)),
-                Span::raw(format!("r:Round({}) ", rounding_status)),
+                Span::raw(format!(
+                    "r:Round({}{}) ",
+                    rounding_status,
+                    if self.save_rounding { "*" } else { "" }
+                )),
+                Span::raw("i:Interval "),
                 Span::raw("f:Filter "),
+                Span::raw("P:ProjectFilter "),
+                Span::raw("o:Running "),
                 Span::raw("c:ClearFilters "),
                 Span::styled("│ ", Style::default().fg(Color::DarkGray)),
                 Span::raw("p:Project "),
                 Span::styled("│ ", Style::default().fg(Color::DarkGray)),
-                Span::raw("y:Copy "),
+                Span::raw("y/Y/M:Copy "),
                 Span::styled("│ ", Style::default().fg(Color::DarkGray)),
                 Span::raw("e:Edit "),
                 Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+                Span::raw("t/T:Start/Stop "),
+                Span::styled("│ ", Style::default().fg(Color::DarkGray)),
+                Span::raw("R:Range u:Refresh "),
+                Span::styled("│ ", Style::default().fg(Color::DarkGray)),
                 Span::raw("q/Esc:Quit"),
             ]),
             Line::from(vec![
                 Span::styled("Status: ", Style::default().fg(Color::Cyan)),
                 Span::raw(format!("Entry {}/{}", selected_pos, len)),
+                Span::raw(format!(
+                    " │ Total: {}",
+                    format_hours(self.visible_total_seconds(Utc::now()), self.duration_format)
+                )),
                 Span::styled(
                     filter_indicator.clone(),
                     Style::default()
@@ -2073,6 +3479,21 @@ impl App {
             ]),
         ];
 
+        if let Some(progress) = &self.weekly_progress {
+            let (tracked, remaining) = progress.summary();
+            let remaining_color = if progress.is_met() {
+                Color::Green
+            } else {
+                Color::Yellow
+            };
+            footer_lines[1].spans.extend([
+                Span::styled(" │ ", Style::default().fg(Color::DarkGray)),
+                Span::styled("Week: ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{} target ", tracked)),
+                Span::styled(remaining, Style::default().fg(remaining_color)),
+            ]);
+        }
+
         if let Some(rate_limit) = rate_limit_indicator {
             footer_lines[1]
                 .spans
@@ -2166,6 +3587,54 @@ impl App {
         }
     }
 
+    fn render_quit_confirm(&self, f: &mut Frame) {
+        let Some(prompt) = quit_prompt(self.in_flight, self.partial_failure) else {
+            return;
+        };
+        let area = f.area();
+        let popup_width = area.width.saturating_sub(POPUP_MARGIN).min(POPUP_MAX_WIDTH);
+        let popup_height = 7.min(area.height);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow))
+            .style(Style::default().bg(Color::Black))
+            .title("Quit")
+            .title_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner_area = block.inner(popup_area);
+
+        let mut text = vec![Line::from(Span::styled(
+            prompt,
+            Style::default().fg(Color::White),
+        ))];
+        if let Some(ref status) = self.status_message {
+            text.push(Line::from(""));
+            text.push(Line::from(Span::styled(
+                status.as_str(),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+        f.render_widget(
+            Paragraph::new(text)
+                .wrap(ratatui::widgets::Wrap { trim: true })
+                .style(Style::default().bg(Color::Black)),
+            inner_area,
+        );
+    }
+
     fn edit_char_byte_index(&self, char_index: usize) -> usize {
         self.edit_input
             .char_indices()
@@ -2200,6 +3669,203 @@ impl App {
         self.edit_input.replace_range(start..end, "");
     }
 
+    fn edit_input_line(&self) -> Line<'static> {
+        let text_style = Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD);
+        let cursor_on_char_style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::White)
+            .add_modifier(Modifier::BOLD);
+        let cursor_at_end_style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::White)
+            .add_modifier(Modifier::SLOW_BLINK);
+
+        let chars: Vec<char> = self.edit_input.chars().collect();
+        let cursor_pos = self.edit_cursor.min(chars.len());
+        let before: String = chars[..cursor_pos].iter().collect();
+        if cursor_pos < chars.len() {
+            let cursor_char = chars[cursor_pos].to_string();
+            let after: String = chars[cursor_pos + 1..].iter().collect();
+            Line::from(vec![
+                Span::styled(before, text_style),
+                Span::styled(cursor_char, cursor_on_char_style),
+                Span::styled(after, text_style),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled(before, text_style),
+                Span::styled(" ", cursor_at_end_style),
+            ])
+        }
+    }
+
+    fn render_favorites_picker(&mut self, f: &mut Frame) {
+        let area = f.area();
+        let popup_width = area.width.saturating_sub(POPUP_MARGIN).min(60);
+        let popup_height = (self.favorites.len() as u16 + 4)
+            .min(POPUP_MAX_HEIGHT)
+            .min(area.height);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .style(Style::default().bg(Color::Black))
+            .title("Start Favorite (Enter: Start │ Esc: Cancel)")
+            .title_style(
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let items: Vec<ListItem> = self
+            .favorites
+            .iter()
+            .map(|favorite| {
+                let mut spans = Vec::new();
+                if let Some(project) = favorite.project_id.and_then(|pid| self.projects.get(&pid)) {
+                    spans.push(Span::styled(
+                        format!("[{}] ", project.name),
+                        Style::default()
+                            .fg(Self::parse_color(&project.color))
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                }
+                spans.push(Span::raw(favorite.description.clone()));
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.favorites_state);
+    }
+
+    fn render_date_presets(&mut self, f: &mut Frame) {
+        let area = f.area();
+        let popup_width = area.width.saturating_sub(POPUP_MARGIN).min(40);
+        let popup_height = (DatePreset::ALL.len() as u16 + 2).min(area.height);
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black))
+            .title("Date Range (Enter: Load │ Esc: Cancel)")
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let items: Vec<ListItem> = DatePreset::ALL
+            .iter()
+            .map(|preset| ListItem::new(preset.label()))
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("> ");
+
+        f.render_widget(Clear, popup_area);
+        f.render_stateful_widget(list, popup_area, &mut self.date_presets_state);
+    }
+
+    fn render_start_timer_modal(&self, f: &mut Frame) {
+        let area = f.area();
+        let popup_width = area.width.saturating_sub(POPUP_MARGIN).min(60);
+        let popup_height = 9;
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(popup_width)) / 2,
+            y: (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green))
+            .style(Style::default().bg(Color::Black))
+            .title("Start Timer")
+            .title_style(
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+        let inner_area = block.inner(popup_area);
+
+        let project_line = match self
+            .start_timer_project_id
+            .and_then(|pid| self.projects.get(&pid))
+        {
+            Some(project) => Line::from(vec![
+                Span::styled("Project: ", Style::default().fg(Color::Gray)),
+                Span::styled(
+                    project.name.clone(),
+                    Style::default()
+                        .fg(Self::parse_color(&project.color))
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            None => Line::from(Span::styled(
+                "Project: (none)",
+                Style::default().fg(Color::Gray),
+            )),
+        };
+
+        let text = vec![
+            Line::from(""),
+            self.edit_input_line(),
+            Line::from(""),
+            project_line,
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Enter: Start  │  Tab: Toggle project  │  Esc: Cancel",
+                Style::default()
+                    .fg(Color::Gray)
+                    .add_modifier(Modifier::ITALIC),
+            )]),
+        ];
+
+        f.render_widget(Clear, popup_area);
+        f.render_widget(block, popup_area);
+
+        let paragraph = Paragraph::new(text)
+            .wrap(ratatui::widgets::Wrap { trim: true })
+            .style(Style::default().bg(Color::Black));
+
+        f.render_widget(paragraph, inner_area);
+    }
+
     fn render_edit_modal(&self, f: &mut Frame) {
         if !self.show_edit_modal {
             return;
@@ -2229,35 +3895,7 @@ impl App {
 
         let inner_area = block.inner(popup_area);
 
-        let text_style = Style::default()
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD);
-        let cursor_on_char_style = Style::default()
-            .fg(Color::Black)
-            .bg(Color::White)
-            .add_modifier(Modifier::BOLD);
-        let cursor_at_end_style = Style::default()
-            .fg(Color::Black)
-            .bg(Color::White)
-            .add_modifier(Modifier::SLOW_BLINK);
-
-        let chars: Vec<char> = self.edit_input.chars().collect();
-        let cursor_pos = self.edit_cursor.min(chars.len());
-        let before: String = chars[..cursor_pos].iter().collect();
-        let input_line: Line = if cursor_pos < chars.len() {
-            let cursor_char = chars[cursor_pos].to_string();
-            let after: String = chars[cursor_pos + 1..].iter().collect();
-            Line::from(vec![
-                Span::styled(before, text_style),
-                Span::styled(cursor_char, cursor_on_char_style),
-                Span::styled(after, text_style),
-            ])
-        } else {
-            Line::from(vec![
-                Span::styled(before, text_style),
-                Span::styled(" ", cursor_at_end_style),
-            ])
-        };
+        let input_line = self.edit_input_line();
 
         let text = vec![
             Line::from(""),
@@ -2284,23 +3922,229 @@ impl App {
 
 #[cfg(test)]
 mod tests {
-    use super::format_rate_limit_reset_duration;
+    use super::{
+        DatePreset, day_jump_target, entry_display_seconds, format_compact_duration,
+        group_display_seconds, markdown_table, next_rounding_step, osc52_sequence, pin_recent,
+        quick_project_filter, quit_prompt, remember_recent_project,
+    };
+    use crate::report::{current_week_bounds, local_midnight};
+    use crate::test_support::timed_entry;
+    use crate::toggl::models::GroupedTimeEntry;
+    use chrono::{Datelike, Local, TimeZone, Weekday};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_parse_color_falls_back_to_gray() {
+        use ratatui::style::Color;
+
+        assert_eq!(
+            super::App::parse_color("#06aaf5"),
+            Color::Rgb(0x06, 0xaa, 0xf5)
+        );
+        assert_eq!(super::App::parse_color(""), Color::Gray);
+        assert_eq!(super::App::parse_color("teal"), Color::Gray);
+    }
 
     #[test]
-    fn formats_rate_limit_reset_duration_as_seconds() {
-        assert_eq!(format_rate_limit_reset_duration(0), "0s");
-        assert_eq!(format_rate_limit_reset_duration(42), "42s");
+    fn test_next_rounding_step_cycles_through_intervals() {
+        let mut seen = Vec::new();
+        let mut current = None;
+        for _ in 0..6 {
+            current = next_rounding_step(current);
+            seen.push(current);
+        }
+        assert_eq!(
+            seen,
+            vec![Some(5), Some(10), Some(15), Some(30), Some(60), None]
+        );
+        assert_eq!(next_rounding_step(Some(20)), Some(30));
+        assert_eq!(next_rounding_step(Some(90)), None);
+    }
+
+    #[test]
+    fn date_presets_resolve_to_local_calendar_ranges() {
+        let now = Local.with_ymd_and_hms(2025, 3, 13, 15, 30, 0).unwrap();
+        let local_day = |dt: chrono::DateTime<chrono::Utc>| dt.with_timezone(&Local).date_naive();
+
+        let (start, end) = DatePreset::Yesterday.range(now);
+        assert_eq!(local_day(start).day(), 12);
+        assert_eq!(local_day(end).day(), 13);
+
+        let (start, end) = DatePreset::LastWeek.range(now);
+        assert_eq!(local_day(start).weekday(), Weekday::Mon);
+        assert_eq!(local_day(start).day(), 3);
+        assert_eq!(local_day(end).day(), 10);
+        let monday = chrono::NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+        assert_eq!(start, local_midnight(monday));
+        assert_eq!(end, current_week_bounds(now).0);
+
+        let (start, end) = DatePreset::ThisMonth.range(now);
+        assert_eq!(local_day(start).day(), 1);
+        assert_eq!(local_day(end).month(), 4);
+
+        let (start, end) = DatePreset::LastDays(30).range(now);
+        assert_eq!(end - start, chrono::Duration::days(30));
+    }
+
+    #[test]
+    fn osc52_sequence_encodes_text_and_wraps_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn recent_projects_are_pinned_in_most_recent_order() {
+        let mut recent = Vec::new();
+        for id in [3, 5, 3, 1, 2, 4, 6] {
+            remember_recent_project(&mut recent, id);
+        }
+        assert_eq!(recent, vec![6, 4, 2, 1, 3]);
+
+        let alphabetical = vec![1, 2, 3, 4, 5, 6, 7];
+        let pinned = pin_recent(alphabetical, &[6, 99, 2], |&id| id);
+        assert_eq!(pinned, vec![6, 2, 1, 3, 4, 5, 7]);
+    }
+
+    #[test]
+    fn markdown_table_escapes_pipes_and_newlines() {
+        let rows = vec![
+            vec![
+                "1.50".to_string(),
+                "Web".to_string(),
+                "Fix a|b\nbug".to_string(),
+            ],
+            vec!["0.25".to_string(), String::new(), "Standup".to_string()],
+        ];
+        let table = markdown_table(&["Hours", "Project", "Description"], &rows);
+
+        assert_eq!(
+            table,
+            "| Hours | Project | Description |\n\
+             | --- | --- | --- |\n\
+             | 1.50 | Web | Fix a\\|b bug |\n\
+             | 0.25 |  | Standup |\n"
+        );
+    }
+
+    #[test]
+    fn format_compact_duration_shows_seconds() {
+        assert_eq!(format_compact_duration(0), "0s");
+        assert_eq!(format_compact_duration(42), "42s");
+    }
+
+    #[test]
+    fn format_compact_duration_shows_minutes_and_seconds() {
+        assert_eq!(format_compact_duration(60), "1m");
+        assert_eq!(format_compact_duration(125), "2m 5s");
+    }
+
+    #[test]
+    fn format_compact_duration_shows_hours_minutes_and_seconds() {
+        assert_eq!(format_compact_duration(3600), "1h");
+        assert_eq!(format_compact_duration(3723), "1h 2m 3s");
+    }
+
+    #[test]
+    fn quick_project_filter_narrows_then_clears() {
+        let narrowed = quick_project_filter(&HashSet::from([1, 2]), 2);
+        assert_eq!(narrowed, HashSet::from([2]));
+        assert!(quick_project_filter(&narrowed, 2).is_empty());
+        assert_eq!(quick_project_filter(&narrowed, 3), HashSet::from([3]));
+    }
+
+    #[test]
+    fn day_jump_moves_to_next_day_block() {
+        let days = [1, 1, 1, 2, 2, 3];
+        assert_eq!(day_jump_target(&days, 0, true), Some(3));
+        assert_eq!(day_jump_target(&days, 4, true), Some(5));
+        assert_eq!(day_jump_target(&days, 5, true), None);
     }
 
     #[test]
-    fn formats_rate_limit_reset_duration_as_minutes_and_seconds() {
-        assert_eq!(format_rate_limit_reset_duration(60), "1m");
-        assert_eq!(format_rate_limit_reset_duration(125), "2m 5s");
+    fn day_jump_moves_to_previous_day_block_start() {
+        let days = [1, 1, 1, 2, 2, 3];
+        assert_eq!(day_jump_target(&days, 5, false), Some(3));
+        assert_eq!(day_jump_target(&days, 4, false), Some(0));
+        assert_eq!(day_jump_target(&days, 2, false), None);
+        assert_eq!(day_jump_target::<i32>(&[], 0, false), None);
     }
 
     #[test]
-    fn formats_rate_limit_reset_duration_as_hours_minutes_and_seconds() {
-        assert_eq!(format_rate_limit_reset_duration(3600), "1h");
-        assert_eq!(format_rate_limit_reset_duration(3723), "1h 2m 3s");
+    fn quit_prompt_only_when_work_is_pending_or_partially_failed() {
+        assert_eq!(quit_prompt(0, false), None);
+        assert_eq!(
+            quit_prompt(1, false),
+            Some("Operations in progress, quit anyway? (y/N)")
+        );
+        assert!(quit_prompt(0, true).is_some());
+    }
+
+    #[test]
+    fn display_total_counts_running_entry_elapsed_time() {
+        let now = chrono::Utc::now();
+        let start = now - chrono::Duration::minutes(10);
+        let finished = timed_entry(1, now - chrono::Duration::hours(2), 1800);
+        let running = timed_entry(2, start, -start.timestamp());
+        let entries = vec![finished, running];
+
+        let total: i64 = entries
+            .iter()
+            .map(|e| entry_display_seconds(e, None, now))
+            .sum();
+        assert_eq!(total, 1800 + 600);
+
+        let group = GroupedTimeEntry {
+            description: None,
+            project_id: None,
+            date: None,
+            total_duration: entries.iter().map(|e| e.duration).sum(),
+            entries,
+        };
+        assert_eq!(group_display_seconds(&group, Some(15), now), 1800 + 600);
+    }
+
+    #[test]
+    fn copied_rows_show_a_running_entry_as_elapsed_time() {
+        let now = chrono::Utc::now();
+        let start = now - chrono::Duration::minutes(30);
+        let running = timed_entry(1, start, -start.timestamp());
+        let finished = timed_entry(2, now - chrono::Duration::hours(3), 600);
+        let mut app = super::App::new(
+            vec![running, finished],
+            now - chrono::Duration::days(1),
+            now,
+            Some(15),
+            Vec::new(),
+            None,
+            None,
+            None,
+            std::sync::Arc::new(crate::test_support::in_memory_db()),
+            std::collections::HashMap::new(),
+            now - chrono::Duration::days(30),
+            crate::config::ProjectSortMethod::default(),
+            crate::config::PersistedFilter::default(),
+        );
+        app.show_rounded = true;
+
+        app.show_grouped = false;
+        let row = |app: &super::App, id: i64| {
+            let index = app.time_entries.iter().position(|e| e.id == id).unwrap();
+            app.row_fields(index, now).unwrap()
+        };
+        assert_eq!(row(&app, 1)[1], "0.50");
+        assert_eq!(row(&app, 2)[1], "0.25");
+
+        app.show_grouped = true;
+        app.grouped_entries = vec![GroupedTimeEntry {
+            description: None,
+            project_id: None,
+            date: None,
+            total_duration: app.time_entries.iter().map(|e| e.duration).sum(),
+            entries: app.time_entries.clone(),
+        }];
+        assert_eq!(app.row_fields(0, now).unwrap()[0], "0.67");
     }
 }